@@ -1,33 +1,29 @@
 use aze_enc::{ keygen, mask, remask, inter_unmask, final_unmask, CardCipher };
 use aze_lib::accounts::create_basic_aze_player_account;
 use aze_lib::client::{
-    self, create_aze_client, AzeAccountTemplate, AzeClient, AzeGameMethods, AzeTransactionTemplate,
+    self, create_aze_client, AzeClient, AzeGameMethods, AzeTransactionTemplate,
     SendCardTransactionData,
     GenPrivateKeyTransactionData,
-    ShuffleCardTransactionData,
     RemaskTransactionData,
     SetCardsTransactionData,
     UnmaskTransactionData,
     InterUnmaskTransactionData,
     SendUnmaskedCardsTransactionData,
     SetHandTransactionData,
-    SendCommunityCardsTransactionData,
 };
 use aze_lib::constants::{
-    FIRST_PLAYER_INDEX, HIGHEST_BET, NO_OF_PLAYERS, PLAYER_INITIAL_BALANCE, SMALL_BUY_IN_AMOUNT,
-    PLAYER_DATA_SLOT, DEFAULT_ACTION_TYPE, PLAYER_CARD1_SLOT, PLAYER_CARD2_SLOT, TEMP_CARD_SLOT,
+    MAX_HOLE_CARDS, NO_OF_PLAYERS, SMALL_BUY_IN_AMOUNT,
+    PLAYER_DATA_SLOT, PLAYER_CARD1_SLOT, PLAYER_CARD4_SLOT, TEMP_CARD_SLOT,
 };
 use aze_lib::executor::execute_tx_and_sync;
+pub use aze_lib::host::{consume_game_notes, create_aze_game_account};
 use aze_lib::notes::{consume_notes, mint_note};
-use aze_lib::storage::GameStorageSlotData;
+use aze_lib::protocol::inter_unmask_target_index;
 use aze_types::accounts::{
     AccountCreationError, AccountCreationRequest, AccountCreationResponse,
     PlayerAccountCreationRequest, PlayerAccountCreationResponse,
 };
-use miden_client::client::{
-    accounts::{AccountStorageMode, AccountTemplate},
-    transactions::transaction_request::TransactionTemplate,
-};
+use miden_client::client::accounts::AccountTemplate;
 use miden_lib::AuthScheme;
 use miden_objects::{
     accounts::{AccountId, AuthSecretKey},
@@ -35,54 +31,8 @@ use miden_objects::{
     notes::NoteType,
     Felt, FieldElement
 };
-use tokio::time::{ sleep, Duration };
 use ecgfp5::scalar::Scalar;
 
-pub async fn create_aze_game_account(
-    player_account_ids: Vec<u64>,
-    small_blind: u8,
-    buy_in: u64,
-) -> Result<AccountId, AccountCreationError> {
-    let mut client: AzeClient = create_aze_client();
-    let slot_data = GameStorageSlotData::new(
-        small_blind,
-        buy_in as u8,
-        NO_OF_PLAYERS,
-        FIRST_PLAYER_INDEX,
-        HIGHEST_BET,
-        PLAYER_INITIAL_BALANCE,
-    );
-
-    let (game_account, _) = client
-        .new_game_account(
-            AzeAccountTemplate::GameAccount {
-                mutable_code: false,
-                storage_mode: AccountStorageMode::Local,
-            },
-            Some(slot_data),
-        )
-        .unwrap();
-
-    let game_account_id = game_account.id();
-
-    // Send note for shuffling and encryption
-    let sender_account_id = game_account_id;
-    let target_account_id = AccountId::try_from(player_account_ids[0]).unwrap();
-    let shuffle_card_data = ShuffleCardTransactionData::new(
-        sender_account_id,
-        target_account_id,
-        [DEFAULT_ACTION_TYPE, player_account_ids[1], player_account_ids[2], player_account_ids[3]]
-    );
-
-    let transaction_template = AzeTransactionTemplate::ShuffleCard(shuffle_card_data);
-    let txn_request = client
-        .build_aze_shuffle_card_tx_request(transaction_template)
-        .unwrap();
-    execute_tx_and_sync(&mut client, txn_request.clone()).await;
-    
-    Ok(game_account_id)
-}
-
 pub async fn create_aze_player_account(
     identifier: String,
 ) -> Result<AccountId, AccountCreationError> {
@@ -128,21 +78,6 @@ pub async fn create_aze_player_account(
     Ok(player_account.id())
 }
 
-pub async fn consume_game_notes(account_id: AccountId) {
-    let mut client: AzeClient = create_aze_client();
-    client.sync_state().await.unwrap();
-    let account = client.get_account(account_id).unwrap();
-    let consumable_notes = client.get_consumable_notes(Some(account_id)).unwrap();
-    println!("Consumable notes: {:?}", consumable_notes.len());
-
-    for consumable_note in consumable_notes {
-        let tx_template = TransactionTemplate::ConsumeNotes(account_id, vec![consumable_note.note.id()]);
-        let tx_request = client.build_transaction_request(tx_template).unwrap();
-        execute_tx_and_sync(&mut client, tx_request).await;
-        sleep(Duration::from_secs(5)).await;
-    }
-}
-
 pub async fn enc_action(action_type: u64, account_id: AccountId, target_account: AccountId) {
     let mut client: AzeClient = create_aze_client();
     let (player_account, _) = client.get_account(account_id).unwrap();
@@ -184,19 +119,16 @@ pub async fn enc_action(action_type: u64, account_id: AccountId, target_account:
     execute_tx_and_sync(&mut client, txn_request.clone()).await;
 }
 
-pub async fn p2p_unmask_flow(sender_account_id: AccountId, cards: [[Felt; 4]; 3]) -> Result<(), String> {
+pub async fn p2p_unmask_flow(sender_account_id: AccountId, cards: [[Felt; 4]; MAX_HOLE_CARDS]) -> Result<(), String> {
     let mut client: AzeClient = create_aze_client();
     let (player_account, _) = client.get_account(sender_account_id).unwrap();
 
     let player_data = player_account.storage().get_item(PLAYER_DATA_SLOT).as_elements().to_vec();
     let player_ids = [player_data[1].as_int(), player_data[2].as_int(), player_data[3].as_int()];
-    let action_type = player_data[0].as_int() as u8;
+    let action_type = player_data[0].as_int();
 
-    let index_bound = NO_OF_PLAYERS * (NO_OF_PLAYERS - 1);
-    let modulo = action_type % index_bound;
-    let next_player_idx = ((modulo as f64) / (NO_OF_PLAYERS as f64)).ceil() as u8;
-    
-    let receiver_account_id = AccountId::try_from(player_ids[next_player_idx as usize]).unwrap();
+    let next_player_idx = inter_unmask_target_index(action_type);
+    let receiver_account_id = AccountId::try_from(player_ids[next_player_idx]).unwrap();
     // send inter-unmask note
     let inter_unmask_data = InterUnmaskTransactionData::new(
         sender_account_id,
@@ -217,8 +149,8 @@ pub async fn self_unmask(account_id: AccountId, card_slot: u8) -> Result<(), Str
     let mut client: AzeClient = create_aze_client();
     let (player_account, _) = client.get_account(account_id).unwrap();
 
-    let mut cards: [[Felt; 4]; 3] = [[Felt::ZERO; 4]; 3];
-    for (i, slot) in (TEMP_CARD_SLOT..TEMP_CARD_SLOT + 3).enumerate() {
+    let mut cards: [[Felt; 4]; MAX_HOLE_CARDS] = [[Felt::ZERO; 4]; MAX_HOLE_CARDS];
+    for (i, slot) in (TEMP_CARD_SLOT..TEMP_CARD_SLOT + MAX_HOLE_CARDS as u8).enumerate() {
         let card_digest = player_account.storage().get_item(slot);
         cards[i] = card_digest.into();
     }
@@ -242,7 +174,7 @@ pub async fn self_unmask(account_id: AccountId, card_slot: u8) -> Result<(), Str
     Ok(())
 }
 
-pub async fn set_community_cards(account_id: AccountId, receiver_account_id: AccountId, cards: [[Felt; 4]; 3], card_slot: u8) {
+pub async fn set_community_cards(account_id: AccountId, receiver_account_id: AccountId, cards: [[Felt; 4]; MAX_HOLE_CARDS], card_slot: u8) {
     let mut client: AzeClient = create_aze_client();
     let (player_account, _) = client.get_account(account_id).unwrap();
 
@@ -260,30 +192,12 @@ pub async fn set_community_cards(account_id: AccountId, receiver_account_id: Acc
     execute_tx_and_sync(&mut client, txn_request.clone()).await;
 }
 
-pub async fn send_community_cards(account_id: AccountId, receiver_account_id: AccountId, cards: [[Felt; 4]; 3], phase: u8) {
-    let mut client: AzeClient = create_aze_client();
-    let (player_account, _) = client.get_account(account_id).unwrap();
-
-    // send set cards note to game account
-    let send_cards_data = SendCommunityCardsTransactionData::new(
-        account_id,
-        receiver_account_id,
-        &cards,
-        phase
-    );
-    let transaction_template = AzeTransactionTemplate::SendCommunityCards(send_cards_data);
-    let txn_request = client
-        .build_send_community_cards_tx_request(transaction_template)
-        .unwrap();
-    execute_tx_and_sync(&mut client, txn_request.clone()).await;
-}
-
 pub async fn send_unmasked_cards(account_id: AccountId, requester_id: AccountId) {
     let mut client: AzeClient = create_aze_client();
     let (player_account, _) = client.get_account(account_id).unwrap();
 
-    let mut cards: [[Felt; 4]; 3] = [[Felt::ZERO; 4]; 3];
-    for (i, slot) in (TEMP_CARD_SLOT..TEMP_CARD_SLOT + 3).enumerate() {
+    let mut cards: [[Felt; 4]; MAX_HOLE_CARDS] = [[Felt::ZERO; 4]; MAX_HOLE_CARDS];
+    for (i, slot) in (TEMP_CARD_SLOT..TEMP_CARD_SLOT + MAX_HOLE_CARDS as u8).enumerate() {
         let card_digest = player_account.storage().get_item(slot);
         cards[i] = card_digest.into();
     }
@@ -307,8 +221,8 @@ pub async fn commit_hand(account_id: AccountId, game_account_id: AccountId, play
 
     // send commit hand note to game account
 
-    let mut cards: [[Felt; 4]; 2] = [[Felt::ZERO; 4]; 2];
-    for (i, slot) in (PLAYER_CARD1_SLOT..PLAYER_CARD2_SLOT + 1).enumerate() {
+    let mut cards: [[Felt; 4]; MAX_HOLE_CARDS] = [[Felt::ZERO; 4]; MAX_HOLE_CARDS];
+    for (i, slot) in (PLAYER_CARD1_SLOT..=PLAYER_CARD4_SLOT).enumerate() {
         let card = player_account.storage().get_item(slot);
         cards[i] = card.into();
     }
@@ -1,4 +1,6 @@
 use crate::actions;
+use aze_lib::auth::RequestSigner;
+use aze_lib::client::{create_aze_client, AzeClient};
 use aze_lib::gamestate::Check_Action;
 use aze_lib::utils::Ws_config;
 use aze_lib::{
@@ -8,6 +10,7 @@ use aze_lib::{
 use aze_types::actions::{ActionType, GameActionResponse};
 use clap::{Parser, ValueEnum};
 use dialoguer::{Input, Select};
+use miden_objects::accounts::AccountId;
 #[derive(Debug, Clone, Parser)]
 pub struct ActionCmd {}
 
@@ -71,7 +74,7 @@ impl ActionCmd {
     }
 }
 
-async fn send_action(
+pub async fn send_action(
     player_id: u64,
     game_id: u64,
     action_type: ActionType,
@@ -80,6 +83,8 @@ async fn send_action(
 ) -> Result<GameActionResponse, String> {
     let amount_u64 = amount.map(|value| value as u64);
     let ws_url = Ws_config::load(ws_config_path).url.unwrap();
+    let client: AzeClient = create_aze_client();
+    let signer = RequestSigner::from_client(&client, AccountId::try_from(player_id).unwrap())?;
     let result = validate_action(
         Check_Action {
             action_type,
@@ -87,6 +92,7 @@ async fn send_action(
         },
         ws_url,
         player_id,
+        &signer,
     )
     .await.unwrap();
     if result == false {
@@ -99,5 +105,6 @@ async fn send_action(
         ActionType::Call => actions::call(player_id, game_id, ws_config_path).await,
         ActionType::Check => actions::check(player_id, game_id, ws_config_path).await,
         ActionType::Fold => actions::fold(player_id, game_id, ws_config_path).await,
+        ActionType::Straddle => Err(String::from("The table posts the straddle")),
     }
 }
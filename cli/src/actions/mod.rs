@@ -1,12 +1,14 @@
+use aze_lib::auth::RequestSigner;
 use aze_lib::client::{
     create_aze_client, AzeAccountTemplate, AzeClient, AzeGameMethods, AzeTransactionTemplate,
     PlayBetTransactionData, PlayCallTransactionData, PlayCheckTransactionData,
     PlayFoldTransactionData, PlayRaiseTransactionData,
 };
-use aze_lib::constants::{HIGHEST_BET_SLOT, SMALL_BUY_IN_AMOUNT};
+use aze_lib::constants::{BIG_BLIND_SLOT, HIGHEST_BET_SLOT, SMALL_BLIND_SLOT, SMALL_BUY_IN_AMOUNT};
 use aze_lib::executor::execute_tx_and_sync;
 use aze_lib::storage::GameStorageSlotData;
-use aze_types::actions::{GameActionError, GameActionResponse};
+use aze_types::actions::{ActionType, GameActionError, GameActionResponse};
+use aze_types::events::GameEvent;
 use miden_client::client::{
     accounts::{AccountStorageMode, AccountTemplate},
     transactions::transaction_request::TransactionTemplate,
@@ -16,7 +18,7 @@ use miden_objects::{
     notes::NoteType,
 };
 
-use aze_lib::utils::{broadcast_message, read_player_data, Ws_config};
+use aze_lib::utils::{broadcast_event, Ws_config};
 
 pub async fn raise(
     player_id: u64,
@@ -27,16 +29,18 @@ pub async fn raise(
     let mut client: AzeClient = create_aze_client();
     let player_account_id = AccountId::try_from(player_id).unwrap();
     let game_account_id = AccountId::try_from(game_id).unwrap();
+    let signer = RequestSigner::from_client(&client, player_account_id)?;
     let ws_url = Ws_config::load(ws_config_path).url.unwrap();
 
-    let _ = broadcast_message(
+    let _ = broadcast_event(
         game_account_id.to_string(),
         ws_url.clone(),
-        format!(
-            "Player: {} plays raise by amount: {}",
+        GameEvent::PlayerActed {
             player_id,
-            amount.unwrap()
-        ),
+            action: ActionType::Raise,
+            amount: amount.map(u64::from),
+        },
+        &signer,
     )
     .await;
 
@@ -68,6 +72,7 @@ pub async fn call(
     let mut client: AzeClient = create_aze_client();
     let player_account_id = AccountId::try_from(player_id).unwrap();
     let game_account_id = AccountId::try_from(game_id).unwrap();
+    let signer = RequestSigner::from_client(&client, player_account_id)?;
 
     let mut ws_url: String = String::new();
 
@@ -80,10 +85,15 @@ pub async fn call(
             eprintln!("Ws_config DNE, use init or connect command before action");
         }
     }
-    let _ = broadcast_message(
+    let _ = broadcast_event(
         game_account_id.to_string(),
         ws_url.clone(),
-        format!("Player: {} plays call ", player_id),
+        GameEvent::PlayerActed {
+            player_id,
+            action: ActionType::Call,
+            amount: None,
+        },
+        &signer,
     )
     .await;
 
@@ -111,6 +121,7 @@ pub async fn check(
     let mut client: AzeClient = create_aze_client();
     let player_account_id = AccountId::try_from(player_id).unwrap();
     let game_account_id = AccountId::try_from(game_id).unwrap();
+    let signer = RequestSigner::from_client(&client, player_account_id)?;
 
     let mut ws_url: String = String::new();
 
@@ -123,10 +134,15 @@ pub async fn check(
             eprintln!("Ws_config DNE, use init or connect command before action");
         }
     }
-    let _ = broadcast_message(
+    let _ = broadcast_event(
         game_account_id.to_string(),
         ws_url.clone(),
-        format!("Player: {} plays check", player_id),
+        GameEvent::PlayerActed {
+            player_id,
+            action: ActionType::Check,
+            amount: None,
+        },
+        &signer,
     )
     .await;
 
@@ -154,6 +170,7 @@ pub async fn fold(
     let mut client: AzeClient = create_aze_client();
     let player_account_id = AccountId::try_from(player_id).unwrap();
     let game_account_id = AccountId::try_from(game_id).unwrap();
+    let signer = RequestSigner::from_client(&client, player_account_id)?;
 
     let mut ws_url: String = String::new();
 
@@ -167,10 +184,15 @@ pub async fn fold(
         }
     }
 
-    let _ = broadcast_message(
+    let _ = broadcast_event(
         game_account_id.to_string(),
         ws_url.clone(),
-        format!("Player: {} plays fold", player_id),
+        GameEvent::PlayerActed {
+            player_id,
+            action: ActionType::Fold,
+            amount: None,
+        },
+        &signer,
     )
     .await;
 
@@ -194,11 +216,13 @@ pub async fn bet(
     player_id: u64,
     game_id: u64,
     amount: u8,
+    action_type: ActionType,
     ws_config_path: &std::path::PathBuf,
 ) -> Result<GameActionResponse, String> {
     let mut client: AzeClient = create_aze_client();
     let player_account_id = AccountId::try_from(player_id).unwrap();
     let game_account_id = AccountId::try_from(game_id).unwrap();
+    let signer = RequestSigner::from_client(&client, player_account_id)?;
     let mut ws_url: String = String::new();
 
     match Ws_config::load(ws_config_path).url {
@@ -210,10 +234,15 @@ pub async fn bet(
             eprintln!("Ws_config DNE, use init or connect command before action");
         }
     }
-    let _ = broadcast_message(
+    let _ = broadcast_event(
         game_account_id.to_string(),
         ws_url.clone(),
-        format!("Player: {} bet amount: {}", read_player_data().expect("Failed to read player data from Player.toml"), amount),
+        GameEvent::PlayerActed {
+            player_id,
+            action: action_type,
+            amount: Some(amount as u64),
+        },
+        &signer,
     )
     .await;
 
@@ -239,9 +268,8 @@ pub async fn small_blind(
     game_id: u64,
     ws_config_path: &std::path::PathBuf,
 ) -> Result<GameActionResponse, String> {
-    // request small blind amount from game account
-    let small_blind = 5; // for now
-    bet(player_id, game_id, small_blind, ws_config_path).await
+    let small_blind = blind_of(game_id, SMALL_BLIND_SLOT).unwrap_or(5);
+    bet(player_id, game_id, small_blind, ActionType::SmallBlind, ws_config_path).await
 }
 
 pub async fn big_blind(
@@ -249,7 +277,16 @@ pub async fn big_blind(
     game_id: u64,
     ws_config_path: &std::path::PathBuf,
 ) -> Result<GameActionResponse, String> {
-    // request big blind amount from game account
-    let big_blind = 10; // for now
-    bet(player_id, game_id, big_blind, ws_config_path).await
+    let big_blind = blind_of(game_id, BIG_BLIND_SLOT).unwrap_or(10);
+    bet(player_id, game_id, big_blind, ActionType::BigBlind, ws_config_path).await
+}
+
+// blind the game account set for the hand being played, tournaments raise it between hands. None
+// when the game account isn't in the local store
+fn blind_of(game_id: u64, blind_slot: u8) -> Option<u8> {
+    let client: AzeClient = create_aze_client();
+    let game_account_id = AccountId::try_from(game_id).ok()?;
+    let (game_account, _) = client.get_account(game_account_id).ok()?;
+    let blind = game_account.storage().get_item(blind_slot).as_elements()[0].as_int();
+    u8::try_from(blind).ok()
 }
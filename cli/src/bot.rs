@@ -0,0 +1,114 @@
+use crate::action::send_action;
+use crate::consume_notes::consume_and_step;
+use aze_lib::client::{ create_aze_client, AzeClient };
+use aze_lib::constants::PLAYER_CARD1_SLOT;
+use aze_lib::gamestate::{ Check_Action, PokerGame };
+use aze_lib::strategy::create_strategy;
+use aze_lib::utils::{ get_game_state, Ws_config };
+use aze_types::actions::{ActionType, StrategyType};
+use clap::Parser;
+use miden_objects::accounts::AccountId;
+use std::path::PathBuf;
+use tokio::task::LocalSet;
+use tokio::time::{ sleep, Duration };
+
+#[derive(Debug, Clone, Parser)]
+pub struct BotCmd {
+    #[arg(short, long)]
+    player_id: u64,
+
+    #[arg(short, long)]
+    game_id: u64,
+
+    #[arg(short, long, value_enum, default_value = "always-call")]
+    strategy: StrategyType,
+
+    // seed for the random strategy, to replay a session
+    #[arg(long)]
+    seed: Option<u64>,
+}
+
+impl BotCmd {
+    pub async fn execute(&self, ws_config_path: &PathBuf) -> Result<(), String> {
+        let mut client: AzeClient = create_aze_client();
+        let account_id = AccountId::try_from(self.player_id).unwrap();
+        let game_account_id = AccountId::try_from(self.game_id).unwrap();
+        let ws_url = Ws_config::load(ws_config_path)
+            .url
+            .ok_or("Ws_config DNE, use init or connect command before bot")?;
+        let mut strategy = create_strategy(self.strategy, self.seed);
+
+        let local_set = LocalSet::new();
+        local_set.run_until(async {
+            loop {
+                consume_and_step(&mut client, account_id, game_account_id).await;
+
+                let game = match get_game_state(ws_url.clone()).await {
+                    Ok(game) => game,
+                    Err(_) => {
+                        sleep(Duration::from_secs(5)).await;
+                        continue;
+                    }
+                };
+
+                // the table posts the straddle for us
+                if game.current_player_id() == self.player_id
+                    && game.pending_blind() != Some(ActionType::Straddle)
+                {
+                    // wait until our hole cards are unmasked before playing them
+                    let hole_card_count = game.variant().hole_cards();
+                    if let Some(hole_cards) = read_hole_cards(&client, account_id, hole_card_count) {
+                        let check_action = strategy.act(&game, self.player_id, &hole_cards);
+                        println!("Bot plays {:?}", check_action);
+                        let (action_type, amount) = fit_action(&game, self.player_id, check_action);
+                        if let Err(e) = send_action(
+                            self.player_id,
+                            self.game_id,
+                            action_type,
+                            amount,
+                            ws_config_path,
+                        ).await {
+                            eprintln!("Bot action failed: {}", e);
+                        }
+                    }
+                }
+
+                sleep(Duration::from_secs(5)).await;
+            }
+        }).await;
+        Ok(())
+    }
+}
+
+// bets are a single byte on-chain, so a raise to more than that is cut down to the largest raise
+// the table allows, or turns into a check or call when there is none
+fn fit_action(game: &PokerGame, player_id: u64, check_action: Check_Action) -> (ActionType, Option<u8>) {
+    let amount = match check_action.amount {
+        Some(amount) => amount,
+        None => return (check_action.action_type, None),
+    };
+    let to_call = game.amount_to_call(player_id).unwrap_or(0);
+    let balance = game.balance(player_id).unwrap_or(0);
+    let largest = (u8::MAX as u64).saturating_sub(game.current_bet()).min(amount);
+    match (1..=largest)
+        .rev()
+        .find(|amount| to_call + amount <= balance && game.can_raise(*amount))
+    {
+        Some(amount) => (check_action.action_type, Some(amount as u8)),
+        None if to_call == 0 => (ActionType::Check, None),
+        None => (ActionType::Call, None),
+    }
+}
+
+fn read_hole_cards(client: &AzeClient, account_id: AccountId, count: usize) -> Option<Vec<u64>> {
+    let (player_account, _) = client.get_account(account_id).unwrap();
+    let cards: Vec<u64> = (PLAYER_CARD1_SLOT..PLAYER_CARD1_SLOT + count as u8)
+        .map(|slot| player_account.storage().get_item(slot).as_elements()[0].as_int())
+        .collect();
+    let is_unmasked = |card: &u64| (1..=52).contains(card);
+    if cards.iter().all(is_unmasked) {
+        Some(cards)
+    } else {
+        None
+    }
+}
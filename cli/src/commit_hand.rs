@@ -1,5 +1,9 @@
 use crate::accounts::{ commit_hand };
+use aze_lib::auth::RequestSigner;
+use aze_lib::client::{ create_aze_client, AzeClient };
 use aze_lib::constants::{ PLAYER_CARD1_SLOT, PLAYER_CARD2_SLOT };
+use aze_lib::utils::{ broadcast_event, Ws_config };
+use aze_types::events::GameEvent;
 use clap::Parser;
 use miden_objects::{ 
     accounts::AccountId,
@@ -17,7 +21,7 @@ pub struct CommitHandCmd {
 }
 
 impl CommitHandCmd {
-    pub async fn execute(&self) -> Result<(), String> {
+    pub async fn execute(&self, ws_config_path: &std::path::PathBuf) -> Result<(), String> {
         let sender_account_id = AccountId::try_from(self.player_id).unwrap();
         let game_account_id = AccountId::try_from(self.game_id).unwrap();
 
@@ -36,7 +40,28 @@ impl CommitHandCmd {
             .interact()
             .expect("Failed to get action type");
         
-        commit_hand(sender_account_id, game_account_id, player_hand as u8 + 1).await;
+        let hand_type = player_hand as u8 + 1;
+        commit_hand(sender_account_id, game_account_id, hand_type).await;
+
+        let client: AzeClient = create_aze_client();
+        let signer = RequestSigner::from_client(&client, sender_account_id)?;
+        match Ws_config::load(ws_config_path).url {
+            Some(ws_url) => {
+                let _ = broadcast_event(
+                    game_account_id.to_string(),
+                    ws_url,
+                    GameEvent::HandCommitted {
+                        player_id: self.player_id,
+                        hand_type: hand_type as u64,
+                    },
+                    &signer,
+                )
+                .await;
+            }
+            None => {
+                eprintln!("Ws_config DNE, use init or connect command before action");
+            }
+        }
 
         Ok(())
     }
@@ -1,43 +1,271 @@
 use clap::Parser;
 use std::error::Error;
 use std::path::PathBuf;
-use futures_util::{StreamExt, SinkExt}; // Import the required traits
+use futures_util::{Sink, Stream, StreamExt, SinkExt}; // Import the required traits
+use tokio::sync::mpsc;
+use tokio::time::{sleep, Duration};
 use tokio_tungstenite::connect_async;
 use tokio_tungstenite::tungstenite::protocol::Message;
-use aze_lib::utils::Ws_config;
+use tokio_tungstenite::tungstenite::Error as WsError;
+use url::Url;
+use aze_lib::auth::RequestSigner;
+use aze_lib::client::{create_aze_client, AzeClient};
+use aze_lib::hands::HandCategory;
+use aze_lib::utils::{
+    broadcast_event, card_from_number, read_player_data, read_player_id, subscribe_request,
+    Ws_config,
+};
+use aze_types::events::{EventMessage, GameEvent, EVENT_PROTOCOL_VERSION};
 use ansi_term::Colour::{Blue, Green, Red, Yellow};
+use miden_objects::accounts::AccountId;
+
+// longest wait between two reconnection attempts
+const MAX_BACKOFF_SECS: u64 = 30;
 
 #[derive(Debug, Clone, Parser)]
 pub struct ConnectCmd {
     #[arg(short, long, help = "WebSocket server URL")]
     url: String,
+
+    #[arg(long, help = "Send every line typed as a chat message to the table")]
+    chat: bool,
+}
+
+// lines typed by the player, sent as signed chat messages
+pub(crate) struct ChatInput {
+    identifier: String,
+    lines: mpsc::UnboundedReceiver<String>,
+}
+
+impl ChatInput {
+    // the next line typed, signed and ready to go over the websocket
+    async fn next_message(
+        chat: &mut Option<ChatInput>,
+        signer: Option<&RequestSigner>,
+    ) -> Option<String> {
+        let (chat, signer) = match (chat, signer) {
+            (Some(chat), Some(signer)) => (chat, signer),
+            _ => return std::future::pending().await,
+        };
+        let message = chat.lines.recv().await?;
+        let request = signer.sign(&GameEvent::Chat {
+            player_id: signer.account_id(),
+            identifier: chat.identifier.clone(),
+            message,
+        });
+        Some(serde_json::to_string(&request).unwrap())
+    }
 }
 
 impl ConnectCmd {
     pub async fn execute(&self, config_path: &PathBuf) -> Result<(), Box<dyn Error>> {
+        // only the game's players may connect, the handshake is signed with the player's key
+        let (player_id, identifier) = match (read_player_id(), read_player_data()) {
+            (Some(player_id), Some(identifier)) => (player_id, identifier),
+            _ => return Err("Register a player before connecting".into()),
+        };
+        let client: AzeClient = create_aze_client();
+        let account_id = AccountId::try_from(player_id).map_err(|e| e.to_string())?;
+        let signer = RequestSigner::from_client(&client, account_id)?;
+
         // Connect to the WebSocket server
-        let (ws_stream, _) = connect_async(&self.url).await?;
+        let (ws_stream, _) = connect_async(subscribe_request(&self.url, &signer)?).await?;
         let mut config = Ws_config::load(config_path);
         config.url = Some(self.url.to_string());
         config.save(config_path);
         println!("Connected to the game server at {}", self.url);
 
-        let (mut _write, mut read) = ws_stream.split();
+        // let the table know who joined, the url ends with the game id
+        if let Some(game_id) = self.url.rsplit('/').next() {
+            let _ = broadcast_event(
+                game_id.to_string(),
+                self.url.clone(),
+                GameEvent::PlayerJoined {
+                    player_id,
+                    identifier: identifier.clone(),
+                },
+                &signer,
+            )
+            .await;
+        }
 
-        // Read messages from the server
-        while let Some(message) = read.next().await {
-            match message {
-                Ok(msg) => match msg {
-                    Message::Text(text) => println!("{} {}", Yellow.bold().paint("Game Update: "), text),
-                    _ => (),
+        let mut chat = None;
+        if self.chat {
+            // stdin blocks, read it on its own thread
+            let (lines_tx, lines) = mpsc::unbounded_channel();
+            std::thread::spawn(move || {
+                for line in std::io::stdin().lines().map_while(Result::ok) {
+                    if lines_tx.send(line).is_err() {
+                        break;
+                    }
+                }
+            });
+            println!("Type a message and press enter to chat");
+            chat = Some(ChatInput { identifier, lines });
+        }
+
+        follow_events(&self.url, ws_stream, Some(&signer), chat).await
+    }
+}
+
+// reads messages from the server, on a dropped connection resumes after the last event seen.
+// Players sign every handshake, spectators connect without a signer
+pub(crate) async fn follow_events<S>(
+    url: &str,
+    ws_stream: S,
+    signer: Option<&RequestSigner>,
+    mut chat: Option<ChatInput>,
+) -> Result<(), Box<dyn Error>>
+where
+    S: Stream<Item = Result<Message, WsError>> + Sink<Message, Error = WsError> + Unpin,
+{
+    let mut last_sequence = read_events(ws_stream, 0, signer, &mut chat).await;
+    let mut backoff = 1;
+    loop {
+        eprintln!("{}", Red.bold().paint(format!("Connection lost, reconnecting in {}s", backoff)));
+        sleep(Duration::from_secs(backoff)).await;
+        let resume_url = replay_url(url, last_sequence)?;
+        let reconnected = match signer {
+            Some(signer) => connect_async(subscribe_request(&resume_url, signer)?).await,
+            None => connect_async(resume_url).await,
+        };
+        match reconnected {
+            Ok((ws_stream, _)) => {
+                println!("Reconnected to the game server, resuming after event {}", last_sequence);
+                backoff = 1;
+                last_sequence = read_events(ws_stream, last_sequence, signer, &mut chat).await;
+            }
+            Err(e) => {
+                eprintln!("{}", Red.bold().paint(format!("Error reconnecting: {}", e)));
+                backoff = (backoff * 2).min(MAX_BACKOFF_SECS);
+            }
+        }
+    }
+}
+
+// prints events and sends chat messages until the connection drops, returns the last sequence
+// number seen
+async fn read_events<S>(
+    ws_stream: S,
+    mut last_sequence: u64,
+    signer: Option<&RequestSigner>,
+    chat: &mut Option<ChatInput>,
+) -> u64
+where
+    S: Stream<Item = Result<Message, WsError>> + Sink<Message, Error = WsError> + Unpin,
+{
+    let (mut write, mut read) = ws_stream.split();
+    loop {
+        tokio::select! {
+            message = read.next() => match message {
+                Some(Ok(Message::Text(text))) => match serde_json::from_str::<EventMessage>(&text) {
+                    // already seen before reconnecting
+                    Ok(message) if message.sequence <= last_sequence => (),
+                    Ok(message) if message.version <= EVENT_PROTOCOL_VERSION => {
+                        last_sequence = message.sequence;
+                        match message.event {
+                            GameEvent::Chat { .. } => println!("{} {}", Green.bold().paint("Chat: "), describe_event(&message.event)),
+                            _ => println!("{} {}", Yellow.bold().paint("Game Update: "), describe_event(&message.event)),
+                        }
+                    }
+                    _ => println!("{} {}", Yellow.bold().paint("Game Update: "), text),
                 },
-                Err(e) => {
+                Some(Ok(_)) => (),
+                Some(Err(e)) => {
                     eprintln!("{}", Red.bold().paint(format!("Error receiving message: {}", e)));
                     break;
                 }
+                None => break,
+            },
+            Some(chat_message) = ChatInput::next_message(chat, signer) => {
+                if let Err(e) = write.send(Message::Text(chat_message)).await {
+                    eprintln!("{}", Red.bold().paint(format!("Error sending message: {}", e)));
+                    break;
+                }
             }
         }
+    }
+    last_sequence
+}
 
-        Ok(())
+fn replay_url(ws_url: &str, since: u64) -> Result<String, Box<dyn Error>> {
+    let mut url = Url::parse(ws_url)?;
+    url.query_pairs_mut().append_pair("since", &since.to_string());
+    Ok(url.to_string())
+}
+
+fn describe_event(event: &GameEvent) -> String {
+    match event {
+        GameEvent::PlayerJoined { player_id, identifier } => {
+            format!("Player: {} joined as {}", player_id, identifier)
+        }
+        GameEvent::SeatTaken { player_id, seat, buy_in } => {
+            format!("Player: {} took seat {} with {}", player_id, seat, buy_in)
+        }
+        GameEvent::SeatLeft { player_id, seat, balance } => {
+            format!("Player: {} left seat {} with {}", player_id, seat, balance)
+        }
+        GameEvent::SittingOut { player_id, sitting_out: true } => {
+            format!("Player: {} sits out", player_id)
+        }
+        GameEvent::SittingOut { player_id, sitting_out: false } => {
+            format!("Player: {} is back", player_id)
+        }
+        GameEvent::HandStarted { player_ids } => format!("New hand for players {:?}", player_ids),
+        GameEvent::PlayerActed { player_id, action, amount } => match amount {
+            Some(amount) => format!("Player: {} plays {:?} amount: {}", player_id, action, amount),
+            None => format!("Player: {} plays {:?}", player_id, action),
+        },
+        GameEvent::TurnChanged { player_id } => format!("Player: {} to act", player_id),
+        GameEvent::StreetAdvanced { phase } => format!("Phase advanced to {}", phase),
+        GameEvent::CommunityCardsRevealed { cards } => format!(
+            "Community Cards Revealed: {}",
+            cards.iter().map(|card| card_from_number(*card)).collect::<Vec<String>>().join(" ")
+        ),
+        // spectators only learn the hand at the showdown
+        GameEvent::HandCommitted { player_id, hand_type: 0 } => {
+            format!("Player: {} committed a hand", player_id)
+        }
+        GameEvent::HandCommitted { player_id, hand_type } => {
+            format!("Player: {} committed {}", player_id, hand_name(*hand_type))
+        }
+        GameEvent::ShowdownResult { winners, hand_type, pot, low_winners } => {
+            let mut result = format!(
+                "Player(s) {:?} win pot: {} with {}",
+                winners,
+                pot,
+                hand_name(*hand_type)
+            );
+            if !low_winners.is_empty() {
+                result.push_str(&format!(", Player(s) {:?} win the low", low_winners));
+            }
+            result
+        }
+        GameEvent::GameEnded => String::from("Game Ended"),
+        GameEvent::BlindsRaised { level, small_blind, big_blind } => {
+            format!("Blinds up to {}/{} at level {}", small_blind, big_blind, level)
+        }
+        GameEvent::PlayerEliminated { player_id, place } => {
+            format!("Player: {} is out in place {}", player_id, place)
+        }
+        GameEvent::TournamentEnded { placements } => format!(
+            "Tournament over, placements: {}",
+            placements
+                .iter()
+                .enumerate()
+                .map(|(i, player_id)| format!("{}. {}", i + 1, player_id))
+                .collect::<Vec<String>>()
+                .join(", ")
+        ),
+        GameEvent::Chat { identifier, message, .. } => format!("{}: {}", identifier, message),
     }
 }
+
+// committed hand types start at 1 for a royal flush
+fn hand_name(hand_type: u64) -> &'static str {
+    hand_type
+        .checked_sub(1)
+        .and_then(HandCategory::from_hand_type)
+        .map(|category| category.name())
+        .unwrap_or("unknown hand")
+}
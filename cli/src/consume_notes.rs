@@ -7,12 +7,10 @@ use crate::accounts::{
     send_unmasked_cards,
 };
 use aze_lib::client::{ create_aze_client, AzeClient };
-use aze_lib::constants::{ PLAYER_DATA_SLOT, PLAYER_CARD1_SLOT, TEMP_CARD_SLOT, REQUESTER_SLOT, PHASE_DATA_SLOT, FLOP_SLOT };
+use aze_lib::constants::{ PLAYER_DATA_SLOT, PLAYER_CARD1_SLOT, TEMP_CARD_SLOT };
+use aze_lib::protocol::{ next_steps, PlayerSnapshot, ProtocolStep };
 use clap::Parser;
-use miden_objects::{
-    accounts::AccountId,
-    Felt, FieldElement
-};
+use miden_objects::accounts::AccountId;
 use tokio::time::{ sleep, Duration };
 use tokio::task::LocalSet;
 
@@ -29,113 +27,74 @@ impl ConsumeNotesCmd {
     pub async fn execute(&self) -> Result<(), String> {
         let mut client: AzeClient = create_aze_client();
         let account_id = AccountId::try_from(self.player_id).unwrap();
+        let game_account_id = AccountId::try_from(self.game_id).unwrap();
         let local_set = LocalSet::new();
         local_set.run_until(async {
             loop {
-                let (player_account, _) = client.get_account(account_id).unwrap();
-                let player_data = player_account
-                    .storage()
-                    .get_item(PLAYER_DATA_SLOT)
-                    .as_elements()
-                    .to_vec();
-                let requester_info = player_account
-                    .storage()
-                    .get_item(REQUESTER_SLOT)
-                    .as_elements()
-                    .to_vec();
-                let action_type_pre = player_data[0].as_int();
-                let requester_id = requester_info[0].as_int();
-                let community_card = player_account.storage().get_item(TEMP_CARD_SLOT).as_elements().to_vec();
+                if !consume_and_step(&mut client, account_id, game_account_id).await {
+                    return
+                }
 
-                consume_game_notes(account_id).await;
+                sleep(Duration::from_secs(5)).await;
+            }
+        }).await;
+        Ok(())
+    }
+}
+
+// consumes the notes sent to the player and takes the protocol steps they trigger,
+// returns false once the community cards unmasking was started
+pub async fn consume_and_step(
+    client: &mut AzeClient,
+    account_id: AccountId,
+    game_account_id: AccountId,
+) -> bool {
+    let (player_account, _) = client.get_account(account_id).unwrap();
+    let pre = PlayerSnapshot::from_account(&player_account);
+
+    consume_game_notes(account_id).await;
 
-                let (player_account, _) = client.get_account(account_id).unwrap();
+    let (player_account, _) = client.get_account(account_id).unwrap();
+    let post = PlayerSnapshot::from_account(&player_account);
+
+    for step in next_steps(&pre, &post) {
+        match step {
+            ProtocolStep::Wait => (),
+            ProtocolStep::StartCommunityUnmask => {
+                p2p_unmask_flow(account_id, post.temp_cards).await;
+                return false;
+            }
+            ProtocolStep::SendUnmaskedCards { requester_id } => {
+                let requester_account_id = AccountId::try_from(requester_id).unwrap();
+                send_unmasked_cards(account_id, requester_account_id).await;
+            }
+            ProtocolStep::Remask { player_index } => {
                 let player_data = player_account
                     .storage()
                     .get_item(PLAYER_DATA_SLOT)
                     .as_elements()
                     .to_vec();
-                let requester_info = player_account
-                    .storage()
-                    .get_item(REQUESTER_SLOT)
-                    .as_elements()
-                    .to_vec();
-                let action_type = player_data[0].as_int();
-                let requester_id_post = requester_info[0].as_int();
-                let community_card_post = player_account.storage().get_item(TEMP_CARD_SLOT).as_elements().to_vec();
-
-                // if requester_id has changed post consumption
-                if requester_id != requester_id_post {
-
-                    if community_card != community_card_post {
-                        let mut cards: [[Felt; 4]; 3] = [[Felt::ZERO; 4]; 3];
-                        for (i, slot) in (TEMP_CARD_SLOT..TEMP_CARD_SLOT + 3).enumerate() {
-                            let card_digest = player_account.storage().get_item(slot);
-                            cards[i] = card_digest.into();
-                        }
-                        p2p_unmask_flow(account_id, cards).await;
-                        return
-                    }
-
-                    let requester_account_id = AccountId::try_from(requester_id_post).unwrap();
-                    send_unmasked_cards(account_id, requester_account_id).await;
-                }
-
-                // if action type hasn't changed post consumption, continue
-                if action_type == action_type_pre {
-                    sleep(Duration::from_secs(5)).await;
-                    continue;
-                } else if
-                    // check here if note triggered enc/dec action
-                    (1..4).contains(&action_type)
-                {
-                    let target_account = AccountId::try_from(
-                        player_data[action_type as usize]
-                    ).unwrap();
-                    enc_action(action_type, account_id, target_account).await;
-                } else if action_type == 4 {
-                    let target_account = AccountId::try_from(self.game_id).unwrap();
-                    enc_action(action_type, account_id, target_account).await;
-                } else if
-                    (5..13).contains(&action_type) ||
-                    (17..25).contains(&action_type) ||
-                    (29..37).contains(&action_type) ||
-                    (41..49).contains(&action_type)
-                {
-                    let mut cards: [[Felt; 4]; 3] = [[Felt::ZERO; 4]; 3];
-                    for (i, slot) in (TEMP_CARD_SLOT..TEMP_CARD_SLOT + 3).enumerate() {
-                        let card_digest = player_account.storage().get_item(slot);
-                        cards[i] = card_digest.into();
-                    }
-                    p2p_unmask_flow(account_id, cards).await;
-                } else if (13..17).contains(&action_type) {
-                    self_unmask(account_id, PLAYER_CARD1_SLOT).await;
-                } else if
-                    (25..29).contains(&action_type) ||
-                    (37..41).contains(&action_type) ||
-                    (49..53).contains(&action_type)
-                {
-                    self_unmask(account_id, TEMP_CARD_SLOT).await;
-                    // send cards to game account
-                    let game_account_id = AccountId::try_from(self.game_id).unwrap();
-                    let mut cards: [[Felt; 4]; 3] = [[Felt::ZERO; 4]; 3];
-                    for (i, slot) in (TEMP_CARD_SLOT..TEMP_CARD_SLOT + 3).enumerate() {
-                        let card_digest = player_account.storage().get_item(slot);
-                        cards[i] = card_digest.into();
-                    }
-                    let current_phase = player_account.storage().get_item(PHASE_DATA_SLOT).as_elements().to_vec()[0].as_int();
-                    let card_slot = match current_phase {
-                        1 => FLOP_SLOT,
-                        2 => FLOP_SLOT + 3,
-                        3 => FLOP_SLOT + 4,
-                        _ => FLOP_SLOT,
-                    };
-                    set_community_cards(account_id, game_account_id, cards, card_slot).await;
-                }
-
-                sleep(Duration::from_secs(5)).await;
+                let target_account = AccountId::try_from(
+                    player_data[player_index]
+                ).unwrap();
+                enc_action(post.action_type, account_id, target_account).await;
             }
-        }).await;
-        Ok(())
+            ProtocolStep::SendDeckToGame => {
+                enc_action(post.action_type, account_id, game_account_id).await;
+            }
+            ProtocolStep::InterUnmask => {
+                p2p_unmask_flow(account_id, post.temp_cards).await;
+            }
+            ProtocolStep::RevealHoleCards => {
+                self_unmask(account_id, PLAYER_CARD1_SLOT).await;
+            }
+            ProtocolStep::RevealCommunityCards { card_slot } => {
+                self_unmask(account_id, TEMP_CARD_SLOT).await;
+                // send cards to game account
+                set_community_cards(account_id, game_account_id, post.temp_cards, card_slot).await;
+            }
+        }
     }
-}
\ No newline at end of file
+
+    true
+}
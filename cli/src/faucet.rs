@@ -0,0 +1,88 @@
+use aze_lib::client::{create_aze_client, AzeClient};
+use aze_lib::constants::BUY_IN_AMOUNT;
+use aze_lib::notes::{consume_notes, mint_note};
+use clap::{Parser, Subcommand};
+use miden_client::client::accounts::{AccountStorageMode, AccountTemplate};
+use miden_objects::{accounts::AccountId, assets::TokenSymbol, notes::NoteType};
+
+// Seeds a local network with chips to buy in with. The faucet lives in the local store, so
+// minting has to run from the directory it was created in, next to the receiving player.
+
+#[derive(Debug, Clone, Parser)]
+pub struct FaucetCmd {
+    #[command(subcommand)]
+    cmd: FaucetCommands,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+enum FaucetCommands {
+    // creates a fungible faucet for tables to buy in with
+    Create(CreateFaucetCmd),
+    // mints chips to a player and consumes them into its wallet
+    Mint(MintCmd),
+}
+
+#[derive(Debug, Clone, Parser)]
+struct CreateFaucetCmd {
+    #[arg(short, long, default_value = "CHIP")]
+    symbol: String,
+
+    #[arg(short, long, default_value_t = 8)]
+    decimals: u8,
+
+    #[arg(short, long, default_value_t = 1_000_000_000)]
+    max_supply: u64,
+}
+
+#[derive(Debug, Clone, Parser)]
+struct MintCmd {
+    #[arg(short, long, help = "Faucet account ID")]
+    faucet: String,
+
+    #[arg(short, long, help = "Player account ID receiving the chips")]
+    to: String,
+
+    #[arg(short, long, default_value_t = BUY_IN_AMOUNT)]
+    amount: u64,
+}
+
+impl FaucetCmd {
+    pub async fn execute(&self) -> Result<(), String> {
+        let mut client: AzeClient = create_aze_client();
+        match &self.cmd {
+            FaucetCommands::Create(cmd) => {
+                let token_symbol = TokenSymbol::new(&cmd.symbol).map_err(|e| e.to_string())?;
+                let (faucet_account, _) = client
+                    .new_account(AccountTemplate::FungibleFaucet {
+                        token_symbol,
+                        decimals: cmd.decimals,
+                        max_supply: cmd.max_supply,
+                        storage_mode: AccountStorageMode::Local,
+                    })
+                    .map_err(|e| e.to_string())?;
+                println!("Faucet {} created for {}", faucet_account.id(), cmd.symbol);
+            }
+            FaucetCommands::Mint(cmd) => {
+                let faucet_account_id = AccountId::from_hex(&cmd.faucet).map_err(|e| e.to_string())?;
+                let player_account_id = AccountId::from_hex(&cmd.to).map_err(|e| e.to_string())?;
+                if cmd.amount == 0 {
+                    return Err("Nothing to mint".to_string());
+                }
+                client.get_account(faucet_account_id).map_err(|e| e.to_string())?;
+                client.get_account(player_account_id).map_err(|e| e.to_string())?;
+
+                let note = mint_note(
+                    &mut client,
+                    player_account_id,
+                    faucet_account_id,
+                    cmd.amount,
+                    NoteType::Public,
+                )
+                .await;
+                consume_notes(&mut client, player_account_id, &[note]).await;
+                println!("Minted {} to {}", cmd.amount, player_account_id);
+            }
+        }
+        Ok(())
+    }
+}
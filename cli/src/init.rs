@@ -1,37 +1,51 @@
-use crate::accounts::{ create_aze_game_account, consume_game_notes, send_community_cards };
+use crate::accounts::create_aze_game_account;
+use aze_lib::auth::RequestSigner;
 use aze_lib::client::{ create_aze_client, AzeClient };
-use aze_lib::broadcast::initialise_server;
+use aze_lib::broadcast::{ initialise_server, ServerConfig };
 use aze_lib::constants::{
-    BUY_IN_AMOUNT, COMMUNITY_CARDS, CURRENT_PHASE_SLOT, NO_OF_PLAYERS, SMALL_BLIND_AMOUNT,
+    BUY_IN_AMOUNT, DEFAULT_ACTION_TIMEOUT, DEFAULT_SERVER_PORT, DEFAULT_SPECTATOR_DELAY,
+    NO_OF_PLAYERS, SMALL_BLIND_AMOUNT,
 };
-use aze_lib::utils::{broadcast_message, card_from_number, Ws_config};
+use aze_lib::gamestate::{check_straddle, Ante, BettingStructure};
+use aze_lib::hands::GameVariant;
+use aze_lib::host::{run_host, HostedGame, RakeConfig};
+use aze_lib::tournament::{BlindSchedule, LevelUp};
+use aze_lib::utils::{register_game, Ws_config};
 use aze_types::accounts::AccountCreationError;
 use clap::{Parser, ValueEnum};
 use figment::{
     providers::{Format, Toml},
     Figment,
 };
-use miden_objects::{
-    accounts::AccountId,
-    Felt, FieldElement
-};
+use miden_objects::accounts::AccountId;
 use serde::Deserialize;
+use std::net::IpAddr;
 use std::path::PathBuf;
+use tokio::sync::watch;
 use tokio::task::LocalSet;
-use tokio::time::{sleep, Duration};
 
-#[derive(ValueEnum, Debug, Clone)]
+#[derive(ValueEnum, Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
 enum GameType {
     Holdem,
     Omaha,
     Hilo,
 }
 
+#[derive(ValueEnum, Deserialize, Debug, Clone, Copy)]
+#[serde(rename_all = "kebab-case")]
+enum Betting {
+    NoLimit,
+    PotLimit,
+    FixedLimit,
+}
+
 #[derive(Debug, Clone, Parser)]
 pub struct InitCmd {
     #[arg(short, long, value_enum, default_value = "holdem")]
     game_type: GameType,
 
+    // players seated in order, without them the seats stay open for `aze-cli seat join`
     #[arg(short, long, num_args = NO_OF_PLAYERS as usize)]
     player: Option<Vec<u64>>,
 
@@ -43,20 +57,144 @@ pub struct InitCmd {
 
     #[arg(short, long, value_parser)]
     config: Option<std::path::PathBuf>,
+
+    // seconds a player has to act, 0 to wait forever
+    #[arg(short = 't', long, default_value_t = DEFAULT_ACTION_TIMEOUT)]
+    action_timeout: u64,
+
+    // address the game server listens on, the first non-loopback IPv4 by default
+    #[arg(long)]
+    bind_address: Option<IpAddr>,
+
+    // 0 picks a free port
+    #[arg(long, default_value_t = DEFAULT_SERVER_PORT)]
+    port: u16,
+
+    // url players connect to when it differs from the bound address, e.g. behind NAT
+    #[arg(long)]
+    public_url: Option<String>,
+
+    // seconds spectators lag behind the table
+    #[arg(long, default_value_t = DEFAULT_SPECTATOR_DELAY)]
+    spectator_delay: u64,
+
+    // http url of a running aze-server to host the game on instead of this process
+    #[arg(long)]
+    server: Option<String>,
+
+    // faucet whose asset players buy in with at open tables
+    #[arg(long)]
+    faucet: Option<String>,
+
+    // percentage of every pot that reaches the flop taken as rake
+    #[arg(long, default_value_t = 0)]
+    rake: u64,
+
+    // most rake taken from a single pot
+    #[arg(long, default_value_t = 0)]
+    rake_cap: u64,
+
+    // account the rake is withdrawn to
+    #[arg(long)]
+    operator: Option<String>,
+
+    // blind levels of a sit-and-go, e.g. 5/10,10/20,25/50, every seat buys in for the same stack
+    #[arg(long)]
+    blind_levels: Option<String>,
+
+    // hands dealt at every blind level
+    #[arg(long, conflicts_with = "level_minutes")]
+    level_hands: Option<u64>,
+
+    // minutes every blind level lasts
+    #[arg(long)]
+    level_minutes: Option<u64>,
+
+    // pot-limit for Omaha and no-limit otherwise by default
+    #[arg(long, value_enum)]
+    betting: Option<Betting>,
+
+    // fixed-limit raises before the turn, the big blind by default
+    #[arg(long)]
+    small_bet: Option<u64>,
+
+    // fixed-limit raises from the turn on, twice the small bet by default
+    #[arg(long)]
+    big_bet: Option<u64>,
+
+    // fixed-limit raises allowed on a street
+    #[arg(long, default_value_t = 4)]
+    raise_cap: u64,
+
+    // ante every player dealt in posts, 0 for none
+    #[arg(long, default_value_t = 0)]
+    ante: u64,
+
+    // the big blind posts a single ante for the whole table instead
+    #[arg(long, requires = "ante")]
+    big_blind_ante: bool,
+
+    // the player after the big blind posts twice the big blind every hand
+    #[arg(long)]
+    straddle: bool,
 }
 
 impl InitCmd {
     pub async fn execute(&self, ws_config: &PathBuf) -> Result<(), String> {
+        let mut game_type = self.game_type;
         let mut player_ids = self.player.clone().unwrap_or_else(Vec::new);
         let mut small_blind_amount = self.small_blind;
         let mut buy_in_amount = self.buy_in;
+        let mut action_timeout = self.action_timeout;
+        let mut faucet = self.faucet.clone();
+        let mut rake_percent = self.rake;
+        let mut rake_cap = self.rake_cap;
+        let mut operator = self.operator.clone();
+        let mut blind_levels = self.blind_levels.clone();
+        let mut level_hands = self.level_hands;
+        let mut level_minutes = self.level_minutes;
+        let mut betting = self.betting;
+        let mut small_bet = self.small_bet;
+        let mut big_bet = self.big_bet;
+        let mut raise_cap = self.raise_cap;
+        let mut ante_amount = self.ante;
+        let mut big_blind_ante = self.big_blind_ante;
+        let mut straddle = self.straddle;
+        let mut server_config = ServerConfig {
+            bind_address: self.bind_address,
+            port: self.port,
+            public_url: self.public_url.clone(),
+            spectator_delay: self.spectator_delay,
+        };
 
         if let Some(config_path) = &self.config {
             match load_config(&config_path) {
                 Ok(config) => {
+                    game_type = config.game_type;
                     player_ids = config.player_ids;
                     small_blind_amount = config.small_blind;
                     buy_in_amount = config.buy_in;
+                    action_timeout = config.action_timeout;
+                    faucet = config.faucet;
+                    rake_percent = config.rake;
+                    rake_cap = config.rake_cap;
+                    operator = config.operator;
+                    blind_levels = config.blind_levels;
+                    level_hands = config.level_hands;
+                    level_minutes = config.level_minutes;
+                    betting = config.betting;
+                    small_bet = config.small_bet;
+                    big_bet = config.big_bet;
+                    raise_cap = config.raise_cap;
+                    ante_amount = config.ante;
+                    big_blind_ante = config.big_blind_ante;
+                    straddle = config.straddle;
+                    server_config = ServerConfig {
+                        bind_address: config.bind_address,
+                        port: config.port,
+                        public_url: config.public_url,
+                        spectator_delay: config.spectator_delay,
+                    };
                 }
                 Err(e) => {
                     return Err(format!("Error loading config: {}", e));
@@ -64,153 +202,142 @@ impl InitCmd {
             }
         }
 
-        match create_aze_game_account(player_ids.clone(), small_blind_amount, buy_in_amount).await {
+        let buy_in_faucet = match faucet {
+            Some(faucet) => Some(AccountId::from_hex(&faucet).map_err(|e| e.to_string())?),
+            None if player_ids.is_empty() => {
+                return Err(String::from("Players buy in with an asset at open tables, pass --faucet"))
+            }
+            None => None,
+        };
+
+        let rake = match operator {
+            _ if rake_percent == 0 => None,
+            _ if rake_percent > 100 => return Err(String::from("The rake is a percentage of the pot")),
+            _ if rake_cap == 0 => return Err(String::from("Raked tables need a --rake-cap")),
+            Some(operator) => Some(RakeConfig {
+                percent: rake_percent,
+                cap: rake_cap,
+                operator: AccountId::from_hex(&operator).map_err(|e| e.to_string())?,
+            }),
+            None => return Err(String::from("Raked tables need an --operator to withdraw the rake")),
+        };
+
+        let blind_schedule = match blind_levels {
+            Some(blind_levels) => {
+                let level_up = match (level_hands, level_minutes) {
+                    (Some(hands), None) => LevelUp::Hands(hands),
+                    (None, Some(minutes)) => LevelUp::Minutes(minutes),
+                    _ => {
+                        return Err(String::from(
+                            "Tournaments raise the blinds every --level-hands or --level-minutes",
+                        ))
+                    }
+                };
+                if !player_ids.is_empty() {
+                    return Err(String::from("Tournament players take their seats with seat join"));
+                }
+                // the winner cashes out every chip the seats bought in with
+                if rake.is_some() {
+                    return Err(String::from("Tournaments don't take a rake"));
+                }
+                let levels = BlindSchedule::parse_levels(&blind_levels)?;
+                Some(BlindSchedule::new(levels, level_up)?)
+            }
+            None => None,
+        };
+        if let Some(blind_schedule) = &blind_schedule {
+            small_blind_amount = blind_schedule.first_level().small_blind;
+        }
+        let starting_stack = blind_schedule.as_ref().map(|_| buy_in_amount);
+
+        let variant = match game_type {
+            GameType::Holdem => GameVariant::Holdem,
+            GameType::Omaha => GameVariant::Omaha,
+            GameType::Hilo => GameVariant::OmahaHiLo,
+        };
+
+        let betting = betting.unwrap_or(match variant {
+            GameVariant::Omaha | GameVariant::OmahaHiLo => Betting::PotLimit,
+            GameVariant::Holdem => Betting::NoLimit,
+        });
+        let betting_structure = match betting {
+            Betting::NoLimit => BettingStructure::NoLimit,
+            Betting::PotLimit => BettingStructure::PotLimit,
+            Betting::FixedLimit => {
+                let small_bet = small_bet.unwrap_or(small_blind_amount as u64 * 2);
+                let big_bet = big_bet.unwrap_or(small_bet * 2);
+                if small_bet == 0 || big_bet < small_bet {
+                    return Err(String::from("Fixed-limit bets start above 0 and the big bet is at least the small bet"));
+                }
+                if raise_cap == 0 {
+                    return Err(String::from("Fixed-limit tables allow at least one raise a street"));
+                }
+                BettingStructure::FixedLimit { small_bet, big_bet, raise_cap }
+            }
+        };
+
+        let ante = match ante_amount {
+            0 if big_blind_ante => return Err(String::from("A big blind ante needs an --ante")),
+            0 => Ante::None,
+            _ if big_blind_ante => Ante::BigBlind(ante_amount),
+            _ => Ante::PerPlayer(ante_amount),
+        };
+        // the straddle has to fit a single on-chain bet at every blind level the table reaches
+        let big_blinds: Vec<u64> = match &blind_schedule {
+            Some(blind_schedule) => blind_schedule
+                .levels()
+                .iter()
+                .map(|level| level.big_blind as u64)
+                .collect(),
+            None => vec![small_blind_amount as u64 * 2],
+        };
+        for big_blind in big_blinds {
+            check_straddle(straddle, big_blind)?;
+        }
+
+        match create_aze_game_account(player_ids.clone(), small_blind_amount, buy_in_amount, buy_in_faucet, rake, starting_stack, betting_structure, variant, ante, straddle).await {
             Ok(game_account_id) => {
                 println!("Game account created: {:?}", game_account_id);
+                let hosted_game = HostedGame {
+                    game_id: game_account_id.to_string(),
+                    player_ids: player_ids.clone(),
+                    small_blind: small_blind_amount,
+                    buy_in: buy_in_amount,
+                    action_timeout,
+                    blind_schedule,
+                };
+
+                // the server shares our store, so it can consume the game account's notes
+                if let Some(server_url) = &self.server {
+                    let client: AzeClient = create_aze_client();
+                    let signer = RequestSigner::from_client(&client, game_account_id)?;
+                    let ws_url = register_game(server_url.clone(), &hosted_game, &signer)
+                        .await
+                        .map_err(|e| e.to_string())?;
+                    let mut config = Ws_config::load(ws_config);
+                    config.url = Some(ws_url.clone());
+                    config.save(ws_config);
+                    println!("Game hosted at: {}", ws_url);
+                    return Ok(());
+                }
+
                 /*
                     Start ws and http server on exposed port of user in background
                     Setup local off chain game state 
                 */
-                let config_clone = ws_config.clone();
-                let player_ids_clone = player_ids.clone();
-                tokio::spawn(async move {
-                    match initialise_server(game_account_id.to_string(), &config_clone, buy_in_amount.clone(), small_blind_amount.clone(), player_ids_clone) {
-                        Some(ws_url) => {
-                            println!("Game server started at: {}", ws_url);
-                            Ok(())
-                        }
-                        None => {
-                            return Err("Error starting ws server");
-                        }
-                    }
-                });
-                let mut client: AzeClient = create_aze_client();
-                let local_set = LocalSet::new();
-                let mut ws_url: String = String::new();
-
-                match Ws_config::load(ws_config).url {
-                    Some(url) => {
-                        ws_url = url;
-                    }
-
-                    None => {
-                        eprintln!("Ws_config DNE, use init or connect command before action");
-                    }
-                }
-                local_set
-                    .run_until(async {
-                        loop {
-                            let (game_account, _) = client.get_account(game_account_id).unwrap();
-                            let phase_data = game_account
-                                .storage()
-                                .get_item(CURRENT_PHASE_SLOT)
-                                .as_elements()
-                                .to_vec();
-                            let pre_phase = phase_data[0].as_int();
-                            consume_game_notes(game_account_id).await;
-                            let (game_account, _) = client.get_account(game_account_id).unwrap();
-                            let phase_data = game_account
-                                .storage()
-                                .get_item(CURRENT_PHASE_SLOT)
-                                .as_elements()
-                                .to_vec();
-                            let phase = phase_data[0].as_int();
-
-                            // if phase is not incremented post consumption, continue
-                            if pre_phase + 1 != phase {
-                                sleep(Duration::from_secs(5)).await;
-                                match pre_phase {
-                                    0 => {
-                                        let mut revealed_comm: Vec<u64> = vec![];
-                                        for i in 0..3 {
-                                            revealed_comm.push(
-                                                game_account
-                                                    .storage()
-                                                    .get_item(COMMUNITY_CARDS[i])
-                                                    .as_elements()[0]
-                                                    .as_int(),
-                                            );
-                                        }
-                                        let _ = broadcast_message(
-                                            game_account_id.clone().to_string(),
-                                            ws_url.clone(),
-                                            format!(
-                                                "Community Cards Revealed: {} {} {}",
-                                                card_from_number(revealed_comm[0]),
-                                                card_from_number(revealed_comm[1]),
-                                                card_from_number(revealed_comm[2])
-                                            ),
-                                        )
-                                        .await;
-                                    }
-
-                                    1 => {
-                                        let _ = broadcast_message(
-                                            game_account_id.clone().to_string(),
-                                            ws_url.clone(),
-                                            format!(
-                                                "Community Card Revealed: {}",
-                                                card_from_number(
-                                                    game_account
-                                                        .storage()
-                                                        .get_item(COMMUNITY_CARDS[3])
-                                                        .as_elements()[0]
-                                                        .as_int()
-                                                ),
-                                            ),
-                                        )
-                                        .await;
-                                    }
-                                    2 => {
-                                        let _ = broadcast_message(
-                                            game_account_id.clone().to_string(),
-                                            ws_url.clone(),
-                                            format!(
-                                                "Community Card Revealed: {}",
-                                                card_from_number(
-                                                    game_account
-                                                        .storage()
-                                                        .get_item(COMMUNITY_CARDS[4])
-                                                        .as_elements()[0]
-                                                        .as_int()
-                                                ),
-                                            ),
-                                        )
-                                        .await;
-                                    }
-                                    _ => (),
-                                }
-                                continue;
-                            }
-
-                            // broadcast message if game ends
-                            if phase == 3 {
-                                let _ = broadcast_message(
-                                    game_account_id.to_string(),
-                                    ws_url.clone(),
-                                    format!("Game Ended"),
-                                )
-                                .await;
-                            }
-
-                            // if phase changes, send community cards for unmasking
-                            let player_account_id = AccountId::try_from(player_ids[0]).unwrap();
-                            let mut cards: [[Felt; 4]; 3] = [[Felt::ZERO; 4]; 3];
-                            for (i, slot) in (1..4).enumerate() {
-                                let card_digest = game_account.storage().get_item(slot);
-                                cards[i] = card_digest.into();
-                            }
-                            // send community cards
-                            send_community_cards(
-                                game_account_id,
-                                player_account_id,
-                                cards,
-                                phase as u8,
-                            )
-                            .await;
-                            sleep(Duration::from_secs(5)).await;
-                        }
-                    })
+                let ws_url = initialise_server(
+                    game_account_id.to_string(),
+                    ws_config,
+                    buy_in_amount,
+                    small_blind_amount,
+                    player_ids.clone(),
+                    &server_config,
+                )?;
+                println!("Game server started at: {}", ws_url);
+                // runs until the process is killed
+                let (_shutdown, shutdown_rx) = watch::channel(false);
+                LocalSet::new()
+                    .run_until(run_host(hosted_game, shutdown_rx))
                     .await;
                 Ok(())
             }
@@ -221,9 +348,69 @@ impl InitCmd {
 
 #[derive(Deserialize, Debug)]
 struct Config {
+    #[serde(default = "default_game_type")]
+    game_type: GameType,
     player_ids: Vec<u64>,
     small_blind: u8,
     buy_in: u64,
+    #[serde(default = "default_action_timeout")]
+    action_timeout: u64,
+    #[serde(default)]
+    bind_address: Option<IpAddr>,
+    #[serde(default = "default_port")]
+    port: u16,
+    #[serde(default)]
+    public_url: Option<String>,
+    #[serde(default = "default_spectator_delay")]
+    spectator_delay: u64,
+    #[serde(default)]
+    faucet: Option<String>,
+    #[serde(default)]
+    rake: u64,
+    #[serde(default)]
+    rake_cap: u64,
+    #[serde(default)]
+    operator: Option<String>,
+    #[serde(default)]
+    blind_levels: Option<String>,
+    #[serde(default)]
+    level_hands: Option<u64>,
+    #[serde(default)]
+    level_minutes: Option<u64>,
+    #[serde(default)]
+    betting: Option<Betting>,
+    #[serde(default)]
+    small_bet: Option<u64>,
+    #[serde(default)]
+    big_bet: Option<u64>,
+    #[serde(default = "default_raise_cap")]
+    raise_cap: u64,
+    #[serde(default)]
+    ante: u64,
+    #[serde(default)]
+    big_blind_ante: bool,
+    #[serde(default)]
+    straddle: bool,
+}
+
+fn default_action_timeout() -> u64 {
+    DEFAULT_ACTION_TIMEOUT
+}
+
+fn default_game_type() -> GameType {
+    GameType::Holdem
+}
+
+fn default_raise_cap() -> u64 {
+    4
+}
+
+fn default_port() -> u16 {
+    DEFAULT_SERVER_PORT
+}
+
+fn default_spectator_delay() -> u64 {
+    DEFAULT_SPECTATOR_DELAY
 }
 
 fn load_config(config_file: &PathBuf) -> Result<Config, String> {
@@ -0,0 +1,91 @@
+use ansi_term::Colour::{Green, Yellow};
+use aze_lib::auth::RequestSigner;
+use aze_lib::client::{create_aze_client, AzeClient};
+use aze_lib::utils::{read_player_data, read_player_id, Ws_config};
+use aze_types::lobby::{LobbyJoinRequest, LobbyUpdate, Stakes};
+use clap::{Parser, Subcommand};
+use futures_util::{SinkExt, StreamExt};
+use miden_objects::accounts::AccountId;
+use std::error::Error;
+use std::path::PathBuf;
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::protocol::Message;
+use url::Url;
+
+#[derive(Debug, Clone, Parser)]
+pub struct LobbyCmd {
+    #[command(subcommand)]
+    cmd: LobbyCommands,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+enum LobbyCommands {
+    // waits for a table at the given stakes
+    Join(JoinCmd),
+}
+
+#[derive(Debug, Clone, Parser)]
+struct JoinCmd {
+    #[arg(short, long, help = "Game server URL, e.g. ws://192.168.30.51:12044")]
+    url: String,
+
+    #[arg(short, long, help = "Small and big blind, e.g. 5/10")]
+    stakes: Stakes,
+}
+
+impl LobbyCmd {
+    pub async fn execute(&self, ws_config: &PathBuf) -> Result<(), Box<dyn Error>> {
+        match &self.cmd {
+            LobbyCommands::Join(join_cmd) => join_cmd.execute(ws_config).await,
+        }
+    }
+}
+
+impl JoinCmd {
+    async fn execute(&self, ws_config: &PathBuf) -> Result<(), Box<dyn Error>> {
+        let (player_id, identifier) = match (read_player_id(), read_player_data()) {
+            (Some(player_id), Some(identifier)) => (player_id, identifier),
+            _ => return Err("Register a player before joining the lobby".into()),
+        };
+        let client: AzeClient = create_aze_client();
+        let account_id = AccountId::try_from(player_id).map_err(|e| e.to_string())?;
+        let signer = RequestSigner::from_client(&client, account_id)?;
+
+        let mut lobby_url = Url::parse(&self.url)?;
+        lobby_url.set_path("/lobby");
+        let (ws_stream, _) = connect_async(lobby_url.as_str()).await?;
+        let (mut write, mut read) = ws_stream.split();
+        let request = signer.sign(&LobbyJoinRequest {
+            player_id,
+            identifier,
+            stakes: self.stakes,
+        });
+        write
+            .send(Message::Text(serde_json::to_string(&request)?))
+            .await?;
+        println!("Joined the lobby at {}", self.stakes);
+
+        while let Some(message) = read.next().await {
+            let text = match message? {
+                Message::Text(text) => text,
+                _ => continue,
+            };
+            match serde_json::from_str::<LobbyUpdate>(&text)? {
+                LobbyUpdate::Waiting { waiting, needed } => println!(
+                    "{}",
+                    Yellow.paint(format!("{} of {} players waiting", waiting, needed))
+                ),
+                LobbyUpdate::Seated { game_id, ws_url } => {
+                    let mut config = Ws_config::load(ws_config);
+                    config.url = Some(ws_url.clone());
+                    config.save(ws_config);
+                    println!("{}", Green.bold().paint(format!("Seated at game {}", game_id)));
+                    println!("Run `aze-cli consume-notes` and `aze-cli connect -u {}` to play", ws_url);
+                    return Ok(());
+                }
+                LobbyUpdate::Rejected { reason } => return Err(reason.into()),
+            }
+        }
+        Err("The lobby closed the connection".into())
+    }
+}
@@ -1,18 +1,26 @@
 mod accounts;
 mod action;
 mod actions;
+mod bot;
 mod commit_hand;
 mod connect;
 mod consume_notes;
+mod faucet;
 mod init;
+mod lobby;
 mod peek_hand;
 mod register;
+mod seat;
 mod see_hands;
+mod simulate;
 mod stats;
+mod watch;
+mod withdraw_rake;
 use self::{
-    action::ActionCmd, commit_hand::CommitHandCmd, connect::ConnectCmd,
-    consume_notes::ConsumeNotesCmd, init::InitCmd, peek_hand::PeekHandCmd, register::RegisterCmd,
-    see_hands::SeeHandsCmd, stats::StatsCmd,
+    action::ActionCmd, bot::BotCmd, commit_hand::CommitHandCmd, connect::ConnectCmd,
+    consume_notes::ConsumeNotesCmd, faucet::FaucetCmd, init::InitCmd, lobby::LobbyCmd, peek_hand::PeekHandCmd,
+    register::RegisterCmd, seat::SeatCmd, see_hands::SeeHandsCmd, simulate::SimulateCmd, stats::StatsCmd,
+    watch::WatchCmd, withdraw_rake::WithdrawRakeCmd,
 };
 use clap::Parser;
 
@@ -34,6 +42,13 @@ enum Commands {
     Stats(StatsCmd),
     SeeHands(SeeHandsCmd),
     CommitHand(CommitHandCmd),
+    Bot(BotCmd),
+    Simulate(SimulateCmd),
+    Watch(WatchCmd),
+    Lobby(LobbyCmd),
+    Seat(SeatCmd),
+    WithdrawRake(WithdrawRakeCmd),
+    Faucet(FaucetCmd),
 }
 
 #[tokio::main]
@@ -82,7 +97,42 @@ async fn main() {
             }
         }
         Commands::CommitHand(commit_hand_cmd) => {
-            if let Err(error) = commit_hand_cmd.execute().await {
+            if let Err(error) = commit_hand_cmd.execute(&ws_config_path).await {
+                println!("{}", error);
+            }
+        }
+        Commands::Bot(bot_cmd) => {
+            if let Err(error) = bot_cmd.execute(&ws_config_path).await {
+                println!("{}", error);
+            }
+        }
+        Commands::Simulate(simulate_cmd) => {
+            if let Err(error) = simulate_cmd.execute().await {
+                println!("{}", error);
+            }
+        }
+        Commands::Watch(watch_cmd) => {
+            if let Err(error) = watch_cmd.execute().await {
+                println!("{}", error);
+            }
+        }
+        Commands::Lobby(lobby_cmd) => {
+            if let Err(error) = lobby_cmd.execute(&ws_config_path).await {
+                println!("{}", error);
+            }
+        }
+        Commands::Seat(seat_cmd) => {
+            if let Err(error) = seat_cmd.execute().await {
+                println!("{}", error);
+            }
+        }
+        Commands::WithdrawRake(withdraw_rake_cmd) => {
+            if let Err(error) = withdraw_rake_cmd.execute().await {
+                println!("{}", error);
+            }
+        }
+        Commands::Faucet(faucet_cmd) => {
+            if let Err(error) = faucet_cmd.execute().await {
                 println!("{}", error);
             }
         }
@@ -1,6 +1,6 @@
 use crate::accounts::{ p2p_unmask_flow };
 use aze_lib::client::{ create_aze_client, AzeClient };
-use aze_lib::constants::{ PLAYER_CARD1_SLOT, PLAYER_CARD2_SLOT };
+use aze_lib::constants::{ MAX_HOLE_CARDS, PLAYER_CARD1_SLOT, PLAYER_CARD4_SLOT };
 use clap::Parser;
 use miden_objects::{ 
     accounts::AccountId,
@@ -18,8 +18,8 @@ impl PeekHandCmd {
         let sender_account_id = AccountId::try_from(self.player_id).unwrap();
         let mut client: AzeClient = create_aze_client();
         let (player_account, _) = client.get_account(sender_account_id).unwrap();
-        let mut cards: [[Felt; 4]; 3] = [[Felt::ZERO; 4]; 3];
-        for (i, slot) in (PLAYER_CARD1_SLOT..PLAYER_CARD2_SLOT + 1).enumerate() {
+        let mut cards: [[Felt; 4]; MAX_HOLE_CARDS] = [[Felt::ZERO; 4]; MAX_HOLE_CARDS];
+        for (i, slot) in (PLAYER_CARD1_SLOT..=PLAYER_CARD4_SLOT).enumerate() {
             let card = player_account.storage().get_item(slot);
             cards[i] = card.into();
         }
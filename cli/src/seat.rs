@@ -0,0 +1,127 @@
+use aze_lib::client::{
+    create_aze_client, AzeClient, AzeGameMethods, AzeTransactionTemplate, CashOutTransactionData,
+    JoinSeatTransactionData, LeaveSeatTransactionData, SitOutTransactionData,
+};
+use aze_lib::constants::{BUY_IN_AMOUNT, NO_OF_PLAYERS};
+use aze_lib::executor::execute_tx_and_sync;
+use aze_lib::utils::read_player_id;
+use clap::{Parser, Subcommand};
+use miden_objects::accounts::AccountId;
+use miden_objects::assets::FungibleAsset;
+
+// Seats are taken and left with notes to the game account, which the dealer consumes between
+// hands. The table picks the changes up from the game account once they went through.
+
+#[derive(Debug, Clone, Parser)]
+pub struct SeatCmd {
+    #[command(subcommand)]
+    cmd: SeatCommands,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+enum SeatCommands {
+    // takes a free seat, the buy-in asset is escrowed by the game account and becomes the balance
+    Join(JoinSeatCmd),
+    // frees the seat, the balance leaves with the player
+    Leave(LeaveSeatCmd),
+    // keeps the seat but isn't dealt in, --back to play again
+    SitOut(SitOutCmd),
+    // frees the seat and pays the balance out to the player's account
+    CashOut(CashOutCmd),
+}
+
+#[derive(Debug, Clone, Parser)]
+struct JoinSeatCmd {
+    #[arg(short, long, help = "Game account ID")]
+    game_id: String,
+
+    #[arg(short, long, help = "Seat to take, from 0")]
+    seat: u8,
+
+    #[arg(short, long, default_value_t = BUY_IN_AMOUNT)]
+    buy_in: u64,
+
+    #[arg(short, long, help = "Faucet ID of the asset the table buys in with")]
+    faucet: String,
+}
+
+#[derive(Debug, Clone, Parser)]
+struct LeaveSeatCmd {
+    #[arg(short, long, help = "Game account ID")]
+    game_id: String,
+
+    #[arg(short, long, help = "Seat the player holds, from 0")]
+    seat: u8,
+}
+
+#[derive(Debug, Clone, Parser)]
+struct SitOutCmd {
+    #[arg(short, long, help = "Game account ID")]
+    game_id: String,
+
+    #[arg(short, long, help = "Seat the player holds, from 0")]
+    seat: u8,
+
+    #[arg(long, help = "Be dealt in again from the next hand")]
+    back: bool,
+}
+
+#[derive(Debug, Clone, Parser)]
+struct CashOutCmd {
+    #[arg(short, long, help = "Game account ID")]
+    game_id: String,
+
+    #[arg(short, long, help = "Seat the player holds, from 0")]
+    seat: u8,
+}
+
+impl SeatCmd {
+    pub async fn execute(&self) -> Result<(), String> {
+        let player_id = read_player_id().ok_or("Register a player before taking a seat")?;
+        let player_account_id = AccountId::try_from(player_id).map_err(|e| e.to_string())?;
+        let (game_id, seat) = match &self.cmd {
+            SeatCommands::Join(cmd) => (&cmd.game_id, cmd.seat),
+            SeatCommands::Leave(cmd) => (&cmd.game_id, cmd.seat),
+            SeatCommands::SitOut(cmd) => (&cmd.game_id, cmd.seat),
+            SeatCommands::CashOut(cmd) => (&cmd.game_id, cmd.seat),
+        };
+        let game_account_id = AccountId::from_hex(game_id).map_err(|e| e.to_string())?;
+        if seat >= NO_OF_PLAYERS {
+            return Err(format!("Seats go from 0 to {}", NO_OF_PLAYERS - 1));
+        }
+
+        let mut client: AzeClient = create_aze_client();
+        let txn_request = match &self.cmd {
+            SeatCommands::Join(cmd) => {
+                let faucet_id = AccountId::from_hex(&cmd.faucet).map_err(|e| e.to_string())?;
+                let buy_in = FungibleAsset::new(faucet_id, cmd.buy_in).map_err(|e| e.to_string())?;
+                let join_seat_data =
+                    JoinSeatTransactionData::new(player_account_id, game_account_id, seat, buy_in);
+                client.build_aze_join_seat_tx_request(AzeTransactionTemplate::JoinSeat(join_seat_data))
+            }
+            SeatCommands::Leave(_) => {
+                let leave_seat_data =
+                    LeaveSeatTransactionData::new(player_account_id, game_account_id, seat);
+                client.build_aze_leave_seat_tx_request(AzeTransactionTemplate::LeaveSeat(leave_seat_data))
+            }
+            SeatCommands::SitOut(cmd) => {
+                let sit_out_data =
+                    SitOutTransactionData::new(player_account_id, game_account_id, seat, !cmd.back);
+                client.build_aze_sit_out_tx_request(AzeTransactionTemplate::SitOut(sit_out_data))
+            }
+            SeatCommands::CashOut(_) => {
+                let cash_out_data =
+                    CashOutTransactionData::new(player_account_id, game_account_id, seat);
+                client.build_aze_cash_out_tx_request(AzeTransactionTemplate::CashOut(cash_out_data))
+            }
+        }
+        .map_err(|e| e.to_string())?;
+        execute_tx_and_sync(&mut client, txn_request).await;
+
+        println!("Seat note sent, it takes effect once the hand being played is over");
+        if matches!(self.cmd, SeatCommands::CashOut(_)) {
+            println!("The payout arrives as a P2ID note, consume it with consume-notes");
+        }
+        Ok(())
+    }
+}
@@ -2,8 +2,9 @@ use std::path::PathBuf;
 
 use ansi_term::Colour::{Blue, Green, Red, Yellow};
 use aze_lib::{
+    auth::RequestSigner,
     client::{create_aze_client, AzeClient},
-    utils::{card_from_number, get_stats, Ws_config},
+    utils::{card_from_number, get_stats, read_player_id, Ws_config},
 };
 use clap::Parser;
 use dialoguer::Input;
@@ -23,8 +24,12 @@ impl SeeHandsCmd {
         let mut client: AzeClient = create_aze_client();
         let game_account_id = AccountId::try_from(gameid).unwrap();
         let ws_url = Ws_config::load(ws_config).url.unwrap();
+        // other players' hole cards only show once the game ended
+        let player_id = read_player_id().ok_or("Register a player before looking at the table")?;
+        let player_account_id = AccountId::try_from(player_id).map_err(|e| e.to_string())?;
+        let signer = RequestSigner::from_client(&client, player_account_id)?;
         let stat_data: aze_lib::utils::StatResponse =
-            get_stats(game_account_id.to_string(), ws_url).await?;
+            get_stats(game_account_id.to_string(), ws_url, &signer).await?;
 
         // Check: If game has not ended there is no hand to show. Comment for testing
         // if stat_data.current_state != 3 {
@@ -81,11 +86,7 @@ impl SeeHandsCmd {
                 "| {:^15}| {:15}| {:15} |",
                 "P1",
                 get_hand(stat_data.player_hands[0]).unwrap(),
-                format!(
-                    "{} {}",
-                    card_from_number(stat_data.player_hand_cards[0][0]),
-                    card_from_number(stat_data.player_hand_cards[0][1])
-                )
+                hand_cards(&stat_data.player_hand_cards[0])
             )),
             Blue.bold()
                 .paint("|---------------------------------------------------|"),
@@ -93,11 +94,7 @@ impl SeeHandsCmd {
                 "| {:^15}| {:15}| {:15} |",
                 "P2",
                 get_hand(stat_data.player_hands[0]).unwrap(),
-                format!(
-                    "{} {}",
-                    card_from_number(stat_data.player_hand_cards[1][0]),
-                    card_from_number(stat_data.player_hand_cards[1][1])
-                )
+                hand_cards(&stat_data.player_hand_cards[1])
             )),
             Blue.bold()
                 .paint("|---------------------------------------------------|"),
@@ -105,11 +102,7 @@ impl SeeHandsCmd {
                 "| {:^15}| {:15}| {:15} |",
                 "P3",
                 get_hand(stat_data.player_hands[0]).unwrap(),
-                format!(
-                    "{} {}",
-                    card_from_number(stat_data.player_hand_cards[2][0]),
-                    card_from_number(stat_data.player_hand_cards[2][1])
-                )
+                hand_cards(&stat_data.player_hand_cards[2])
             )),
             Blue.bold()
                 .paint("|---------------------------------------------------|"),
@@ -117,21 +110,49 @@ impl SeeHandsCmd {
                 "| {:^15}| {:15}| {:15} |",
                 "P4",
                 get_hand(stat_data.player_hands[0]).unwrap(),
-                format!(
-                    "{} {}",
-                    card_from_number(stat_data.player_hand_cards[3][0]),
-                    card_from_number(stat_data.player_hand_cards[3][1])
-                )
+                hand_cards(&stat_data.player_hand_cards[3])
             )),
             Blue.bold()
                 .paint("+---------------------------------------------------+"),
         );
 
         println!("{}", hands_output);
+
+        // in hi-lo the best 8-or-better low takes half of the pot
+        if stat_data.variant.is_hi_lo() {
+            println!("{}", Red.bold().paint("LOW HANDS"));
+            for (i, low) in stat_data.player_low_hands.iter().enumerate() {
+                println!("{}", Yellow.bold().paint(format!("P{}: {}", i + 1, low_hand(low))));
+            }
+        }
         Ok(())
     }
 }
 
+// two cards in Hold'em, four in Omaha
+fn hand_cards(cards: &[u64]) -> String {
+    cards
+        .iter()
+        .map(|card| card_from_number(*card))
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+// low ranks come highest first with the ace as 1, e.g. 7-5-4-2-A
+fn low_hand(ranks: &[u64]) -> String {
+    if ranks.is_empty() {
+        return String::from("No low");
+    }
+    ranks
+        .iter()
+        .map(|rank| match rank {
+            1 => String::from("A"),
+            rank => rank.to_string(),
+        })
+        .collect::<Vec<String>>()
+        .join("-")
+}
+
 fn get_hand(int_hand: u64) -> Result<(String), String> {
     let hand = match int_hand {
         0 => "Royal Flush",
@@ -0,0 +1,118 @@
+use ansi_term::Colour::{Blue, Red, Yellow};
+use aze_lib::client::create_mock_aze_client;
+use aze_lib::constants::{NO_OF_PLAYERS, PLAYER_INITIAL_BALANCE, SMALL_BLIND_AMOUNT};
+use aze_lib::hands::HandCategory;
+use aze_lib::mock::MockNode;
+use aze_lib::simulator::{simulate_offchain, simulate_onchain, SimulationConfig, SimulationStats};
+use aze_lib::strategy::{create_strategy, Strategy};
+use aze_types::actions::StrategyType;
+use clap::Parser;
+
+#[derive(Debug, Clone, Parser)]
+pub struct SimulateCmd {
+    #[arg(long, default_value_t = 100)]
+    hands: u64,
+
+    #[arg(long, default_value_t = NO_OF_PLAYERS as usize)]
+    players: usize,
+
+    // one strategy for every seat, or one per seat
+    #[arg(long, value_enum, num_args = 1.., default_value = "always-call")]
+    strategy: Vec<StrategyType>,
+
+    #[arg(long, default_value_t = SMALL_BLIND_AMOUNT as u64)]
+    small_blind: u64,
+
+    #[arg(long, default_value_t = PLAYER_INITIAL_BALANCE as u64)]
+    buy_in: u64,
+
+    #[arg(long)]
+    seed: Option<u64>,
+
+    // only run the Rust game state, without replaying the hands on the mock node
+    #[arg(long)]
+    offchain: bool,
+}
+
+impl SimulateCmd {
+    pub async fn execute(&self) -> Result<(), String> {
+        let strategy_types = match self.strategy.len() {
+            1 => vec![self.strategy[0]; self.players],
+            n if n == self.players => self.strategy.clone(),
+            n => {
+                return Err(format!(
+                    "Expected 1 or {} strategies, got {}",
+                    self.players, n
+                ))
+            }
+        };
+        let mut strategies: Vec<Box<dyn Strategy>> = strategy_types
+            .iter()
+            .enumerate()
+            .map(|(seat, strategy_type)| {
+                create_strategy(*strategy_type, self.seed.map(|seed| seed + seat as u64))
+            })
+            .collect();
+
+        let config = SimulationConfig {
+            hands: self.hands,
+            players: self.players,
+            small_blind: self.small_blind,
+            buy_in: self.buy_in,
+            seed: self.seed,
+        };
+
+        let stats = if self.offchain {
+            simulate_offchain(&config, &mut strategies)
+        } else {
+            let node = MockNode::new();
+            let mut client = create_mock_aze_client(&node);
+            simulate_onchain(&mut client, &config, &mut strategies).await?
+        };
+
+        print_report(&stats, &strategy_types, !self.offchain);
+        Ok(())
+    }
+}
+
+fn print_report(stats: &SimulationStats, strategy_types: &[StrategyType], on_chain: bool) {
+    println!("{}", Blue.bold().paint("+---------------------------------------------------+"));
+    println!("{}", Red.bold().paint(format!("| {:^49} |", "SIMULATION")));
+    println!("{}", Blue.bold().paint("|---------------------------------------------------|"));
+    println!("{}", Yellow.bold().paint(format!("| {:<30}{:>19} |", "Hands", stats.hands)));
+    println!("{}", Yellow.bold().paint(format!("| {:<30}{:>19} |", "Showdowns", stats.showdowns)));
+    println!("{}", Yellow.bold().paint(format!("| {:<30}{:>19.2} |", "Average pot", stats.average_pot())));
+    println!("{}", Blue.bold().paint("|---------------------------------------------------|"));
+    for (seat, strategy_type) in strategy_types.iter().enumerate() {
+        println!(
+            "{}",
+            Yellow.bold().paint(format!(
+                "| {:<30}{:>18.2}% |",
+                format!("P{} ({:?})", seat + 1, strategy_type),
+                stats.win_rate(seat) * 100.0
+            ))
+        );
+    }
+    println!("{}", Blue.bold().paint("|---------------------------------------------------|"));
+    for category in HandCategory::ALL.iter() {
+        println!(
+            "{}",
+            Yellow.bold().paint(format!(
+                "| {:<30}{:>18.2}% |",
+                category.name(),
+                stats.category_frequency(*category) * 100.0
+            ))
+        );
+    }
+    if on_chain {
+        println!("{}", Blue.bold().paint("|---------------------------------------------------|"));
+        println!(
+            "{}",
+            Yellow.bold().paint(format!("| {:<30}{:>19} |", "Divergences", stats.divergences.len()))
+        );
+    }
+    println!("{}", Blue.bold().paint("+---------------------------------------------------+"));
+    for divergence in stats.divergences.iter() {
+        println!("{}", Red.paint(divergence));
+    }
+}
@@ -2,8 +2,9 @@ use std::path::PathBuf;
 
 use ansi_term::Colour::{Blue, Green, Red, Yellow};
 use aze_lib::{
+    auth::RequestSigner,
     client::{create_aze_client, AzeClient},
-    utils::{card_from_number, get_stats, Ws_config},
+    utils::{card_from_number, get_stats, read_player_id, Ws_config},
 };
 use clap::Parser;
 use dialoguer::Input;
@@ -24,8 +25,12 @@ impl StatsCmd {
         let game_account_id = AccountId::try_from(gameid).unwrap();
         let game_account = client.get_account(game_account_id).unwrap().0;
         let ws_url = Ws_config::load(ws_config).url.unwrap();
+        // other players' hole cards only show once the game ended
+        let player_id = read_player_id().ok_or("Register a player before looking at the table")?;
+        let player_account_id = AccountId::try_from(player_id).map_err(|e| e.to_string())?;
+        let signer = RequestSigner::from_client(&client, player_account_id)?;
         let stat_data: aze_lib::utils::StatResponse =
-            get_stats(game_account_id.to_string(), ws_url).await?;
+            get_stats(game_account_id.to_string(), ws_url, &signer).await?;
 
         let poker_table = format!(
             "{}\n\
@@ -0,0 +1,33 @@
+use crate::connect::follow_events;
+use clap::Parser;
+use std::error::Error;
+use tokio_tungstenite::connect_async;
+use url::Url;
+
+#[derive(Debug, Clone, Parser)]
+pub struct WatchCmd {
+    #[arg(short, long, help = "WebSocket server URL of the game, as given to players")]
+    url: String,
+}
+
+impl WatchCmd {
+    pub async fn execute(&self) -> Result<(), Box<dyn Error>> {
+        let url = spectator_url(&self.url)?;
+        let (ws_stream, _) = connect_async(&url).await?;
+        println!("Watching the game at {}", url);
+        follow_events(&url, ws_stream, None, None).await
+    }
+}
+
+// spectators are served under /watch/<game id> instead of /ws/<game id>
+fn spectator_url(ws_url: &str) -> Result<String, Box<dyn Error>> {
+    let mut url = Url::parse(ws_url)?;
+    let game_id = url
+        .path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .filter(|game_id| !game_id.is_empty())
+        .ok_or("The url doesn't end with a game id")?
+        .to_string();
+    url.set_path(&format!("/watch/{}", game_id));
+    Ok(url.to_string())
+}
@@ -0,0 +1,39 @@
+use aze_lib::client::{
+    create_aze_client, AzeClient, AzeGameMethods, AzeTransactionTemplate,
+    WithdrawRakeTransactionData,
+};
+use aze_lib::executor::execute_tx_and_sync;
+use clap::Parser;
+use miden_objects::accounts::AccountId;
+
+// The operator asks the game account for the rake with a note, the game account pays it out as a
+// P2ID note once the dealer consumes it.
+
+#[derive(Debug, Clone, Parser)]
+pub struct WithdrawRakeCmd {
+    #[arg(short, long, help = "Game account ID")]
+    game_id: String,
+
+    #[arg(short, long, help = "Operator account ID the table was created with")]
+    operator: String,
+}
+
+impl WithdrawRakeCmd {
+    pub async fn execute(&self) -> Result<(), String> {
+        let game_account_id = AccountId::from_hex(&self.game_id).map_err(|e| e.to_string())?;
+        let operator_account_id = AccountId::from_hex(&self.operator).map_err(|e| e.to_string())?;
+
+        let mut client: AzeClient = create_aze_client();
+        let withdraw_rake_data =
+            WithdrawRakeTransactionData::new(operator_account_id, game_account_id);
+        let txn_request = client
+            .build_aze_withdraw_rake_tx_request(AzeTransactionTemplate::WithdrawRake(
+                withdraw_rake_data,
+            ))
+            .map_err(|e| e.to_string())?;
+        execute_tx_and_sync(&mut client, txn_request).await;
+
+        println!("Withdrawal note sent, the rake arrives as a P2ID note to consume with consume-notes");
+        Ok(())
+    }
+}
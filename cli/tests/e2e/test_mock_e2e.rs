@@ -0,0 +1,278 @@
+mod utils;
+use utils::{
+    create_mock_test_client,
+    create_player_account,
+    create_game_account,
+    mask_cards,
+    remask_cards,
+    peek_hand,
+    unmask_community_cards,
+    commit_hand,
+    play_bet,
+    play_raise,
+    play_call,
+    play_check,
+    end_hand,
+    create_buy_in_faucet,
+    create_escrow_game_account,
+    cash_out,
+};
+use aze_lib::client::AzeClient;
+use aze_lib::gamestate::{ Check_Action, PokerGame };
+use aze_lib::constants::{
+    PLAYER_DATA_SLOT,
+    DEFAULT_ACTION_TYPE,
+    PLAYER_CARD1_SLOT,
+    PLAYER_CARD2_SLOT,
+    FIRST_PLAYER_INDEX,
+    HAND_OFFSET,
+    PLAYER_STATS_SLOTS,
+    PLAYER_BALANCE_SLOT,
+    PLAYER_BET_OFFSET,
+    CURRENT_TURN_INDEX_SLOT,
+    CURRENT_PHASE_SLOT,
+    HAND_OVER_PHASE,
+    HIGHEST_BET_SLOT,
+    POT_VALUE,
+    PLAYER_INITIAL_BALANCE,
+    SMALL_BLIND_AMOUNT,
+    SEAT_OFFSET,
+    IS_FOLD_OFFSET,
+};
+use aze_lib::mock::{ MockNode, MockRpcClient };
+use aze_types::actions::ActionType;
+use miden_objects::{
+    accounts::AccountId,
+    assets::Asset,
+    crypto::hash::rpo::RpoDigest,
+    Felt, FieldElement
+};
+
+fn slot_value(client: &AzeClient<MockRpcClient>, account_id: AccountId, slot: u8) -> u64 {
+    let (account, _) = client.get_account(account_id).unwrap();
+    account.storage().get_item(slot).as_elements()[0].as_int()
+}
+
+// plays a full hand up to the showdown against the in-process node, no running miden node needed
+#[tokio::test]
+async fn test_mock_e2e() {
+    let node = MockNode::new();
+    let mut client = create_mock_test_client(&node);
+
+    // Create player accounts
+    let player1_id = create_player_account(&mut client).await;
+    let player2_id = create_player_account(&mut client).await;
+    let player3_id = create_player_account(&mut client).await;
+    let player4_id = create_player_account(&mut client).await;
+    let player_ids = vec![player1_id, player2_id, player3_id, player4_id];
+
+    // Create an game account
+    let game_account_id = create_game_account(&mut client).await;
+
+    // Mask the cards
+    mask_cards(&mut client, game_account_id, player_ids.clone()).await;
+    remask_cards(&mut client, game_account_id, player_ids.clone(), DEFAULT_ACTION_TYPE + 1).await;
+    remask_cards(&mut client, game_account_id, player_ids.clone(), DEFAULT_ACTION_TYPE + 2).await;
+    remask_cards(&mut client, game_account_id, player_ids.clone(), DEFAULT_ACTION_TYPE + 3).await;
+
+    for (i, player_id) in player_ids.iter().enumerate() {
+        let action_type = slot_value(&client, *player_id, PLAYER_DATA_SLOT);
+        assert_eq!(action_type, DEFAULT_ACTION_TYPE + i as u64);
+    }
+
+    // Peek hand
+    for player_id in player_ids.iter() {
+        peek_hand(&mut client, *player_id).await;
+        let (player_account, _) = client.get_account(*player_id).unwrap();
+        let player_card1 = player_account.storage().get_item(PLAYER_CARD1_SLOT);
+        let player_card2 = player_account.storage().get_item(PLAYER_CARD2_SLOT);
+        assert_eq!(player_card1, RpoDigest::new([Felt::from(17_u8), Felt::ZERO, Felt::ZERO, Felt::ZERO]));
+        assert_eq!(player_card2, RpoDigest::new([Felt::from(18_u8), Felt::ZERO, Felt::ZERO, Felt::ZERO]));
+    }
+
+    // Betting round, first player opens with the small blind and everyone else calls
+    play_bet(&mut client, game_account_id, player1_id, SMALL_BLIND_AMOUNT).await;
+    assert_eq!(slot_value(&client, game_account_id, HIGHEST_BET_SLOT), SMALL_BLIND_AMOUNT as u64);
+    assert_eq!(
+        slot_value(&client, game_account_id, CURRENT_TURN_INDEX_SLOT),
+        (FIRST_PLAYER_INDEX + PLAYER_STATS_SLOTS) as u64
+    );
+
+    for player_id in player_ids.iter().skip(1) {
+        play_call(&mut client, game_account_id, *player_id).await;
+    }
+
+    for i in 0..player_ids.len() as u8 {
+        let player_index = FIRST_PLAYER_INDEX + i * PLAYER_STATS_SLOTS;
+        assert_eq!(
+            slot_value(&client, game_account_id, player_index + PLAYER_BET_OFFSET),
+            SMALL_BLIND_AMOUNT as u64
+        );
+        assert_eq!(
+            slot_value(&client, game_account_id, PLAYER_BALANCE_SLOT + i * PLAYER_STATS_SLOTS),
+            (PLAYER_INITIAL_BALANCE - SMALL_BLIND_AMOUNT) as u64
+        );
+    }
+
+    // the small blind checks, the big blind checks its option and the flop is dealt
+    play_check(&mut client, game_account_id, player1_id).await;
+    play_check(&mut client, game_account_id, player2_id).await;
+    assert_eq!(slot_value(&client, game_account_id, CURRENT_PHASE_SLOT), 1);
+    assert_eq!(
+        slot_value(&client, game_account_id, CURRENT_TURN_INDEX_SLOT),
+        FIRST_PLAYER_INDEX as u64
+    );
+
+    // the flop and the turn are checked around, the river is turned for the showdown
+    for phase in 1..3_u8 {
+        unmask_community_cards(&mut client, game_account_id, player1_id, phase).await;
+        for player_id in player_ids.iter() {
+            play_check(&mut client, game_account_id, *player_id).await;
+        }
+        assert_eq!(slot_value(&client, game_account_id, CURRENT_PHASE_SLOT), phase as u64 + 1);
+        assert_eq!(
+            slot_value(&client, game_account_id, CURRENT_TURN_INDEX_SLOT),
+            FIRST_PLAYER_INDEX as u64
+        );
+    }
+    unmask_community_cards(&mut client, game_account_id, player1_id, 3).await;
+    let pot = slot_value(&client, game_account_id, POT_VALUE);
+    assert_eq!(pot, player_ids.len() as u64 * SMALL_BLIND_AMOUNT as u64);
+
+    // Commit hands, player 1 holds the best one
+    let player_hands: [u8; 4] = [7, 3, 2, 1];
+    for (seat, player_id) in player_ids.iter().enumerate() {
+        commit_hand(&mut client, game_account_id, *player_id, player_hands[seat], seat as u8).await;
+    }
+    let (game_account, _) = client.get_account(game_account_id).unwrap();
+    let commited_cards = game_account.storage().get_item(FIRST_PLAYER_INDEX + HAND_OFFSET);
+    assert_eq!(commited_cards, RpoDigest::new([Felt::from(17_u8), Felt::from(18_u8), Felt::from(player_hands[0]), Felt::ZERO]));
+
+    // Showdown, the dealer pays the whole pot to player 1
+    end_hand(&mut client, game_account_id, pot, [pot, 0, 0, 0]).await;
+    assert_eq!(slot_value(&client, game_account_id, CURRENT_PHASE_SLOT), HAND_OVER_PHASE);
+    let balances: Vec<u64> = (0..player_ids.len() as u8)
+        .map(|i| slot_value(&client, game_account_id, PLAYER_BALANCE_SLOT + i * PLAYER_STATS_SLOTS))
+        .collect();
+    let stack = (PLAYER_INITIAL_BALANCE - SMALL_BLIND_AMOUNT) as u64;
+    assert_eq!(balances, vec![stack + pot, stack, stack, stack]);
+    assert_eq!(balances.iter().sum::<u64>(), player_ids.len() as u64 * PLAYER_INITIAL_BALANCE as u64);
+
+    // every transaction went into its own block
+    assert!(node.chain_tip() > 0);
+}
+
+// a re-raise charges only what the raiser still owes, on the game account and off-chain alike
+#[tokio::test]
+async fn test_mock_reraise() {
+    let node = MockNode::new();
+    let mut client = create_mock_test_client(&node);
+
+    let mut player_ids = vec![];
+    for _ in 0..4 {
+        player_ids.push(create_player_account(&mut client).await);
+    }
+    let game_account_id = create_game_account(&mut client).await;
+    let small_blind = SMALL_BLIND_AMOUNT as u64;
+    let mut game = PokerGame::new(
+        (1..=4).collect(),
+        vec![PLAYER_INITIAL_BALANCE as u64; 4],
+        small_blind,
+        small_blind * 2,
+    );
+    let action = |action_type, amount| Check_Action { action_type, amount };
+
+    // player 1 opens, player 2 raises to 10, both others call, player 1 re-raises to 20
+    play_bet(&mut client, game_account_id, player_ids[0], SMALL_BLIND_AMOUNT).await;
+    assert!(game.check_move(action(ActionType::SmallBlind, None), 1));
+    play_raise(&mut client, game_account_id, player_ids[1], SMALL_BLIND_AMOUNT * 2).await;
+    assert!(game.check_move(action(ActionType::Raise, Some(small_blind)), 2));
+    for seat in 2..4 {
+        play_call(&mut client, game_account_id, player_ids[seat]).await;
+        assert!(game.check_move(action(ActionType::Call, None), seat as u64 + 1));
+    }
+    play_raise(&mut client, game_account_id, player_ids[0], SMALL_BLIND_AMOUNT * 4).await;
+    assert!(game.check_move(action(ActionType::Raise, Some(small_blind * 2)), 1));
+
+    // the re-raiser only put in the 15 their open didn't cover
+    let player1_balance = slot_value(&client, game_account_id, PLAYER_BALANCE_SLOT);
+    assert_eq!(player1_balance, PLAYER_INITIAL_BALANCE as u64 - 4 * small_blind);
+    assert_eq!(slot_value(&client, game_account_id, POT_VALUE), 9 * small_blind);
+
+    for seat in 1..4 {
+        play_call(&mut client, game_account_id, player_ids[seat]).await;
+        assert!(game.check_move(action(ActionType::Call, None), seat as u64 + 1));
+    }
+
+    assert_eq!(slot_value(&client, game_account_id, POT_VALUE), 16 * small_blind);
+    assert_eq!(slot_value(&client, game_account_id, POT_VALUE), game.pot());
+    assert_eq!(slot_value(&client, game_account_id, HIGHEST_BET_SLOT), game.current_bet());
+    for seat in 0..4_u8 {
+        assert_eq!(
+            slot_value(&client, game_account_id, PLAYER_BALANCE_SLOT + seat * PLAYER_STATS_SLOTS),
+            game.balance(seat as u64 + 1).unwrap()
+        );
+    }
+    // the last call closed the street
+    assert_eq!(slot_value(&client, game_account_id, CURRENT_PHASE_SLOT), 1);
+}
+
+// a seat is cashed out only by its owner between hands, the balance leaves the vault as a P2ID note
+#[tokio::test]
+async fn test_mock_cash_out() {
+    let node = MockNode::new();
+    let mut client = create_mock_test_client(&node);
+
+    let mut player_ids = vec![];
+    for _ in 0..4 {
+        player_ids.push(create_player_account(&mut client).await);
+    }
+    let faucet_id = create_buy_in_faucet(&mut client).await;
+    let game_account_id = create_escrow_game_account(&mut client, &player_ids, faucet_id).await;
+    let game_nonce = |client: &AzeClient<MockRpcClient>| client.get_account(game_account_id).unwrap().0.nonce();
+    let seat_slot = FIRST_PLAYER_INDEX + SEAT_OFFSET;
+
+    play_bet(&mut client, game_account_id, player_ids[0], SMALL_BLIND_AMOUNT).await;
+    for player_id in player_ids.iter().skip(1) {
+        play_call(&mut client, game_account_id, *player_id).await;
+    }
+
+    // nobody leaves with chips in the pot
+    let nonce = game_nonce(&client);
+    cash_out(&mut client, game_account_id, player_ids[0], 0).await;
+    assert_eq!(game_nonce(&client), nonce);
+    assert_eq!(slot_value(&client, game_account_id, seat_slot), 1);
+
+    let pot = slot_value(&client, game_account_id, POT_VALUE);
+    end_hand(&mut client, game_account_id, pot, [pot, 0, 0, 0]).await;
+    let balance = slot_value(&client, game_account_id, PLAYER_BALANCE_SLOT);
+    assert_eq!(balance, (PLAYER_INITIAL_BALANCE - SMALL_BLIND_AMOUNT) as u64 + pot);
+
+    // player 2 can't cash out the seat of player 1
+    let nonce = game_nonce(&client);
+    cash_out(&mut client, game_account_id, player_ids[1], 0).await;
+    assert_eq!(game_nonce(&client), nonce);
+    assert_eq!(slot_value(&client, game_account_id, PLAYER_BALANCE_SLOT), balance);
+
+    cash_out(&mut client, game_account_id, player_ids[0], 0).await;
+    let (game_account, _) = client.get_account(game_account_id).unwrap();
+    assert_eq!(game_account.storage().get_item(seat_slot), RpoDigest::new([Felt::ZERO; 4]));
+    assert_eq!(slot_value(&client, game_account_id, PLAYER_BALANCE_SLOT), 0);
+    assert_eq!(slot_value(&client, game_account_id, FIRST_PLAYER_INDEX + IS_FOLD_OFFSET), 1);
+    let escrow = 4 * PLAYER_INITIAL_BALANCE as u64;
+    assert_eq!(game_account.vault().get_balance(faucet_id).unwrap(), escrow - balance);
+
+    // the payout note carries the whole balance to player 1
+    client.sync_state().await.unwrap();
+    let payouts: Vec<u64> = client
+        .get_consumable_notes(Some(player_ids[0]))
+        .unwrap()
+        .iter()
+        .flat_map(|consumable_note| consumable_note.note.assets().iter().cloned().collect::<Vec<_>>())
+        .filter_map(|asset| match asset {
+            Asset::Fungible(asset) if asset.faucet_id() == faucet_id => Some(asset.amount()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(payouts, vec![balance]);
+}
@@ -1,5 +1,6 @@
 use aze_lib::client::{
     create_aze_client,
+    create_mock_aze_client,
     AzeAccountTemplate,
     AzeClient,
     AzeGameMethods,
@@ -13,6 +14,12 @@ use aze_lib::client::{
     SetCardsTransactionData,
     SetHandTransactionData,
     SendCommunityCardsTransactionData,
+    PlayBetTransactionData,
+    PlayCallTransactionData,
+    PlayCheckTransactionData,
+    PlayRaiseTransactionData,
+    EndHandTransactionData,
+    CashOutTransactionData,
 };
 use aze_lib::accounts::create_basic_aze_player_account;
 use aze_lib::constants::{
@@ -28,6 +35,7 @@ use aze_lib::constants::{
     PLAYER_CARD1_SLOT,
     PLAYER_CARD2_SLOT,
     TEMP_CARD_SLOT,
+    MAX_HOLE_CARDS,
     REQUESTER_SLOT,
     CURRENT_PHASE_SLOT,
     FLOP_SLOT,
@@ -37,15 +45,18 @@ use aze_lib::executor::execute_tx_and_sync;
 use aze_lib::utils::{ get_random_coin, load_config };
 use aze_lib::notes::{ consume_notes, mint_note };
 use aze_lib::storage::GameStorageSlotData;
+use aze_lib::mock::{ MockNode, MockRpcClient };
 use miden_client::{
     client::{
         accounts::{ AccountTemplate, AccountStorageMode },
-        transactions::transaction_request::TransactionTemplate,
+        rpc::NodeRpcClient,
+        transactions::transaction_request::{ TransactionRequest, TransactionTemplate },
     },
 };
 use miden_lib::AuthScheme;
 use miden_objects::{
     accounts::{ Account, AccountId, AccountType, AuthSecretKey },
+    assets::TokenSymbol,
     crypto::{
         dsa::rpo_falcon512::{PublicKey, SecretKey},
         hash::rpo::RpoDigest,
@@ -58,7 +69,11 @@ pub fn create_test_client() -> AzeClient {
     create_aze_client()
 }
 
-pub async fn create_player_account(client: &mut AzeClient) -> AccountId {
+pub fn create_mock_test_client(node: &MockNode) -> AzeClient<MockRpcClient> {
+    create_mock_aze_client(node)
+}
+
+pub async fn create_player_account<N: NodeRpcClient>(client: &mut AzeClient<N>) -> AccountId {
     let key_pair = SecretKey::new();
     let pub_key: PublicKey = key_pair.public_key();
     let auth_scheme: AuthScheme = AuthScheme::RpoFalcon512 { pub_key };
@@ -99,10 +114,10 @@ pub async fn create_player_account(client: &mut AzeClient) -> AccountId {
     player_account.id()
 }
 
-pub async fn create_game_account(client: &mut AzeClient) -> AccountId {
+pub async fn create_game_account<N: NodeRpcClient>(client: &mut AzeClient<N>) -> AccountId {
     let slot_data = GameStorageSlotData::new(
         SMALL_BLIND_AMOUNT,
-        BUY_IN_AMOUNT as u8,
+        BUY_IN_AMOUNT,
         NO_OF_PLAYERS,
         FIRST_PLAYER_INDEX,
         HIGHEST_BET,
@@ -122,7 +137,54 @@ pub async fn create_game_account(client: &mut AzeClient) -> AccountId {
     game_account.id()
 }
 
-pub async fn mask_cards(client: &mut AzeClient, game_account_id: AccountId, player_account_ids: Vec<AccountId>) {
+pub async fn create_buy_in_faucet<N: NodeRpcClient>(client: &mut AzeClient<N>) -> AccountId {
+    let (faucet_account, _) = client
+        .new_account(AccountTemplate::FungibleFaucet {
+            token_symbol: TokenSymbol::new("AZE").unwrap(),
+            decimals: 0,
+            max_supply: 1_000_000_000,
+            storage_mode: AccountStorageMode::Local,
+        })
+        .unwrap();
+    faucet_account.id()
+}
+
+// table the players are seated at, with their buy-ins of the faucet's asset escrowed in its vault
+pub async fn create_escrow_game_account<N: NodeRpcClient>(
+    client: &mut AzeClient<N>,
+    player_account_ids: &[AccountId],
+    faucet_account_id: AccountId,
+) -> AccountId {
+    let player_ids: Vec<u64> = player_account_ids.iter().map(|id| (*id).into()).collect();
+    let slot_data = GameStorageSlotData::new(
+        SMALL_BLIND_AMOUNT,
+        BUY_IN_AMOUNT,
+        NO_OF_PLAYERS,
+        FIRST_PLAYER_INDEX,
+        HIGHEST_BET,
+        PLAYER_INITIAL_BALANCE,
+    )
+    .with_seated_players(&player_ids)
+    .with_buy_in_faucet(faucet_account_id.into());
+
+    let (game_account, _) = client
+        .new_game_account(
+            AzeAccountTemplate::GameAccount {
+                mutable_code: false,
+                storage_mode: AccountStorageMode::Local,
+            },
+            Some(slot_data),
+        )
+        .unwrap();
+
+    let escrow = PLAYER_INITIAL_BALANCE as u64 * player_ids.len() as u64;
+    let note = mint_note(client, game_account.id(), faucet_account_id, escrow, NoteType::Public).await;
+    consume_notes(client, game_account.id(), &[note]).await;
+
+    game_account.id()
+}
+
+pub async fn mask_cards<N: NodeRpcClient>(client: &mut AzeClient<N>, game_account_id: AccountId, player_account_ids: Vec<AccountId>) {
     let target_account_id = player_account_ids[0];
     let shuffle_card_data = ShuffleCardTransactionData::new(
         game_account_id,
@@ -140,7 +202,7 @@ pub async fn mask_cards(client: &mut AzeClient, game_account_id: AccountId, play
     consume_notes(client, target_account_id, &[note.try_into().unwrap()]).await;
 }
 
-pub async fn remask_cards(client: &mut AzeClient, game_account_id: AccountId, player_account_ids: Vec<AccountId>, action_type: u64) {
+pub async fn remask_cards<N: NodeRpcClient>(client: &mut AzeClient<N>, game_account_id: AccountId, player_account_ids: Vec<AccountId>, action_type: u64) {
     let target_account_id = player_account_ids[action_type as usize - 1];
     let mut player_data = vec![player_account_ids[0].into(), player_account_ids[1].into(), player_account_ids[2].into(), player_account_ids[3].into()];
     player_data.remove(action_type as usize - 1);
@@ -198,13 +260,13 @@ pub async fn remask_cards(client: &mut AzeClient, game_account_id: AccountId, pl
     }
 }
 
-pub async fn peek_hand(client: &mut AzeClient, player_account_id: AccountId) {
+pub async fn peek_hand<N: NodeRpcClient>(client: &mut AzeClient<N>, player_account_id: AccountId) {
     let card_slot_start = PLAYER_CARD1_SLOT;
     let card_slot_end = PLAYER_CARD2_SLOT;
     p2p_unmask_flow(client, player_account_id, [card_slot_start, card_slot_end]).await;
 }
 
-pub async fn unmask_community_cards(client: &mut AzeClient, game_account_id: AccountId, player_account_id: AccountId, current_phase: u8) {
+pub async fn unmask_community_cards<N: NodeRpcClient>(client: &mut AzeClient<N>, game_account_id: AccountId, player_account_id: AccountId, current_phase: u8) {
     let (game_account, _) = client.get_account(game_account_id).unwrap();
     let mut cards: [[Felt; 4]; 3] = [[Felt::ZERO; 4]; 3];
     for (i, slot) in (1..4).enumerate() {
@@ -278,11 +340,11 @@ pub async fn unmask_community_cards(client: &mut AzeClient, game_account_id: Acc
     // }
 }
 
-pub async fn p2p_unmask_flow(client: &mut AzeClient, player_account_id: AccountId, card_slots: [u8; 2]) {
+pub async fn p2p_unmask_flow<N: NodeRpcClient>(client: &mut AzeClient<N>, player_account_id: AccountId, card_slots: [u8; 2]) {
     let (player_account, _) = client.get_account(player_account_id).unwrap();
     let player_data = player_account.storage().get_item(PLAYER_DATA_SLOT).as_elements().to_vec();
     let player_ids = [player_data[1].as_int(), player_data[2].as_int(), player_data[3].as_int()];
-    let mut cards: [[Felt; 4]; 3] = [[Felt::ZERO; 4]; 3];
+    let mut cards: [[Felt; 4]; MAX_HOLE_CARDS] = [[Felt::ZERO; 4]; MAX_HOLE_CARDS];
     for (i, slot) in (card_slots[0]..card_slots[1] + 1).enumerate() {
         let card_digest = player_account.storage().get_item(slot);
         cards[i] = card_digest.into();
@@ -316,8 +378,8 @@ pub async fn p2p_unmask_flow(client: &mut AzeClient, player_account_id: AccountI
         // check if the requester is the player_account_id
         assert_eq!(requester_id, player_account_id);
 
-        let mut cards: [[Felt; 4]; 3] = [[Felt::ZERO; 4]; 3];
-        for (i, slot) in (TEMP_CARD_SLOT..TEMP_CARD_SLOT + 3).enumerate() {
+        let mut cards: [[Felt; 4]; MAX_HOLE_CARDS] = [[Felt::ZERO; 4]; MAX_HOLE_CARDS];
+        for (i, slot) in (TEMP_CARD_SLOT..TEMP_CARD_SLOT + MAX_HOLE_CARDS as u8).enumerate() {
             let card_digest = player_account.storage().get_item(slot);
             cards[i] = card_digest.into();
         }
@@ -341,7 +403,7 @@ pub async fn p2p_unmask_flow(client: &mut AzeClient, player_account_id: AccountI
         // check if the action type changed
         assert_eq!(action_type, action_type_post);
         // check cards
-        for (i, slot) in (TEMP_CARD_SLOT..TEMP_CARD_SLOT + 3).enumerate() {
+        for (i, slot) in (TEMP_CARD_SLOT..TEMP_CARD_SLOT + MAX_HOLE_CARDS as u8).enumerate() {
             let card: [Felt; 4] = player_account.storage().get_item(slot).into();
             assert_eq!(cards[i], card);
         }
@@ -364,8 +426,8 @@ pub async fn p2p_unmask_flow(client: &mut AzeClient, player_account_id: AccountI
     consume_notes(client, player_account_id, &[note.try_into().unwrap()]).await;
 }
 
-pub async fn commit_hand(
-    client: &mut AzeClient,
+pub async fn commit_hand<N: NodeRpcClient>(
+    client: &mut AzeClient<N>,
     game_account_id: AccountId, 
     player_account_id: AccountId,
     player_hand: u8,
@@ -374,7 +436,7 @@ pub async fn commit_hand(
     let (player_account, _) = client.get_account(player_account_id).unwrap();
 
     // send commit hand note to game account
-    let mut cards: [[Felt; 4]; 2] = [[Felt::ZERO; 4]; 2];
+    let mut cards: [[Felt; 4]; MAX_HOLE_CARDS] = [[Felt::ZERO; 4]; MAX_HOLE_CARDS];
     for (i, slot) in (PLAYER_CARD1_SLOT..PLAYER_CARD2_SLOT + 1).enumerate() {
         let card = player_account.storage().get_item(slot);
         cards[i] = card.into();
@@ -395,4 +457,105 @@ pub async fn commit_hand(
     let note_id = txn_request.expected_output_notes()[0].id();
     let note = client.get_input_note(note_id).unwrap();
     consume_notes(client, game_account_id, &[note.try_into().unwrap()]).await;
-}
\ No newline at end of file
+}
+
+pub async fn play_bet<N: NodeRpcClient>(
+    client: &mut AzeClient<N>,
+    game_account_id: AccountId,
+    player_account_id: AccountId,
+    player_bet: u8,
+) {
+    let play_bet_data = PlayBetTransactionData::new(
+        player_account_id,
+        game_account_id,
+        player_bet,
+    );
+    let transaction_template = AzeTransactionTemplate::PlayBet(play_bet_data);
+    let txn_request = client
+        .build_aze_play_bet_tx_request(transaction_template)
+        .unwrap();
+    send_action_to_game(client, game_account_id, txn_request).await;
+}
+
+pub async fn play_raise<N: NodeRpcClient>(
+    client: &mut AzeClient<N>,
+    game_account_id: AccountId,
+    player_account_id: AccountId,
+    player_bet: u8,
+) {
+    let play_raise_data = PlayRaiseTransactionData::new(
+        player_account_id,
+        game_account_id,
+        player_bet,
+    );
+    let transaction_template = AzeTransactionTemplate::PlayRaise(play_raise_data);
+    let txn_request = client
+        .build_aze_play_raise_tx_request(transaction_template)
+        .unwrap();
+    send_action_to_game(client, game_account_id, txn_request).await;
+}
+
+pub async fn play_call<N: NodeRpcClient>(
+    client: &mut AzeClient<N>,
+    game_account_id: AccountId,
+    player_account_id: AccountId,
+) {
+    let play_call_data = PlayCallTransactionData::new(player_account_id, game_account_id);
+    let transaction_template = AzeTransactionTemplate::PlayCall(play_call_data);
+    let txn_request = client
+        .build_aze_play_call_tx_request(transaction_template)
+        .unwrap();
+    send_action_to_game(client, game_account_id, txn_request).await;
+}
+
+pub async fn play_check<N: NodeRpcClient>(
+    client: &mut AzeClient<N>,
+    game_account_id: AccountId,
+    player_account_id: AccountId,
+) {
+    let play_check_data = PlayCheckTransactionData::new(player_account_id, game_account_id);
+    let transaction_template = AzeTransactionTemplate::PlayCheck(play_check_data);
+    let txn_request = client
+        .build_aze_play_check_tx_request(transaction_template)
+        .unwrap();
+    send_action_to_game(client, game_account_id, txn_request).await;
+}
+
+pub async fn end_hand<N: NodeRpcClient>(
+    client: &mut AzeClient<N>,
+    game_account_id: AccountId,
+    pot: u64,
+    winnings: [u64; NO_OF_PLAYERS as usize],
+) {
+    let end_hand_data = EndHandTransactionData::new(game_account_id, game_account_id, pot, winnings);
+    let transaction_template = AzeTransactionTemplate::EndHand(end_hand_data);
+    let txn_request = client
+        .build_aze_end_hand_tx_request(transaction_template)
+        .unwrap();
+    send_action_to_game(client, game_account_id, txn_request).await;
+}
+
+pub async fn cash_out<N: NodeRpcClient>(
+    client: &mut AzeClient<N>,
+    game_account_id: AccountId,
+    player_account_id: AccountId,
+    seat: u8,
+) {
+    let cash_out_data = CashOutTransactionData::new(player_account_id, game_account_id, seat);
+    let transaction_template = AzeTransactionTemplate::CashOut(cash_out_data);
+    let txn_request = client
+        .build_aze_cash_out_tx_request(transaction_template)
+        .unwrap();
+    send_action_to_game(client, game_account_id, txn_request).await;
+}
+
+async fn send_action_to_game<N: NodeRpcClient>(
+    client: &mut AzeClient<N>,
+    game_account_id: AccountId,
+    txn_request: TransactionRequest,
+) {
+    execute_tx_and_sync(client, txn_request.clone()).await;
+    let note_id = txn_request.expected_output_notes()[0].id();
+    let note = client.get_input_note(note_id).unwrap();
+    consume_notes(client, game_account_id, &[note.try_into().unwrap()]).await;
+}
@@ -9,8 +9,8 @@ use miden_objects::{
     AccountError, Felt, FieldElement, Word, ZERO,
 };
 
-use crate::constants::{ PLAYER_STATS_SLOTS, SECRET_KEY_SLOT, DEFAULT_SKEY, MASKING_FACTOR_SLOT, DEFAULT_MASKING_FACTOR };
-use crate::storage::GameStorageSlotData;
+use crate::constants::{ PLAYER_STATS_SLOTS, IS_FOLD_OFFSET, SEAT_OFFSET, SEAT_TAKEN, SECRET_KEY_SLOT, DEFAULT_SKEY, MASKING_FACTOR_SLOT, DEFAULT_MASKING_FACTOR, RAKE_PERCENT_SLOT, RAKE_CAP_SLOT, RAKE_OPERATOR_SLOT, STARTING_STACK_SLOT, BETTING_STRUCTURE_SLOT, GAME_VARIANT_SLOT, FORCED_BETS_SLOT, BUTTON_SLOT, FIRST_PLAYER_INDEX, POT_VALUE, PLAYER_CARD1_SLOT, PLAYER_CARD2_SLOT };
+use crate::storage::{betting_structure_word, forced_bets_word, GameStorageSlotData};
 use miden_lib::{transaction::TransactionKernel, AuthScheme};
 
 fn construct_game_constructor_storage(
@@ -100,7 +100,7 @@ fn construct_game_constructor_storage(
             slot: StorageSlot {
                 slot_type: StorageSlotType::Value { value_arity: 0 },
                 value: [
-                    Felt::from(buy_in_amt as u8),
+                    Felt::new(buy_in_amt),
                     Felt::ZERO,
                     Felt::ZERO,
                     Felt::ZERO,
@@ -126,6 +126,18 @@ fn construct_game_constructor_storage(
                 value: [Felt::ZERO, Felt::ZERO, Felt::ZERO, Felt::ZERO],
             },
         },
+        SlotItem {
+            index: slot_index + 6, // storing buy-in faucet id
+            slot: StorageSlot {
+                slot_type: StorageSlotType::Value { value_arity: 0 },
+                value: [
+                    Felt::new(slot_data.buy_in_faucet()),
+                    Felt::ZERO,
+                    Felt::ZERO,
+                    Felt::ZERO,
+                ],
+            },
+        },
         SlotItem {
             index: slot_index + 7, // storing curr turn pub key index
             slot: StorageSlot {
@@ -150,12 +162,25 @@ fn construct_game_constructor_storage(
                 ],
             },
         },
+        SlotItem {
+            index: slot_index + 9, // storing current phase
+            slot: StorageSlot {
+                slot_type: StorageSlotType::Value { value_arity: 0 },
+                value: [
+                    Felt::new(slot_data.current_phase()),
+                    Felt::ZERO,
+                    Felt::ZERO,
+                    Felt::ZERO,
+                ],
+            },
+        },
     ];
 
     slot_index += 12;
 
-    for _ in 0..no_of_players {
-        let player_slots = vec![
+    for seat in 0..no_of_players {
+        let seated_player = slot_data.seated_player(seat);
+        let mut player_slots = vec![
             SlotItem {
                 index: slot_index, // pub key
                 slot: StorageSlot {
@@ -180,7 +205,11 @@ fn construct_game_constructor_storage(
                 slot: StorageSlot {
                     slot_type: StorageSlotType::Value { value_arity: 0 },
                     value: [
-                        Felt::from(slot_data.player_balance()),
+                        Felt::new(if seated_player.is_some() {
+                            slot_data.player_balance() as u64 - slot_data.ante_posted(seat)
+                        } else {
+                            0
+                        }),
                         Felt::ZERO,
                         Felt::ZERO,
                         Felt::ZERO,
@@ -188,16 +217,74 @@ fn construct_game_constructor_storage(
                 },
             },
         ];
+        match seated_player {
+            Some(player_id) => player_slots.push(SlotItem {
+                index: slot_index + SEAT_OFFSET - 1, // seat
+                slot: StorageSlot {
+                    slot_type: StorageSlotType::Value { value_arity: 0 },
+                    value: [Felt::new(SEAT_TAKEN), Felt::new(player_id), Felt::ZERO, Felt::ZERO],
+                },
+            }),
+            // empty seats stay folded so turns skip them
+            None => player_slots.push(SlotItem {
+                index: slot_index + IS_FOLD_OFFSET - 1, // is fold
+                slot: StorageSlot {
+                    slot_type: StorageSlotType::Value { value_arity: 0 },
+                    value: [Felt::ONE, Felt::ZERO, Felt::ZERO, Felt::ZERO],
+                },
+            }),
+        }
         player_pub_keys.extend(player_slots);
 
         slot_index += PLAYER_STATS_SLOTS; // since the mid 13 elements would cover the player stats and initially all those values are zero
     }
 
+    // the collected rake and the hands played start at zero
+    let table_config = [
+        (RAKE_PERCENT_SLOT, slot_data.rake_percent()),
+        (RAKE_CAP_SLOT, slot_data.rake_cap()),
+        (RAKE_OPERATOR_SLOT, slot_data.rake_operator()),
+        (STARTING_STACK_SLOT, slot_data.starting_stack()),
+        (GAME_VARIANT_SLOT, slot_data.variant().to_slot()),
+        // the first hand of a table created with players starts with the antes in the pot
+        (POT_VALUE, (0..no_of_players).map(|seat| slot_data.ante_posted(seat)).sum()),
+        // the button starts on the last seat, so the first seat posts the small blind
+        (
+            BUTTON_SLOT,
+            FIRST_PLAYER_INDEX as u64 + PLAYER_STATS_SLOTS as u64 * (no_of_players as u64 - 1),
+        ),
+    ]
+    .into_iter()
+    .map(|(index, value)| SlotItem {
+        index,
+        slot: StorageSlot {
+            slot_type: StorageSlotType::Value { value_arity: 0 },
+            value: [Felt::new(value), Felt::ZERO, Felt::ZERO, Felt::ZERO],
+        },
+    });
+    let betting_structure = SlotItem {
+        index: BETTING_STRUCTURE_SLOT,
+        slot: StorageSlot {
+            slot_type: StorageSlotType::Value { value_arity: 0 },
+            value: betting_structure_word(slot_data.betting_structure()).map(Felt::new),
+        },
+    };
+    let forced_bets = SlotItem {
+        index: FORCED_BETS_SLOT,
+        slot: StorageSlot {
+            slot_type: StorageSlotType::Value { value_arity: 0 },
+            value: forced_bets_word(slot_data.ante(), slot_data.straddle()).map(Felt::new),
+        },
+    };
+
     // merge player_id with card_suit
     game_info.push(auth_slot);
     game_info.extend(cards);
     game_info.extend(game_stats);
     game_info.extend(player_pub_keys);
+    game_info.extend(table_config);
+    game_info.push(betting_structure);
+    game_info.push(forced_bets);
     game_info
 }
 
@@ -310,14 +397,14 @@ pub fn create_basic_aze_player_account(
             },
             // for testing only,
             SlotItem {
-                index: 100,
+                index: PLAYER_CARD1_SLOT,
                 slot: StorageSlot {
                     slot_type: StorageSlotType::Value { value_arity: 0 },
                     value: [Felt::new(15911754940807515092), Felt::new(127677651693142771), Felt::ZERO, Felt::ZERO],
                 },
             },
             SlotItem {
-                index: 101,
+                index: PLAYER_CARD2_SLOT,
                 slot: StorageSlot {
                     slot_type: StorageSlotType::Value { value_arity: 0 },
                     value: [Felt::new(15911754940807515092), Felt::new(7730906248022274125), Felt::ZERO, Felt::ZERO],
@@ -0,0 +1,350 @@
+use crate::client::AzeClient;
+use miden_client::client::rpc::{AccountDetails, NodeRpcClient, TonicRpcClient};
+use miden_client::config::RpcConfig;
+use miden_objects::{
+    accounts::{AccountId, AuthSecretKey},
+    crypto::{
+        dsa::rpo_falcon512::{PublicKey, SecretKey, Signature},
+        hash::rpo::Rpo256,
+        utils::{Deserializable, Serializable},
+    },
+    Felt, Word,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// Requests to the game server are signed with the Falcon key of the account sending them, the
+// same key that authenticates its transactions and whose commitment is in storage slot 0.
+
+// how far a request's timestamp may drift from the server's clock
+pub const MAX_REQUEST_AGE_MILLIS: u64 = 60_000;
+
+// websocket handshakes can't have a body, they carry the signed request in this header
+pub const SIGNED_REQUEST_HEADER: &str = "x-aze-signed-request";
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct SignedRequest {
+    pub account_id: u64,
+    // milliseconds since the unix epoch, must grow with every request of the account
+    pub timestamp: u64,
+    pub public_key: [u64; 4],
+    // hex encoded Falcon signature over the account id, timestamp and payload
+    pub signature: String,
+    // JSON of the actual request, signed as is
+    pub payload: String,
+}
+
+impl SignedRequest {
+    pub fn payload<T: DeserializeOwned>(&self) -> Result<T, AuthError> {
+        serde_json::from_str(&self.payload).map_err(|_| AuthError::BadPayload)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthError {
+    Unsigned,
+    UnknownAccount(u64),
+    KeyUnavailable(u64),
+    KeyMismatch(u64),
+    NotSigner(u64),
+    BadSignature,
+    StaleRequest,
+    BadPayload,
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::Unsigned => write!(f, "Request is not signed"),
+            AuthError::UnknownAccount(account_id) => {
+                write!(f, "Account {} is not part of this game", account_id)
+            }
+            AuthError::KeyUnavailable(account_id) => write!(
+                f,
+                "Public key of account {} could not be read from the node",
+                account_id
+            ),
+            AuthError::KeyMismatch(account_id) => {
+                write!(f, "Public key does not belong to account {}", account_id)
+            }
+            AuthError::NotSigner(account_id) => {
+                write!(f, "Request is not signed by account {}", account_id)
+            }
+            AuthError::BadSignature => write!(f, "Invalid signature"),
+            AuthError::StaleRequest => write!(f, "Request expired or replayed"),
+            AuthError::BadPayload => write!(f, "Malformed request payload"),
+        }
+    }
+}
+
+pub struct RequestSigner {
+    account_id: u64,
+    secret_key: SecretKey,
+}
+
+impl RequestSigner {
+    pub fn new(account_id: u64, secret_key: SecretKey) -> Self {
+        Self {
+            account_id,
+            secret_key,
+        }
+    }
+
+    // signs with the key the client stored when the account was created
+    pub fn from_client<N: NodeRpcClient>(
+        client: &AzeClient<N>,
+        account_id: AccountId,
+    ) -> Result<Self, String> {
+        match client.get_account_auth(account_id) {
+            Ok(AuthSecretKey::RpoFalcon512(secret_key)) => {
+                Ok(Self::new(account_id.into(), secret_key))
+            }
+            Err(e) => Err(format!("No key found for account {}: {}", account_id, e)),
+        }
+    }
+
+    pub fn account_id(&self) -> u64 {
+        self.account_id
+    }
+
+    pub fn sign<T: Serialize>(&self, payload: &T) -> SignedRequest {
+        let payload = serde_json::to_string(payload).unwrap();
+        let timestamp = now_millis();
+        let signature = self
+            .secret_key
+            .sign(request_digest(self.account_id, timestamp, &payload));
+        let public_key: Word = self.secret_key.public_key().into();
+
+        SignedRequest {
+            account_id: self.account_id,
+            timestamp,
+            public_key: public_key.map(|felt| felt.as_int()),
+            signature: to_hex(&signature.to_bytes()),
+            payload,
+        }
+    }
+}
+
+/// Checks requests against the public keys of the accounts taking part in a game. Keys not
+/// added up front are read from storage slot 0 of the account on the node, a request is turned
+/// down when the node doesn't have it.
+pub struct RequestVerifier {
+    // None lets any account in, e.g. players queueing in the lobby
+    allowed: Option<Vec<u64>>,
+    node: RpcConfig,
+    known_keys: Mutex<HashMap<u64, Word>>,
+    last_timestamps: Mutex<HashMap<u64, u64>>,
+}
+
+impl RequestVerifier {
+    pub fn new(allowed: Vec<u64>, node: RpcConfig) -> Self {
+        Self {
+            allowed: Some(allowed),
+            node,
+            known_keys: Mutex::new(HashMap::new()),
+            last_timestamps: Mutex::new(HashMap::new()),
+        }
+    }
+
+    // accepts requests of any account whose key is on the node
+    pub fn open(node: RpcConfig) -> Self {
+        Self {
+            allowed: None,
+            node,
+            known_keys: Mutex::new(HashMap::new()),
+            last_timestamps: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub fn add_key(&self, account_id: u64, public_key: Word) {
+        self.known_keys.lock().unwrap().insert(account_id, public_key);
+    }
+
+    pub async fn verify(&self, request: &SignedRequest) -> Result<(), AuthError> {
+        let account_id = request.account_id;
+        if !self
+            .allowed
+            .as_ref()
+            .map_or(true, |allowed| allowed.contains(&account_id))
+        {
+            return Err(AuthError::UnknownAccount(account_id));
+        }
+
+        let now = now_millis();
+        if request.timestamp.abs_diff(now) > MAX_REQUEST_AGE_MILLIS {
+            return Err(AuthError::StaleRequest);
+        }
+
+        let public_key: Word = request.public_key.map(Felt::new);
+        let signature = from_hex(&request.signature)
+            .and_then(|bytes| Signature::read_from_bytes(&bytes).ok())
+            .ok_or(AuthError::BadSignature)?;
+        let message = request_digest(account_id, request.timestamp, &request.payload);
+        if !PublicKey::new(public_key).verify(message, &signature) {
+            return Err(AuthError::BadSignature);
+        }
+
+        let known_key = self.known_keys.lock().unwrap().get(&account_id).copied();
+        let account_key = match known_key {
+            Some(known_key) => known_key,
+            None => {
+                let account_key = on_chain_key(self.node.clone(), account_id).await?;
+                self.add_key(account_id, account_key);
+                account_key
+            }
+        };
+        if account_key != public_key {
+            return Err(AuthError::KeyMismatch(account_id));
+        }
+
+        let mut last_timestamps = self.last_timestamps.lock().unwrap();
+        let last_timestamp = last_timestamps.entry(account_id).or_insert(0);
+        if request.timestamp <= *last_timestamp {
+            return Err(AuthError::StaleRequest);
+        }
+        *last_timestamp = request.timestamp;
+        Ok(())
+    }
+}
+
+/// Reads the public key commitment in storage slot 0 of a public account from the node.
+pub async fn fetch_account_key<N: NodeRpcClient>(
+    rpc_api: &mut N,
+    account_id: u64,
+) -> Result<Word, AuthError> {
+    let id =
+        AccountId::try_from(account_id).map_err(|_| AuthError::UnknownAccount(account_id))?;
+    match rpc_api.get_account_update(id).await {
+        Ok(AccountDetails::Public(account, _)) => Ok(account.storage().get_item(0).into()),
+        _ => Err(AuthError::KeyUnavailable(account_id)),
+    }
+}
+
+// node requests aren't Send, so the key is read on a blocking thread with a runtime of its own
+async fn on_chain_key(node: RpcConfig, account_id: u64) -> Result<Word, AuthError> {
+    tokio::task::spawn_blocking(move || {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|_| AuthError::KeyUnavailable(account_id))?;
+        runtime.block_on(fetch_account_key(&mut TonicRpcClient::new(&node), account_id))
+    })
+    .await
+    .unwrap_or(Err(AuthError::KeyUnavailable(account_id)))
+}
+
+fn request_digest(account_id: u64, timestamp: u64, payload: &str) -> Word {
+    let mut message = Vec::new();
+    message.extend_from_slice(&account_id.to_le_bytes());
+    message.extend_from_slice(&timestamp.to_le_bytes());
+    message.extend_from_slice(payload.as_bytes());
+    Rpo256::hash(&message).into()
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap()
+        .as_millis() as u64
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+fn from_hex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accounts::create_basic_aze_player_account;
+    use crate::mock::MockNode;
+    use miden_lib::AuthScheme;
+    use miden_objects::accounts::AccountType;
+
+    fn signer(account_id: u64) -> RequestSigner {
+        RequestSigner::new(account_id, SecretKey::with_rng(&mut rand::thread_rng()))
+    }
+
+    fn public_key(signer: &RequestSigner) -> Word {
+        signer.secret_key.public_key().into()
+    }
+
+    #[tokio::test]
+    async fn test_signed_request_roundtrip() {
+        let signer = signer(1);
+        let verifier = RequestVerifier::new(vec![1], RpcConfig::default());
+        verifier.add_key(1, public_key(&signer));
+        let request = signer.sign(&vec![1u64, 2, 3]);
+        assert_eq!(verifier.verify(&request).await, Ok(()));
+        assert_eq!(request.payload::<Vec<u64>>(), Ok(vec![1, 2, 3]));
+        // the same request can't be sent twice
+        assert_eq!(verifier.verify(&request).await, Err(AuthError::StaleRequest));
+    }
+
+    #[tokio::test]
+    async fn test_rejects_forged_requests() {
+        let signer = signer(1);
+        let verifier = RequestVerifier::new(vec![1], RpcConfig::default());
+        verifier.add_key(1, public_key(&signer));
+
+        let mut tampered = signer.sign(&"fold");
+        tampered.payload = String::from("\"raise\"");
+        assert_eq!(verifier.verify(&tampered).await, Err(AuthError::BadSignature));
+
+        // someone signing as account 1 with their own key
+        let mut impersonated = self::signer(2).sign(&"fold");
+        impersonated.account_id = 1;
+        assert_eq!(verifier.verify(&impersonated).await, Err(AuthError::KeyMismatch(1)));
+
+        let outsider = self::signer(3).sign(&"fold");
+        assert_eq!(verifier.verify(&outsider).await, Err(AuthError::UnknownAccount(3)));
+
+        let open_verifier = RequestVerifier::open(RpcConfig::default());
+        let joining = self::signer(3);
+        open_verifier.add_key(3, public_key(&joining));
+        assert_eq!(open_verifier.verify(&joining.sign(&"join")).await, Ok(()));
+        let mut impersonated = self::signer(2).sign(&"join");
+        impersonated.account_id = 3;
+        assert_eq!(open_verifier.verify(&impersonated).await, Err(AuthError::KeyMismatch(3)));
+    }
+
+    #[tokio::test]
+    async fn test_key_not_on_the_node() {
+        let secret_key = SecretKey::with_rng(&mut rand::thread_rng());
+        let auth_scheme = AuthScheme::RpoFalcon512 {
+            pub_key: secret_key.public_key(),
+        };
+        let (account, _) = create_basic_aze_player_account(
+            [0; 32],
+            auth_scheme,
+            AccountType::RegularAccountImmutableCode,
+        )
+        .unwrap();
+        // the mock node keeps no account states, like a node that never saw the account
+        let mut rpc_api = MockNode::new().rpc_client();
+        let account_id: u64 = account.id().into();
+        assert_eq!(
+            fetch_account_key(&mut rpc_api, account_id).await,
+            Err(AuthError::KeyUnavailable(account_id))
+        );
+    }
+
+    #[test]
+    fn test_hex() {
+        assert_eq!(to_hex(&[0, 15, 255]), "000fff");
+        assert_eq!(from_hex("000fff"), Some(vec![0, 15, 255]));
+        assert_eq!(from_hex("0g"), None);
+    }
+}
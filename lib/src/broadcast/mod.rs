@@ -1,32 +1,412 @@
 use aze_types::actions::ActionType;
+use aze_types::events::{EventMessage, GameEvent};
+use aze_types::lobby::{LobbyJoinRequest, LobbyUpdate};
 use futures_util::{SinkExt, StreamExt};
 use get_if_addrs::get_if_addrs;
 use log::{error, info};
-use miden_objects::accounts::AccountId;
+use miden_client::config::RpcConfig;
+use miden_objects::accounts::{Account, AccountId};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::net::IpAddr;
-use std::path::PathBuf;
-use std::sync::{Arc, Mutex};
-use tokio::sync::{broadcast, RwLock};
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::future::Future;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, OnceLock};
+use tokio::sync::{broadcast, mpsc, RwLock};
+use tokio::time::{sleep_until, Duration, Instant};
 use tokio_tungstenite::tungstenite::protocol::Message as TungsteniteMessage;
 use warp::hyper::StatusCode;
 use warp::ws::Ws;
-use warp::Filter;
+use warp::{Filter, Reply};
 
-use crate::client::{create_aze_client, AzeClient};
+use crate::auth::{AuthError, RequestVerifier, SignedRequest, SIGNED_REQUEST_HEADER};
+use crate::client::{create_aze_client, node_rpc_config, AzeClient};
 use crate::constants::{
-    COMMUNITY_CARDS, CURRENT_PHASE_SLOT, CURRENT_TURN_INDEX_SLOT, NO_OF_PLAYERS,
-    PLAYER_BALANCE_SLOT, PLAYER_HANDS, POT_VALUE,
+    CHAT_RATE_LIMIT, CHAT_RATE_WINDOW_SECS, COMMUNITY_CARDS, CURRENT_PHASE_SLOT,
+    CURRENT_TURN_INDEX_SLOT, DEFAULT_SERVER_PORT, DEFAULT_SPECTATOR_DELAY, EXTRA_HOLE_CARDS_OFFSET,
+    FIRST_PLAYER_INDEX, GAME_VARIANT_SLOT, HIGHEST_BET_SLOT, IS_FOLD_OFFSET, MAX_CHAT_LENGTH, NO_OF_PLAYERS, PLAYER_BALANCE_SLOT,
+    PLAYER_HANDS, PLAYER_STATS_SLOTS, POT_VALUE, SEAT_ELIMINATED, SEAT_OFFSET, SEAT_SITTING_OUT,
+    SEAT_TAKEN,
 };
-use crate::gamestate::{Check_Action, PokerGame};
-use crate::utils::Ws_config;
-type Peers = Arc<RwLock<HashMap<String, broadcast::Sender<TungsteniteMessage>>>>;
+use crate::gamestate::{Check_Action, OnChainState, PokerGame, SeatState};
+use crate::hands::GameVariant;
+use crate::host::HostedGame;
+use crate::lobby::{FormedTable, Lobby, LobbyPlayer};
+use crate::utils::{PublishRequest, SubscribeRequest, Ws_config};
+
+// live subscribers of a game and every event published to it so far, so late or reconnecting
+// clients can catch up
+struct GameChannel {
+    sender: broadcast::Sender<TungsteniteMessage>,
+    // game events only, for spectators
+    events: broadcast::Sender<EventMessage>,
+    history: Vec<EventMessage>,
+}
+
+impl GameChannel {
+    fn new() -> Self {
+        Self::with_history(vec![])
+    }
+
+    fn with_history(history: Vec<EventMessage>) -> Self {
+        let (sender, _rx) = broadcast::channel(100);
+        let (events, _rx) = broadcast::channel(100);
+        Self {
+            sender,
+            events,
+            history,
+        }
+    }
+
+    fn next_sequence(&self) -> u64 {
+        self.history.len() as u64 + 1
+    }
+
+    fn events_since(&self, sequence: u64) -> &[EventMessage] {
+        let start = (sequence as usize).min(self.history.len());
+        &self.history[start..]
+    }
+}
+
+/// A table hosted by the server: its off-chain game state, who may act on it and its event log.
+pub struct GameTable {
+    pub game_id: String,
+    pub small_blind: u64,
+    pub buy_in: u64,
+    pub game: Mutex<PokerGame>,
+    verifier: RequestVerifier,
+    channel: RwLock<GameChannel>,
+    // rewritten after every event when the registry persists its tables
+    snapshot_path: Option<PathBuf>,
+    chat_limiter: ChatLimiter,
+}
+
+// how many chat messages each player sent within the last window
+#[derive(Default)]
+struct ChatLimiter {
+    sent: Mutex<HashMap<u64, VecDeque<Instant>>>,
+}
+
+impl ChatLimiter {
+    fn allow(&self, player_id: u64, now: Instant) -> bool {
+        let window = Duration::from_secs(CHAT_RATE_WINDOW_SECS);
+        let mut sent = self.sent.lock().unwrap();
+        let sent = sent.entry(player_id).or_default();
+        while sent
+            .front()
+            .map_or(false, |sent_at| now.duration_since(*sent_at) >= window)
+        {
+            sent.pop_front();
+        }
+        if sent.len() >= CHAT_RATE_LIMIT {
+            return false;
+        }
+        sent.push_back(now);
+        true
+    }
+}
+
+// everything needed to pick a table up again after a restart
+#[derive(Deserialize, Serialize)]
+struct GameSnapshot {
+    game: PokerGame,
+    events: Vec<EventMessage>,
+}
+
+impl GameTable {
+    // appends the event to the game's log and sends it to everyone connected
+    pub async fn publish(&self, event: GameEvent) {
+        let mut channel = self.channel.write().await;
+        let message = EventMessage::new(self.game_id.clone(), channel.next_sequence(), event);
+        let msg = TungsteniteMessage::Text(serde_json::to_string(&message).unwrap());
+        channel.history.push(message.clone());
+        self.save_snapshot(&channel.history);
+        // nobody listening is fine, the event is in the log
+        let _ = channel.sender.send(msg);
+        let _ = channel.events.send(message);
+    }
+
+    // publishes a signed chat message of a player, if it is short and they don't flood the table
+    async fn chat(&self, text: &str) -> Result<(), String> {
+        let request: SignedRequest =
+            serde_json::from_str(text).map_err(|_| AuthError::BadPayload.to_string())?;
+        let event = request.payload::<GameEvent>().map_err(|e| e.to_string())?;
+        let (player_id, message) = match &event {
+            GameEvent::Chat {
+                player_id, message, ..
+            } => (*player_id, message),
+            _ => return Err(AuthError::BadPayload.to_string()),
+        };
+        let seated = self.game.lock().unwrap().seat_of(player_id).is_some();
+        if player_id != request.account_id || !seated {
+            return Err(AuthError::NotSigner(player_id).to_string());
+        }
+        self.verifier.verify(&request).await.map_err(|e| e.to_string())?;
+        if message.trim().is_empty() || message.chars().count() > MAX_CHAT_LENGTH {
+            return Err(format!(
+                "Messages must be 1 to {} characters long",
+                MAX_CHAT_LENGTH
+            ));
+        }
+        if !self.chat_limiter.allow(player_id, Instant::now()) {
+            return Err(format!(
+                "At most {} messages every {} seconds",
+                CHAT_RATE_LIMIT, CHAT_RATE_WINDOW_SECS
+            ));
+        }
+
+        self.publish(event).await;
+        Ok(())
+    }
+
+    // the game account or a player holding a seat
+    fn is_participant(&self, account_id: u64) -> bool {
+        let is_game_account = AccountId::from_hex(&self.game_id)
+            .map_or(false, |game_account_id| u64::from(game_account_id) == account_id);
+        is_game_account || self.game.lock().unwrap().seat_of(account_id).is_some()
+    }
+
+    // checks the signed handshake of a websocket subscribing to this game, returns who sent it
+    async fn authorize_subscriber(&self, signed_request: Option<&str>) -> Result<u64, AuthError> {
+        let request: SignedRequest = signed_request
+            .ok_or(AuthError::Unsigned)
+            .and_then(|text| serde_json::from_str(text).map_err(|_| AuthError::BadPayload))?;
+        if request.payload::<SubscribeRequest>()?.game_id != self.game_id {
+            return Err(AuthError::BadPayload);
+        }
+        self.verifier.verify(&request).await?;
+        if !self.is_participant(request.account_id) {
+            return Err(AuthError::NotSigner(request.account_id));
+        }
+        Ok(request.account_id)
+    }
+
+    fn save_snapshot(&self, events: &[EventMessage]) {
+        let snapshot_path = match &self.snapshot_path {
+            Some(snapshot_path) => snapshot_path,
+            None => return,
+        };
+        let snapshot = GameSnapshot {
+            game: self.game.lock().unwrap().clone(),
+            events: events.to_vec(),
+        };
+        // written aside and renamed, so a crash never leaves half a snapshot behind
+        let tmp_path = snapshot_path.with_extension("json.tmp");
+        let result = fs::write(&tmp_path, serde_json::to_string(&snapshot).unwrap())
+            .and_then(|_| fs::rename(&tmp_path, snapshot_path));
+        if let Err(e) = result {
+            error!("Failed to save game {}: {}", self.game_id, e);
+        }
+    }
+
+    async fn summary(&self) -> GameSummary {
+        let (player_ids, pot, current_player_id) = {
+            let game = self.game.lock().unwrap();
+            (game.player_ids(), game.pot(), game.current_player_id())
+        };
+        let channel = self.channel.read().await;
+        GameSummary {
+            game_id: self.game_id.clone(),
+            player_ids,
+            small_blind: self.small_blind,
+            buy_in: self.buy_in,
+            pot,
+            current_player_id,
+            events: channel.history.len() as u64,
+            spectators: channel.events.receiver_count() as u64,
+        }
+    }
+}
+
+/// Tables hosted by this process, keyed by game account ID.
+#[derive(Clone, Default)]
+pub struct GameRegistry {
+    tables: Arc<Mutex<HashMap<String, Arc<GameTable>>>>,
+    // set when the process runs host loops for games registered over `POST /games`
+    new_games: Arc<Mutex<Option<mpsc::UnboundedSender<HostedGame>>>>,
+    // directory the tables' snapshots are kept in, if any
+    state_dir: Arc<Mutex<Option<PathBuf>>>,
+    lobby: Arc<Mutex<Lobby>>,
+    lobby_verifier: Arc<OnceLock<RequestVerifier>>,
+    // set when the process creates game accounts for the tables the lobby fills
+    formed_tables: Arc<Mutex<Option<mpsc::UnboundedSender<FormedTable>>>>,
+}
+
+impl GameRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Lets dealers register games over `POST /games`, the games come out of the receiver once
+    /// they are hosted.
+    pub fn accept_games(&self) -> mpsc::UnboundedReceiver<HostedGame> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        *self.new_games.lock().unwrap() = Some(sender);
+        receiver
+    }
+
+    /// Opens the lobby at `/lobby`, full tables come out of the receiver and are expected to be
+    /// hosted with [GameRegistry::host_new_game]. Joins are checked against the players' keys on
+    /// `node`.
+    pub fn accept_lobby(&self, node: RpcConfig) -> mpsc::UnboundedReceiver<FormedTable> {
+        let _ = self.lobby_verifier.set(RequestVerifier::open(node));
+        let (sender, receiver) = mpsc::unbounded_channel();
+        *self.formed_tables.lock().unwrap() = Some(sender);
+        receiver
+    }
+
+    /// Hosts a game and hands it to the host loops of this process, returns the url players
+    /// connect to.
+    pub fn host_new_game(&self, hosted_game: HostedGame) -> Result<String, String> {
+        let new_games = self
+            .new_games
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| String::from("Server does not host new games"))?;
+        self.host_game(
+            hosted_game.game_id.clone(),
+            hosted_game.player_ids.clone(),
+            hosted_game.buy_in,
+            hosted_game.small_blind as u64,
+        );
+        let ws_url = SERVER
+            .get()
+            .map(|(_, base_url)| game_url(base_url, &hosted_game.game_id))
+            .unwrap_or_default();
+        let _ = new_games.send(hosted_game);
+        Ok(ws_url)
+    }
+
+    // queues the player of a signed join request, returns their id
+    async fn join_lobby(
+        &self,
+        text: &str,
+        updates: mpsc::UnboundedSender<LobbyUpdate>,
+    ) -> Result<u64, String> {
+        let formed_tables = self
+            .formed_tables
+            .lock()
+            .unwrap()
+            .clone()
+            .ok_or_else(|| String::from("Server has no lobby"))?;
+        let request: SignedRequest =
+            serde_json::from_str(text).map_err(|_| AuthError::BadPayload.to_string())?;
+        let join = request
+            .payload::<LobbyJoinRequest>()
+            .map_err(|e| e.to_string())?;
+        if join.player_id != request.account_id {
+            return Err(AuthError::NotSigner(join.player_id).to_string());
+        }
+        // the join has to be signed with the key of the player's account on the node
+        self.lobby_verifier
+            .get()
+            .ok_or_else(|| String::from("Server has no lobby"))?
+            .verify(&request)
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let player = LobbyPlayer::new(join.player_id, join.identifier, updates);
+        let formed_table = self.lobby.lock().unwrap().join(join.stakes, player);
+        if let Some(formed_table) = formed_table {
+            let _ = formed_tables.send(formed_table);
+        }
+        Ok(join.player_id)
+    }
+
+    /// Keeps a snapshot of every table's game state and event log in `state_dir`. Tables hosted
+    /// afterwards resume from their snapshot, caught up with the game account's storage.
+    pub fn persist_to(&self, state_dir: PathBuf) -> Result<(), String> {
+        fs::create_dir_all(&state_dir)
+            .map_err(|e| format!("Failed to create {}: {}", state_dir.display(), e))?;
+        *self.state_dir.lock().unwrap() = Some(state_dir);
+        Ok(())
+    }
+
+    pub fn host_game(
+        &self,
+        game_id: String,
+        player_ids: Vec<u64>,
+        buy_in: u64,
+        small_blind: u64,
+    ) -> Arc<GameTable> {
+        let snapshot_path = self
+            .state_dir
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|state_dir| state_dir.join(format!("{}.json", game_id)));
+        let snapshot = snapshot_path.as_ref().and_then(|path| load_snapshot(path));
+        let (game, channel) = match snapshot {
+            Some(snapshot) => {
+                let mut game = snapshot.game;
+                reconcile_with_chain(&game_id, &mut game, player_ids.is_empty());
+                info!(
+                    "Restored game {} at event {}",
+                    game_id,
+                    snapshot.events.len()
+                );
+                (game, GameChannel::with_history(snapshot.events))
+            }
+            // players take the seats of an open table on-chain
+            None if player_ids.is_empty() => (
+                PokerGame::with_empty_seats(NO_OF_PLAYERS as usize, small_blind, small_blind * 2),
+                GameChannel::new(),
+            ),
+            None => (
+                PokerGame::new(
+                    player_ids.clone(),
+                    vec![buy_in; player_ids.len()],
+                    small_blind,
+                    small_blind * 2,
+                ),
+                GameChannel::new(),
+            ),
+        };
+        let table = Arc::new(GameTable {
+            verifier: create_verifier(&game_id, &player_ids),
+            game: Mutex::new(game),
+            game_id: game_id.clone(),
+            small_blind,
+            buy_in,
+            channel: RwLock::new(channel),
+            snapshot_path,
+            chat_limiter: ChatLimiter::default(),
+        });
+        self.tables.lock().unwrap().insert(game_id, table.clone());
+        table
+    }
+
+    pub fn get(&self, game_id: &str) -> Option<Arc<GameTable>> {
+        self.tables.lock().unwrap().get(game_id).cloned()
+    }
+
+    pub fn remove(&self, game_id: &str) -> Option<Arc<GameTable>> {
+        self.tables.lock().unwrap().remove(game_id)
+    }
+
+    pub fn tables(&self) -> Vec<Arc<GameTable>> {
+        self.tables.lock().unwrap().values().cloned().collect()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GameSummary {
+    pub game_id: String,
+    pub player_ids: Vec<u64>,
+    pub small_blind: u64,
+    pub buy_in: u64,
+    pub pot: u64,
+    pub current_player_id: u64,
+    pub events: u64,
+    pub spectators: u64,
+}
 
 #[derive(Deserialize)]
-struct PublishRequest {
-    game_id: String,
-    event: String,
+struct ReplayQuery {
+    // last sequence number the client has seen, it gets everything after it
+    since: Option<u64>,
 }
 
 #[derive(Deserialize)]
@@ -42,98 +422,281 @@ struct StatResponse {
     pub pot_value: u64,
     pub player_hands: Vec<u64>,
     pub current_state: u64,
-    pub player_hand_cards: Vec<Vec<u64>>
+    pub player_hand_cards: Vec<Vec<u64>>,
+    pub variant: GameVariant,
+    pub player_low_hands: Vec<Vec<u64>>,
 }
 
 #[derive(Deserialize, Serialize)]
 pub struct CheckmoveRequest {
+    pub game_id: String,
     pub player_id: u64,
     pub action: Check_Action,
 }
 
+/// Where the game server listens and the url players are told to connect to.
+#[derive(Debug, Clone)]
+pub struct ServerConfig {
+    // defaults to the first non-loopback IPv4 address of the host
+    pub bind_address: Option<IpAddr>,
+    // 0 lets the OS pick a free port
+    pub port: u16,
+    // e.g. ws://poker.example.com:8080, for servers behind NAT or a proxy
+    pub public_url: Option<String>,
+    // seconds every event is held back from spectators
+    pub spectator_delay: u64,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            bind_address: None,
+            port: DEFAULT_SERVER_PORT,
+            public_url: None,
+            spectator_delay: DEFAULT_SPECTATOR_DELAY,
+        }
+    }
+}
+
+// the tables of this process and the server serving them, started by the first game hosted
+static REGISTRY: OnceLock<GameRegistry> = OnceLock::new();
+static SERVER: OnceLock<(SocketAddr, String)> = OnceLock::new();
+
+pub fn registry() -> &'static GameRegistry {
+    REGISTRY.get_or_init(GameRegistry::new)
+}
+
+/// Hosts the game on this process' server, starting the server for the first game, and returns
+/// the url players connect to.
 pub fn initialise_server(
     game_id: String,
     ws_config_path: &PathBuf,
     buy_in_amount: u64,
     small_blind_amount: u8,
     player_ids: Vec<u64>,
-) -> Option<String> {
-    let ip: [u8; 4] = get_ipv4_bytes().unwrap();
-    let port = 12044;
-    let ws_url = format!(
-        "ws://{}.{}.{}.{}:{}/ws/{}",
-        ip[0], ip[1], ip[2], ip[3], port, game_id
+    server_config: &ServerConfig,
+) -> Result<String, String> {
+    let (bound_address, base_url) = start_server(server_config, std::future::pending())?;
+    registry().host_game(
+        game_id.clone(),
+        player_ids,
+        buy_in_amount,
+        small_blind_amount as u64,
     );
-
-    // Spawn the server task in the background
-    tokio::spawn(async move {
-        let peers: Peers = Arc::new(RwLock::new(HashMap::new()));
-        let peers_filter = warp::any().map(move || peers.clone());
-
-        let ws_route = warp::path("ws")
-            .and(warp::path::param())
-            .and(warp::ws())
-            .and(peers_filter.clone())
-            .and_then(ws_handler);
-
-        let publish_route = warp::path("publish")
-            .and(warp::post())
-            .and(warp::body::json())
-            .and(peers_filter.clone())
-            .and_then(publish_handler);
-
-        let stats_route = warp::path("stats")
-            .and(warp::post())
-            .and(warp::body::json())
-            .and_then(stat_handler);
-
-        let checkmove_route = warp::path("checkmove")
-            .and(warp::post())
-            .and(warp::body::json())
-            .and(with_game())
-            .and_then(checkmove_handler);
-
-        let routes = ws_route
-            .or(publish_route)
-            .or(stats_route)
-            .or(checkmove_route)
-            .with(warp::log("broadcast_server"));
-
-        info!(
-            "Starting WebSocket server at {}.{}.{}.{}:{}",
-            ip[0], ip[1], ip[2], ip[3], port
-        );
-        let ip_addr: IpAddr = ip.into();
-        warp::serve(routes).run((ip_addr, port)).await;
-    });
+    let ws_url = game_url(&base_url, &game_id);
 
     // Return the WebSocket URL
     let mut ws_config = Ws_config::load(ws_config_path);
     ws_config.url = Some(ws_url.to_string());
+    ws_config.bind_address = Some(bound_address.to_string());
     ws_config.save(ws_config_path);
 
-    // initialise local game state
-    let game = Arc::new(Mutex::new(PokerGame::new(
-        player_ids,
-        vec![buy_in_amount; 4],
-        small_blind_amount as u64,
-        (small_blind_amount * 2) as u64,
-    )));
+    Ok(ws_url)
+}
+
+/// Starts serving the registry's tables, once per process, and returns the bound address and
+/// the advertised base url. The server stops accepting connections once `shutdown` resolves.
+pub fn start_server(
+    server_config: &ServerConfig,
+    shutdown: impl Future<Output = ()> + Send + 'static,
+) -> Result<(SocketAddr, String), String> {
+    if let Some(server) = SERVER.get() {
+        return Ok(server.clone());
+    }
+
+    // bind before advertising anything, so the url carries the port actually in use
+    let bind_ip = server_config
+        .bind_address
+        .or_else(get_ipv4_address)
+        .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+    let (bound_address, server) = warp::serve(routes(registry().clone(), server_config.spectator_delay))
+        .try_bind_with_graceful_shutdown((bind_ip, server_config.port), shutdown)
+        .map_err(|e| {
+            format!(
+                "Error binding game server to {}:{}: {}",
+                bind_ip, server_config.port, e
+            )
+        })?;
+
+    info!("Starting WebSocket server at {}", bound_address);
+    // Spawn the server task in the background
+    tokio::spawn(server);
+
+    let base_url = advertised_base_url(server_config, bound_address);
+    Ok(SERVER.get_or_init(|| (bound_address, base_url)).clone())
+}
+
+pub fn game_url(base_url: &str, game_id: &str) -> String {
+    format!("{}/ws/{}", base_url, game_id)
+}
+
+fn routes(
+    registry: GameRegistry,
+    spectator_delay: u64,
+) -> impl Filter<Extract = (impl warp::Reply,), Error = warp::Rejection> + Clone {
+    let registry_filter = warp::any().map(move || registry.clone());
+
+    let ws_route = warp::path("ws")
+        .and(warp::path::param())
+        .and(warp::query::<ReplayQuery>())
+        .and(warp::header::optional::<String>(SIGNED_REQUEST_HEADER))
+        .and(warp::ws())
+        .and(registry_filter.clone())
+        .and_then(ws_handler);
+
+    let watch_route = warp::path("watch")
+        .and(warp::path::param())
+        .and(warp::query::<ReplayQuery>())
+        .and(warp::ws())
+        .and(registry_filter.clone())
+        .and_then(move |game_id, query, ws, registry| {
+            watch_handler(game_id, query, ws, registry, spectator_delay)
+        });
+
+    let lobby_route = warp::path("lobby")
+        .and(warp::ws())
+        .and(registry_filter.clone())
+        .and_then(lobby_handler);
+
+    let publish_route = warp::path("publish")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(registry_filter.clone())
+        .and_then(publish_handler);
+
+    let stats_route = warp::path("stats")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(registry_filter.clone())
+        .and_then(stat_handler);
+
+    let checkmove_route = warp::path("checkmove")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(registry_filter.clone())
+        .and_then(checkmove_handler);
+
+    let gamestate_route = warp::path("gamestate")
+        .and(warp::path::param())
+        .and(warp::get())
+        .and(registry_filter.clone())
+        .and_then(gamestate_handler);
+
+    let games_route = warp::path("games")
+        .and(warp::get())
+        .and(registry_filter.clone())
+        .and_then(games_handler);
 
-    set_game(game.clone());
-    Some(ws_url)
+    let host_route = warp::path("games")
+        .and(warp::post())
+        .and(warp::body::json())
+        .and(registry_filter.clone())
+        .and_then(host_handler);
+
+    ws_route
+        .or(watch_route)
+        .or(lobby_route)
+        .or(publish_route)
+        .or(stats_route)
+        .or(checkmove_route)
+        .or(gamestate_route)
+        .or(games_route)
+        .or(host_route)
+        .with(warp::log("broadcast_server"))
 }
 
 // Utility Functions
 
-fn convert_warp_message_to_tungstenite(msg: warp::ws::Message) -> TungsteniteMessage {
-    if msg.is_text() {
-        TungsteniteMessage::Text(msg.to_str().unwrap().to_string())
-    } else if msg.is_binary() {
-        TungsteniteMessage::Binary(msg.as_bytes().to_vec())
+fn load_snapshot(snapshot_path: &Path) -> Option<GameSnapshot> {
+    let snapshot_data = fs::read_to_string(snapshot_path).ok()?;
+    match serde_json::from_str(&snapshot_data) {
+        Ok(snapshot) => Some(snapshot),
+        Err(e) => {
+            error!("Ignoring {}: {}", snapshot_path.display(), e);
+            None
+        }
+    }
+}
+
+// moves made while the server was down only exist on-chain, the game account has the last word
+fn reconcile_with_chain(game_id: &str, game: &mut PokerGame, open_seats: bool) {
+    let client: AzeClient = create_aze_client();
+    let game_account = AccountId::from_hex(game_id)
+        .ok()
+        .and_then(|game_account_id| client.get_account(game_account_id).ok());
+    match game_account {
+        Some((game_account, _)) => {
+            let chain = on_chain_state(&game_account, game.seat_count(), open_seats);
+            for change in game.reconcile(&chain) {
+                info!("Game {}: {}", game_id, change);
+            }
+        }
+        None => error!("Game account {} not found, keeping the saved state", game_id),
+    }
+}
+
+// the betting state of the game account, read from the same player slots `/stats` reports
+fn on_chain_state(game_account: &Account, players: usize, open_seats: bool) -> OnChainState {
+    let storage = game_account.storage();
+    let current_turn_slot = storage.get_item(CURRENT_TURN_INDEX_SLOT).as_elements()[0].as_int();
+    let mut balances = vec![];
+    let mut folded = vec![];
+    for i in 0..players {
+        let balance_slot: u8 = PLAYER_BALANCE_SLOT + (i * 13) as u8;
+        let fold_slot: u8 = FIRST_PLAYER_INDEX + (i * 13) as u8 + IS_FOLD_OFFSET;
+        balances.push(storage.get_item(balance_slot).as_elements()[0].as_int());
+        folded.push(storage.get_item(fold_slot).as_elements()[0].as_int() != 0);
+    }
+    // tables seated at creation don't name their players on-chain
+    let seats = if open_seats {
+        (0..players).map(|seat| seat_on_chain(game_account, seat)).collect()
+    } else {
+        vec![]
+    };
+    OnChainState {
+        current_bet: storage.get_item(HIGHEST_BET_SLOT).as_elements()[0].as_int(),
+        current_player_index: current_turn_slot.saturating_sub(FIRST_PLAYER_INDEX as u64) as usize
+            / PLAYER_STATS_SLOTS as usize,
+        balances,
+        folded,
+        seats,
+    }
+}
+
+/// State and player of a seat as the game account keeps them.
+pub fn seat_on_chain(game_account: &Account, seat: usize) -> (SeatState, u64) {
+    let seat_slot = FIRST_PLAYER_INDEX + seat as u8 * PLAYER_STATS_SLOTS + SEAT_OFFSET;
+    let seat_word = game_account.storage().get_item(seat_slot);
+    let seat_word = seat_word.as_elements();
+    let seat_state = match seat_word[0].as_int() {
+        SEAT_TAKEN => SeatState::Taken,
+        SEAT_SITTING_OUT => SeatState::SittingOut,
+        SEAT_ELIMINATED => SeatState::Eliminated,
+        _ => SeatState::Empty,
+    };
+    (seat_state, seat_word[1].as_int())
+}
+
+// only the game account and its players may publish or move. The players' keys are read from
+// the node, the game account's from the store the server hosts it with
+fn create_verifier(game_id: &str, player_ids: &[u64]) -> RequestVerifier {
+    let game_account_id = AccountId::from_hex(game_id).ok();
+    let mut account_ids = player_ids.to_vec();
+    account_ids.extend(game_account_id.map(u64::from));
+    // anyone may take a seat at an open table, moves still need the turn
+    let verifier = if player_ids.is_empty() {
+        RequestVerifier::open(node_rpc_config())
     } else {
-        TungsteniteMessage::Ping(vec![])
+        RequestVerifier::new(account_ids, node_rpc_config())
+    };
+
+    let client: AzeClient = create_aze_client();
+    if let Some((game_account, _)) =
+        game_account_id.and_then(|game_account_id| client.get_account(game_account_id).ok())
+    {
+        verifier.add_key(game_account.id().into(), game_account.storage().get_item(0).into());
     }
+    verifier
 }
 
 fn convert_tungstenite_message_to_warp(msg: TungsteniteMessage) -> warp::ws::Message {
@@ -147,67 +710,73 @@ fn convert_tungstenite_message_to_warp(msg: TungsteniteMessage) -> warp::ws::Mes
     }
 }
 
-fn get_ipv4_bytes() -> Option<[u8; 4]> {
+fn get_ipv4_address() -> Option<IpAddr> {
     let interfaces = get_if_addrs().ok()?;
 
     for iface in interfaces {
         if !iface.is_loopback() {
             if let IpAddr::V4(ipv4) = iface.addr.ip() {
-                return Some(ipv4.octets());
+                return Some(IpAddr::V4(ipv4));
             }
         }
     }
     None
 }
 
-// Local Poker game
-static mut GAME: Option<Arc<Mutex<PokerGame>>> = None;
-
-pub fn set_game(game: Arc<Mutex<PokerGame>>) {
-    unsafe {
-        GAME = Some(game);
+// the public url when one is configured, otherwise the bound address, with a wildcard bind
+// replaced by an address other machines can reach
+fn advertised_base_url(server_config: &ServerConfig, bound_address: SocketAddr) -> String {
+    if let Some(public_url) = &server_config.public_url {
+        return public_url.trim_end_matches('/').to_string();
     }
-}
-
-fn with_game(
-) -> impl Filter<Extract = (Arc<Mutex<PokerGame>>,), Error = std::convert::Infallible> + Clone {
-    warp::any().map(move || unsafe { GAME.clone().unwrap() })
+    let ip = if bound_address.ip().is_unspecified() {
+        get_ipv4_address().unwrap_or(IpAddr::V4(Ipv4Addr::LOCALHOST))
+    } else {
+        bound_address.ip()
+    };
+    format!("ws://{}", SocketAddr::new(ip, bound_address.port()))
 }
 
 // Handlers
 
+// players get the table's chat and events as they happen, so only the game account and its
+// players may connect, spectators are served under `/watch`
 async fn ws_handler(
     game_id: String,
+    query: ReplayQuery,
+    signed_request: Option<String>,
     ws: Ws,
-    peers: Peers,
+    registry: GameRegistry,
 ) -> Result<impl warp::Reply, warp::Rejection> {
-    Ok(ws.on_upgrade(move |socket| handle_websocket(socket, game_id, peers)))
+    let table = registry.get(&game_id).ok_or_else(warp::reject::not_found)?;
+    if let Err(e) = table.authorize_subscriber(signed_request.as_deref()).await {
+        return Ok(error_reply(e.to_string(), StatusCode::UNAUTHORIZED).into_response());
+    }
+    let since = query.since.unwrap_or(0);
+    Ok(ws
+        .on_upgrade(move |socket| handle_websocket(socket, table, since))
+        .into_response())
 }
 
-async fn handle_websocket(socket: warp::ws::WebSocket, game_id: String, peers: Peers) {
+async fn handle_websocket(socket: warp::ws::WebSocket, table: Arc<GameTable>, since: u64) {
     let (mut ws_sender, mut ws_receiver) = socket.split();
-    let tx = {
-        let mut peers = peers.write().await;
-        peers
-            .entry(game_id.clone())
-            .or_insert_with(|| {
-                let (tx, _rx) = broadcast::channel(100);
-                tx
-            })
-            .clone()
+    // subscribe and copy the missed events under the same lock, so nothing falls in between
+    let (mut rx, missed) = {
+        let channel = table.channel.read().await;
+        (channel.sender.subscribe(), channel.events_since(since).to_vec())
     };
-    let mut rx = tx.subscribe();
+    // why a chat message was turned down, only for the client who sent it
+    let (reply_tx, mut reply_rx) = mpsc::unbounded_channel::<String>();
 
-    // receive messages from the WebSocket and broadcast them
-    let tx_clone = tx.clone();
+    // players may only send signed chat messages, which go to the whole table
+    let chat_table = table.clone();
     tokio::spawn(async move {
         while let Some(result) = ws_receiver.next().await {
             match result {
                 Ok(msg) => {
-                    if msg.is_text() || msg.is_binary() {
-                        let msg = convert_warp_message_to_tungstenite(msg);
-                        if let Err(e) = tx_clone.send(msg) {
-                            error!("Error broadcasting message: {}", e);
+                    if let Ok(text) = msg.to_str() {
+                        if let Err(e) = chat_table.chat(text).await {
+                            let _ = reply_tx.send(format!("Chat rejected: {}", e));
                         }
                     }
                 }
@@ -219,10 +788,29 @@ async fn handle_websocket(socket: warp::ws::WebSocket, game_id: String, peers: P
         }
     });
 
-    // send broadcast messages to the WebSocket
+    // replay what the client missed, then send broadcast messages to the WebSocket
     tokio::spawn(async move {
-        while let Ok(msg) = rx.recv().await {
-            let msg = convert_tungstenite_message_to_warp(msg);
+        for message in missed {
+            let msg = warp::ws::Message::text(serde_json::to_string(&message).unwrap());
+            if ws_sender.send(msg).await.is_err() {
+                error!("Error sending message to client");
+                return;
+            }
+        }
+        loop {
+            let msg = tokio::select! {
+                received = rx.recv() => match received {
+                    Ok(msg) => convert_tungstenite_message_to_warp(msg),
+                    // the client fell behind, close so it reconnects and resumes from the log
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        error!("Client lagged behind by {} messages", skipped);
+                        let _ = ws_sender.close().await;
+                        break;
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                },
+                Some(reply) = reply_rx.recv() => warp::ws::Message::text(reply),
+            };
             if ws_sender.send(msg).await.is_err() {
                 error!("Error sending message to client");
                 break;
@@ -230,31 +818,206 @@ async fn handle_websocket(socket: warp::ws::WebSocket, game_id: String, peers: P
         }
     });
 
-    println!("WebSocket connection established for game_id: {}", game_id);
+    println!("WebSocket connection established for game_id: {}", table.game_id);
+}
+
+async fn watch_handler(
+    game_id: String,
+    query: ReplayQuery,
+    ws: Ws,
+    registry: GameRegistry,
+    spectator_delay: u64,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let table = registry.get(&game_id).ok_or_else(warp::reject::not_found)?;
+    let since = query.since.unwrap_or(0);
+    let delay = Duration::from_secs(spectator_delay);
+    Ok(ws.on_upgrade(move |socket| handle_spectator(socket, table, since, delay)))
+}
+
+// spectators only get game events, redacted and late enough that they can't help a player
+async fn handle_spectator(
+    socket: warp::ws::WebSocket,
+    table: Arc<GameTable>,
+    since: u64,
+    delay: Duration,
+) {
+    let (mut ws_sender, mut ws_receiver) = socket.split();
+    let (mut rx, missed) = {
+        let channel = table.channel.read().await;
+        (channel.events.subscribe(), channel.events_since(since).to_vec())
+    };
+
+    // hold every event back until it is `delay` old, missed ones included since we can't tell
+    // how recent they are
+    let (delayed_tx, mut delayed_rx) = mpsc::unbounded_channel::<(Instant, EventMessage)>();
+    let release_at = Instant::now() + delay;
+    for message in missed {
+        let _ = delayed_tx.send((release_at, message));
+    }
+    let game_id = table.game_id.clone();
+    tokio::spawn(async move {
+        loop {
+            match rx.recv().await {
+                Ok(message) => {
+                    if delayed_tx.send((Instant::now() + delay, message)).is_err() {
+                        break;
+                    }
+                }
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    error!("Spectator of {} lagged behind by {} events", game_id, skipped);
+                    break;
+                }
+                Err(broadcast::error::RecvError::Closed) => break,
+            }
+        }
+    });
+
+    tokio::spawn(async move {
+        loop {
+            tokio::select! {
+                delayed = delayed_rx.recv() => {
+                    let (release_at, message) = match delayed {
+                        Some(delayed) => delayed,
+                        // dropped behind, close so the spectator resumes from the log
+                        None => {
+                            let _ = ws_sender.close().await;
+                            break;
+                        }
+                    };
+                    sleep_until(release_at).await;
+                    let msg = warp::ws::Message::text(
+                        serde_json::to_string(&message.redacted()).unwrap(),
+                    );
+                    if ws_sender.send(msg).await.is_err() {
+                        break;
+                    }
+                }
+                // spectators can't talk to the table, only notice when they leave
+                incoming = ws_receiver.next() => {
+                    if !matches!(incoming, Some(Ok(_))) {
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
+
+async fn lobby_handler(
+    ws: Ws,
+    registry: GameRegistry,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    Ok(ws.on_upgrade(move |socket| handle_lobby(socket, registry)))
+}
+
+// the player's first message is their signed join request, they wait in the lobby until their
+// table is up or they disconnect
+async fn handle_lobby(socket: warp::ws::WebSocket, registry: GameRegistry) {
+    let (mut ws_sender, mut ws_receiver) = socket.split();
+    let (updates_tx, mut updates) = mpsc::unbounded_channel();
+    let joined = match ws_receiver.next().await {
+        Some(Ok(msg)) => {
+            registry
+                .join_lobby(msg.to_str().unwrap_or_default(), updates_tx)
+                .await
+        }
+        _ => return,
+    };
+    let player_id = match joined {
+        Ok(player_id) => player_id,
+        Err(reason) => {
+            let rejected = LobbyUpdate::Rejected { reason };
+            let msg = warp::ws::Message::text(serde_json::to_string(&rejected).unwrap());
+            let _ = ws_sender.send(msg).await;
+            let _ = ws_sender.close().await;
+            return;
+        }
+    };
+
+    loop {
+        tokio::select! {
+            update = updates.recv() => {
+                let update = match update {
+                    Some(update) => update,
+                    None => break,
+                };
+                let done = !matches!(update, LobbyUpdate::Waiting { .. });
+                let msg = warp::ws::Message::text(serde_json::to_string(&update).unwrap());
+                if ws_sender.send(msg).await.is_err() || done {
+                    break;
+                }
+            }
+            incoming = ws_receiver.next() => {
+                if !matches!(incoming, Some(Ok(_))) {
+                    break;
+                }
+            }
+        }
+    }
+    let _ = ws_sender.close().await;
+    registry.lobby.lock().unwrap().leave(player_id);
 }
 
 async fn publish_handler(
-    body: PublishRequest,
-    peers: Peers,
+    request: SignedRequest,
+    registry: GameRegistry,
 ) -> Result<impl warp::Reply, warp::Rejection> {
-    let peers = peers.read().await;
-    if let Some(tx) = peers.get(&body.game_id) {
-        let msg = TungsteniteMessage::Text(body.event);
-        if let Err(e) = tx.send(msg) {
-            error!("Error broadcasting event: {}", e);
+    let body = match request.payload::<PublishRequest>() {
+        Ok(body) => body,
+        Err(e) => {
+            return Ok(warp::reply::with_status(
+                e.to_string(),
+                StatusCode::BAD_REQUEST,
+            ))
         }
-    } else {
+    };
+    let table = match registry.get(&body.game_id) {
+        Some(table) => table,
+        None => {
+            return Ok(warp::reply::with_status(
+                String::from("Game ID not found"),
+                StatusCode::NOT_FOUND,
+            ))
+        }
+    };
+    let verified = table.verifier.verify(&request).await.and_then(|_| {
+        match table.is_participant(request.account_id) {
+            true => Ok(()),
+            false => Err(AuthError::NotSigner(request.account_id)),
+        }
+    });
+    if let Err(e) = verified {
         return Ok(warp::reply::with_status(
-            "Game ID not found",
-            StatusCode::NOT_FOUND,
+            e.to_string(),
+            StatusCode::UNAUTHORIZED,
         ));
     }
 
-    Ok(warp::reply::with_status("Event published", StatusCode::OK))
+    table.publish(body.event).await;
+
+    Ok(warp::reply::with_status(
+        String::from("Event published"),
+        StatusCode::OK,
+    ))
 }
 
-async fn stat_handler(body: StatRequest) -> Result<impl warp::Reply, warp::Rejection> {
-    let game_id = body.game_id;
+async fn stat_handler(
+    request: SignedRequest,
+    registry: GameRegistry,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let game_id = match request.payload::<StatRequest>() {
+        Ok(body) => body.game_id,
+        Err(e) => return Ok(error_reply(e.to_string(), StatusCode::BAD_REQUEST)),
+    };
+    let table = match registry.get(&game_id) {
+        Some(table) => table,
+        None => return Ok(game_not_found()),
+    };
+    if let Err(e) = table.verifier.verify(&request).await {
+        return Ok(error_reply(e.to_string(), StatusCode::UNAUTHORIZED));
+    }
+    // only the requester gets to see their own hole cards before the showdown
+    let requester_seat = table.game.lock().unwrap().seat_of(request.account_id);
     let mut client: AzeClient = create_aze_client();
     let game_account_id = AccountId::from_hex(&game_id).unwrap();
     let game_account = client.get_account(game_account_id).unwrap().0;
@@ -266,6 +1029,9 @@ async fn stat_handler(body: StatRequest) -> Result<impl warp::Reply, warp::Rejec
         .as_int();
 
     let pot_value = game_account.storage().get_item(POT_VALUE).as_elements()[0].as_int();
+    let variant = GameVariant::from_slot(
+        game_account.storage().get_item(GAME_VARIANT_SLOT).as_elements()[0].as_int(),
+    );
 
     // Array with balance of players
     let mut player_balances: Vec<u64> = vec![];
@@ -292,10 +1058,19 @@ async fn stat_handler(body: StatRequest) -> Result<impl warp::Reply, warp::Rejec
             .as_elements()
             .to_vec();
         player_hands.push(player_hand_slot_data[2].as_int());
-        player_hand_cards.push(vec![
+        // [player card 3 index, player card 4 index, 0, 0] next to it, only dealt in Omaha
+        let extra_cards_slot_data = game_account
+            .storage()
+            .get_item(FIRST_PLAYER_INDEX + i * PLAYER_STATS_SLOTS + EXTRA_HOLE_CARDS_OFFSET)
+            .as_elements()
+            .to_vec();
+        let hand_cards = [
             player_hand_slot_data[0].as_int(),
             player_hand_slot_data[1].as_int(),
-        ])
+            extra_cards_slot_data[0].as_int(),
+            extra_cards_slot_data[1].as_int(),
+        ];
+        player_hand_cards.push(hand_cards[..variant.hole_cards()].to_vec())
     }
 
     for i in COMMUNITY_CARDS {
@@ -313,22 +1088,462 @@ async fn stat_handler(body: StatRequest) -> Result<impl warp::Reply, warp::Rejec
         .as_elements()[0]
         .as_int();
 
-    Ok(warp::reply::json(&StatResponse {
-        community_cards,
-        player_balances,
-        current_player,
-        pot_value,
-        player_hands,
-        current_state,
-        player_hand_cards
-    }))
+    redact_hole_cards(&mut player_hand_cards, requester_seat, current_state);
+
+    // ranks of each player's 8-or-better low, highest first, empty without a low or before the
+    // cards are unmasked
+    let is_unmasked = |card: &u64| (1..=52).contains(card);
+    let player_low_hands = player_hand_cards
+        .iter()
+        .map(|hand_cards| {
+            if !hand_cards.iter().all(is_unmasked) || !community_cards.iter().all(is_unmasked) {
+                return vec![];
+            }
+            variant
+                .best_low_hand(hand_cards, &community_cards)
+                .map(|low| low.ranks().to_vec())
+                .unwrap_or_default()
+        })
+        .collect();
+
+    Ok(warp::reply::with_status(
+        warp::reply::json(&StatResponse {
+            community_cards,
+            player_balances,
+            current_player,
+            pot_value,
+            player_hands,
+            current_state,
+            player_hand_cards,
+            variant,
+            player_low_hands,
+        }),
+        StatusCode::OK,
+    ))
+}
+
+// hole cards stay hidden from everyone but their owner until the showdown
+fn redact_hole_cards(player_hand_cards: &mut [Vec<u64>], requester_seat: Option<usize>, phase: u64) {
+    if phase >= 3 {
+        return;
+    }
+    for (seat, hand_cards) in player_hand_cards.iter_mut().enumerate() {
+        if requester_seat != Some(seat) {
+            *hand_cards = vec![0; hand_cards.len()];
+        }
+    }
 }
 
 pub async fn checkmove_handler(
-    body: CheckmoveRequest,
-    local_game: Arc<Mutex<PokerGame>>,
+    request: SignedRequest,
+    registry: GameRegistry,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let body = match request.payload::<CheckmoveRequest>() {
+        Ok(body) => body,
+        Err(e) => return Ok(error_reply(e.to_string(), StatusCode::BAD_REQUEST)),
+    };
+    let table = match registry.get(&body.game_id) {
+        Some(table) => table,
+        None => return Ok(game_not_found()),
+    };
+    // a move may only be checked for the player who signed it
+    let verified = table
+        .verifier
+        .verify(&request)
+        .await
+        .and_then(|_| match body.player_id == request.account_id {
+            true => Ok(()),
+            false => Err(AuthError::NotSigner(body.player_id)),
+        });
+    if let Err(e) = verified {
+        return Ok(error_reply(e.to_string(), StatusCode::UNAUTHORIZED));
+    }
+
+    // the table posts the straddle itself
+    if body.action.action_type == ActionType::Straddle {
+        return Ok(warp::reply::with_status(
+            warp::reply::json(&[false]),
+            StatusCode::OK,
+        ));
+    }
+    let (result, next_player_id) = {
+        let mut game = table.game.lock().unwrap();
+        let result = game.check_move(body.action, body.player_id);
+        (result, game.current_player_id())
+    };
+    if result {
+        table
+            .publish(GameEvent::TurnChanged {
+                player_id: next_player_id,
+            })
+            .await;
+    }
+    Ok(warp::reply::with_status(
+        warp::reply::json(&[result]),
+        StatusCode::OK,
+    ))
+}
+
+pub async fn gamestate_handler(
+    game_id: String,
+    registry: GameRegistry,
 ) -> Result<impl warp::Reply, warp::Rejection> {
-    let mut game = local_game.lock().unwrap();
-    let result = game.check_move(body.action, body.player_id);
-    Ok(warp::reply::json(&[result]))
+    match registry.get(&game_id) {
+        Some(table) => {
+            let game = table.game.lock().unwrap().clone();
+            Ok(warp::reply::with_status(
+                warp::reply::json(&game),
+                StatusCode::OK,
+            ))
+        }
+        None => Ok(game_not_found()),
+    }
+}
+
+async fn games_handler(registry: GameRegistry) -> Result<impl warp::Reply, warp::Rejection> {
+    let mut games = vec![];
+    for table in registry.tables() {
+        games.push(table.summary().await);
+    }
+    games.sort_by(|a, b| a.game_id.cmp(&b.game_id));
+    Ok(warp::reply::json(&games))
+}
+
+// hosts a game created by a dealer sharing this server's store, signed by the game account
+async fn host_handler(
+    request: SignedRequest,
+    registry: GameRegistry,
+) -> Result<impl warp::Reply, warp::Rejection> {
+    let hosted_game = match request.payload::<HostedGame>() {
+        Ok(hosted_game) => hosted_game,
+        Err(e) => return Ok(error_reply(e.to_string(), StatusCode::BAD_REQUEST)),
+    };
+    if registry.get(&hosted_game.game_id).is_some() {
+        return Ok(error_reply(
+            String::from("Game is already hosted"),
+            StatusCode::CONFLICT,
+        ));
+    }
+
+    // the game account has to be in our store to consume its notes, so its key must be too
+    let game_account_key = AccountId::from_hex(&hosted_game.game_id)
+        .ok()
+        .and_then(|game_account_id| {
+            let client: AzeClient = create_aze_client();
+            client.get_account(game_account_id).ok()
+        })
+        .map(|(game_account, _)| (game_account.id(), game_account.storage().get_item(0)));
+    let (game_account_id, public_key) = match game_account_key {
+        Some(game_account_key) => game_account_key,
+        None => {
+            return Ok(error_reply(
+                String::from("Game account not found in the server's store"),
+                StatusCode::NOT_FOUND,
+            ))
+        }
+    };
+    let verifier = RequestVerifier::new(vec![game_account_id.into()], node_rpc_config());
+    verifier.add_key(game_account_id.into(), public_key.into());
+    if let Err(e) = verifier.verify(&request).await {
+        return Ok(error_reply(e.to_string(), StatusCode::UNAUTHORIZED));
+    }
+
+    match registry.host_new_game(hosted_game) {
+        Ok(ws_url) => Ok(warp::reply::with_status(
+            warp::reply::json(&ws_url),
+            StatusCode::OK,
+        )),
+        Err(e) => Ok(error_reply(e, StatusCode::FORBIDDEN)),
+    }
+}
+
+fn error_reply(message: String, status: StatusCode) -> warp::reply::WithStatus<warp::reply::Json> {
+    warp::reply::with_status(warp::reply::json(&message), status)
+}
+
+fn game_not_found() -> warp::reply::WithStatus<warp::reply::Json> {
+    error_reply(String::from("Game ID not found"), StatusCode::NOT_FOUND)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::auth::RequestSigner;
+    use aze_types::lobby::Stakes;
+    use miden_objects::crypto::dsa::rpo_falcon512::SecretKey;
+    use miden_objects::Word;
+    use tokio::time::{sleep, timeout};
+    use warp::test::WsClient;
+
+    fn player(account_id: u64) -> (RequestSigner, Word) {
+        let secret_key = SecretKey::with_rng(&mut rand::thread_rng());
+        let public_key = secret_key.public_key().into();
+        (RequestSigner::new(account_id, secret_key), public_key)
+    }
+
+    // a table whose verifier already knows its players' keys, so no node is asked for them
+    fn host_test_table(
+        registry: &GameRegistry,
+        game_id: &str,
+        players: &[(u64, Word)],
+    ) -> Arc<GameTable> {
+        let player_ids: Vec<u64> = players.iter().map(|(player_id, _)| *player_id).collect();
+        let verifier = RequestVerifier::new(player_ids.clone(), RpcConfig::default());
+        for (player_id, public_key) in players {
+            verifier.add_key(*player_id, *public_key);
+        }
+        let table = Arc::new(GameTable {
+            game_id: game_id.to_string(),
+            small_blind: 5,
+            buy_in: 100,
+            game: Mutex::new(PokerGame::new(
+                player_ids.clone(),
+                vec![100; player_ids.len()],
+                5,
+                10,
+            )),
+            verifier,
+            channel: RwLock::new(GameChannel::new()),
+            snapshot_path: None,
+            chat_limiter: ChatLimiter::default(),
+        });
+        registry
+            .tables
+            .lock()
+            .unwrap()
+            .insert(game_id.to_string(), table.clone());
+        table
+    }
+
+    // every message the client gets until it goes quiet
+    async fn received(client: &mut WsClient) -> Vec<EventMessage> {
+        let mut messages = vec![];
+        while let Ok(Ok(msg)) = timeout(Duration::from_millis(300), client.recv()).await {
+            messages.push(serde_json::from_str(msg.to_str().unwrap()).unwrap());
+        }
+        messages
+    }
+
+    #[test]
+    fn test_chat_rate_limit() {
+        let limiter = ChatLimiter::default();
+        let start = Instant::now();
+        for _ in 0..CHAT_RATE_LIMIT {
+            assert!(limiter.allow(1, start));
+        }
+        assert!(!limiter.allow(1, start));
+        // other players have their own allowance
+        assert!(limiter.allow(2, start));
+        // the oldest messages leave the window
+        assert!(limiter.allow(1, start + Duration::from_secs(CHAT_RATE_WINDOW_SECS)));
+    }
+
+    #[tokio::test]
+    async fn test_lobby_join_checks_the_account_key() {
+        let registry = GameRegistry::new();
+        let _formed_tables = registry.accept_lobby(RpcConfig::default());
+        let secret_key = SecretKey::with_rng(&mut rand::thread_rng());
+        registry
+            .lobby_verifier
+            .get()
+            .unwrap()
+            .add_key(1, secret_key.public_key().into());
+        let join = LobbyJoinRequest {
+            player_id: 1,
+            identifier: String::from("alice"),
+            stakes: Stakes {
+                small_blind: 5,
+                big_blind: 10,
+            },
+        };
+        let (updates, _) = mpsc::unbounded_channel();
+
+        // someone else's key can't queue as player 1
+        let impersonator = RequestSigner::new(1, SecretKey::with_rng(&mut rand::thread_rng()));
+        let request = serde_json::to_string(&impersonator.sign(&join)).unwrap();
+        assert_eq!(
+            registry.join_lobby(&request, updates.clone()).await,
+            Err(AuthError::KeyMismatch(1).to_string())
+        );
+
+        let player = RequestSigner::new(1, secret_key);
+        let request = serde_json::to_string(&player.sign(&join)).unwrap();
+        assert_eq!(registry.join_lobby(&request, updates).await, Ok(1));
+    }
+
+    #[tokio::test]
+    async fn test_resume_from_sequence() {
+        let registry = GameRegistry::new();
+        let (signer, public_key) = player(1);
+        let table = host_test_table(&registry, "game", &[(1, public_key)]);
+        for player_id in 1..=5 {
+            table.publish(GameEvent::TurnChanged { player_id }).await;
+        }
+        let routes = routes(registry.clone(), 0);
+
+        // resuming after event k replays k+1 to 5, nothing once k is past the log
+        for (since, expected) in [(0, vec![1, 2, 3, 4, 5]), (5, vec![]), (9, vec![]), (2, vec![3, 4, 5])] {
+            // every handshake is signed later than the one before
+            sleep(Duration::from_millis(2)).await;
+            let subscribe = signer.sign(&SubscribeRequest {
+                game_id: String::from("game"),
+            });
+            let mut client = warp::test::ws()
+                .path(&format!("/ws/game?since={}", since))
+                .header(SIGNED_REQUEST_HEADER, serde_json::to_string(&subscribe).unwrap())
+                .handshake(routes.clone())
+                .await
+                .unwrap();
+            let replayed = received(&mut client).await;
+            let sequences: Vec<u64> = replayed.iter().map(|message| message.sequence).collect();
+            assert_eq!(sequences, expected);
+            for message in replayed {
+                assert_eq!(message.event, GameEvent::TurnChanged { player_id: message.sequence });
+            }
+
+            // and live events follow the replay
+            if since == 2 {
+                table.publish(GameEvent::GameEnded).await;
+                let live = received(&mut client).await;
+                assert_eq!(live.len(), 1);
+                assert_eq!((live[0].sequence, &live[0].event), (6, &GameEvent::GameEnded));
+            }
+        }
+        assert_eq!(table.channel.read().await.events_since(6).len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_tables_are_routed_by_game_id() {
+        let registry = GameRegistry::new();
+        let (alice, alice_key) = player(1);
+        let (bob, bob_key) = player(3);
+        let table_a = host_test_table(&registry, "game-a", &[(1, alice_key), (2, player(2).1)]);
+        let table_b = host_test_table(&registry, "game-b", &[(3, bob_key), (4, player(4).1)]);
+        let routes = routes(registry.clone(), 0);
+        let post = |path: &str, request: SignedRequest| {
+            warp::test::request()
+                .method("POST")
+                .path(path)
+                .json(&request)
+                .reply(&routes)
+        };
+        let publish = |game_id: &str| PublishRequest {
+            game_id: game_id.to_string(),
+            event: GameEvent::StreetAdvanced { phase: 1 },
+        };
+        let small_blind = |game_id: &str, player_id: u64| CheckmoveRequest {
+            game_id: game_id.to_string(),
+            player_id,
+            action: Check_Action {
+                action_type: ActionType::SmallBlind,
+                amount: None,
+            },
+        };
+
+        assert_eq!(post("/publish", alice.sign(&publish("game-a"))).await.status(), StatusCode::OK);
+        sleep(Duration::from_millis(2)).await;
+        let response = post("/checkmove", alice.sign(&small_blind("game-a", 1))).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.body().as_ref(), b"[true]");
+
+        // a player of one table can't publish or move at the other
+        sleep(Duration::from_millis(2)).await;
+        assert_eq!(
+            post("/publish", alice.sign(&publish("game-b"))).await.status(),
+            StatusCode::UNAUTHORIZED
+        );
+        sleep(Duration::from_millis(2)).await;
+        assert_eq!(
+            post("/checkmove", alice.sign(&small_blind("game-b", 1))).await.status(),
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            post("/checkmove", bob.sign(&small_blind("game-a", 3))).await.status(),
+            StatusCode::UNAUTHORIZED
+        );
+
+        let events = |table: &Arc<GameTable>| {
+            let table = table.clone();
+            async move {
+                let channel = table.channel.read().await;
+                channel.history.iter().map(|message| message.event.clone()).collect::<Vec<_>>()
+            }
+        };
+        assert_eq!(
+            events(&table_a).await,
+            vec![GameEvent::StreetAdvanced { phase: 1 }, GameEvent::TurnChanged { player_id: 2 }]
+        );
+        assert!(table_a.channel.read().await.history.iter().all(|message| message.game_id == "game-a"));
+        assert_eq!(events(&table_b).await, vec![]);
+        assert_eq!(table_a.game.lock().unwrap().pot(), 5);
+        let game_b = table_b.game.lock().unwrap().clone();
+        assert_eq!((game_b.pot(), game_b.current_player_id()), (0, 3));
+
+        let response = warp::test::request().path("/games").reply(&routes).await;
+        let games: Vec<GameSummary> = serde_json::from_slice(response.body()).unwrap();
+        let listed: Vec<(&str, u64)> = games
+            .iter()
+            .map(|game| (game.game_id.as_str(), game.events))
+            .collect();
+        assert_eq!(listed, vec![("game-a", 2), ("game-b", 0)]);
+    }
+
+    #[tokio::test]
+    async fn test_spectators_get_redacted_events_late() {
+        let registry = GameRegistry::new();
+        let table = host_test_table(&registry, "game", &[(1, player(1).1)]);
+        let mut spectator = warp::test::ws()
+            .path("/watch/game")
+            .handshake(routes(registry.clone(), 1))
+            .await
+            .unwrap();
+
+        table
+            .publish(GameEvent::HandCommitted {
+                player_id: 1,
+                hand_type: 4,
+            })
+            .await;
+        let published = Instant::now();
+        assert!(timeout(Duration::from_millis(500), spectator.recv()).await.is_err());
+        let msg = timeout(Duration::from_secs(3), spectator.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(published.elapsed() >= Duration::from_secs(1));
+        let message: EventMessage = serde_json::from_str(msg.to_str().unwrap()).unwrap();
+        assert_eq!(
+            message.event,
+            GameEvent::HandCommitted {
+                player_id: 1,
+                hand_type: 0,
+            }
+        );
+        // the log players replay from keeps the hand
+        assert_eq!(
+            table.channel.read().await.history[0].event,
+            GameEvent::HandCommitted {
+                player_id: 1,
+                hand_type: 4,
+            }
+        );
+    }
+
+    #[test]
+    fn test_stats_only_show_the_requesters_cards() {
+        let hand_cards = vec![vec![1, 2], vec![3, 4], vec![5, 6]];
+        for phase in 0..3 {
+            let mut redacted = hand_cards.clone();
+            redact_hole_cards(&mut redacted, Some(1), phase);
+            assert_eq!(redacted, vec![vec![0, 0], vec![3, 4], vec![0, 0]]);
+        }
+        // someone without a seat sees no cards at all
+        let mut redacted = hand_cards.clone();
+        redact_hole_cards(&mut redacted, None, 0);
+        assert_eq!(redacted, vec![vec![0, 0]; 3]);
+        // every hand is shown at the showdown
+        let mut shown = hand_cards.clone();
+        redact_hole_cards(&mut shown, None, 3);
+        assert_eq!(shown, hand_cards);
+    }
 }
@@ -1,8 +1,12 @@
 use crate::accounts::{create_basic_aze_game_account, create_basic_aze_player_account};
-use crate::constants::{ CLIENT_CONFIG_FILE_NAME, AUTH_SEND_NOTE_SCRIPT };
+use crate::constants::{ CLIENT_CONFIG_FILE_NAME, AUTH_SEND_ASSET_SCRIPT, AUTH_SEND_NOTE_SCRIPT, MAX_HOLE_CARDS, NO_OF_PLAYERS };
+use crate::mock::{MockNode, MockRpcClient};
 use crate::notes::{
     create_play_bet_note, create_play_call_note, create_play_check_note, create_play_fold_note,
-    create_play_raise_note, create_send_card_note, create_key_gen_note, create_shuffle_card_note,
+    create_play_raise_note, create_start_hand_note, create_join_seat_note,
+    create_leave_seat_note, create_sit_out_note, create_end_hand_note, create_cash_out_note,
+    create_p2id_note, create_withdraw_rake_note,
+    create_send_card_note, create_key_gen_note, create_shuffle_card_note,
     create_remask_note, create_set_cards_note, create_set_community_cards_note, create_unmask_note, 
     create_inter_unmask_note, create_send_unmasked_cards_note, create_set_hand_note, create_send_community_cards_note
 };
@@ -31,7 +35,7 @@ use miden_client::{
 
 use crate::storage::GameStorageSlotData;
 use miden_lib::AuthScheme;
-use miden_objects::assets::Asset;
+use miden_objects::assets::{Asset, FungibleAsset};
 use miden_objects::crypto::rand::FeltRng;
 use miden_objects::crypto::rand::RpoRandomCoin;
 use miden_objects::notes::NoteType;
@@ -46,8 +50,8 @@ use miden_tx::utils::Serializable;
 use miden_tx::TransactionAuthenticator;
 use rand::{rngs::ThreadRng, Rng};
 
-pub type AzeClient = Client<
-    TonicRpcClient,
+pub type AzeClient<N = TonicRpcClient> = Client<
+    N,
     RpoRandomCoin,
     SqliteStore,
     StoreAuthenticator<RpoRandomCoin, SqliteStore>,
@@ -57,7 +61,7 @@ pub type AzeClient = Client<
 pub struct SendCardTransactionData {
     sender_account_id: AccountId,
     target_account_id: AccountId,
-    cards: [[Felt; 4]; 2],
+    cards: [[Felt; 4]; MAX_HOLE_CARDS],
 }
 
 #[derive(Clone)]
@@ -107,6 +111,58 @@ pub struct PlayFoldTransactionData {
     target_account_id: AccountId,
 }
 
+#[derive(Clone)]
+pub struct StartHandTransactionData {
+    sender_account_id: AccountId,
+    target_account_id: AccountId,
+    small_blind: u64,
+    big_blind: u64,
+}
+
+#[derive(Clone)]
+pub struct JoinSeatTransactionData {
+    sender_account_id: AccountId,
+    target_account_id: AccountId,
+    seat: u8,
+    buy_in: FungibleAsset,
+}
+
+#[derive(Clone)]
+pub struct LeaveSeatTransactionData {
+    sender_account_id: AccountId,
+    target_account_id: AccountId,
+    seat: u8,
+}
+
+#[derive(Clone)]
+pub struct SitOutTransactionData {
+    sender_account_id: AccountId,
+    target_account_id: AccountId,
+    seat: u8,
+    sitting_out: bool,
+}
+
+#[derive(Clone)]
+pub struct EndHandTransactionData {
+    sender_account_id: AccountId,
+    target_account_id: AccountId,
+    pot: u64,
+    winnings: [u64; NO_OF_PLAYERS as usize],
+}
+
+#[derive(Clone)]
+pub struct WithdrawRakeTransactionData {
+    sender_account_id: AccountId,
+    target_account_id: AccountId,
+}
+
+#[derive(Clone)]
+pub struct CashOutTransactionData {
+    sender_account_id: AccountId,
+    target_account_id: AccountId,
+    seat: u8,
+}
+
 #[derive(Clone)]
 pub struct PlayCheckTransactionData {
     sender_account_id: AccountId,
@@ -124,14 +180,14 @@ pub struct SetCardsTransactionData {
 pub struct SendUnmaskedCardsTransactionData {
     sender_account_id: AccountId,
     target_account_id: AccountId,
-    cards: [[Felt; 4]; 3],
+    cards: [[Felt; 4]; MAX_HOLE_CARDS],
 }
 
 #[derive(Clone)]
 pub struct UnmaskTransactionData {
     sender_account_id: AccountId,
     target_account_id: AccountId,
-    cards: [[Felt; 4]; 3],
+    cards: [[Felt; 4]; MAX_HOLE_CARDS],
     card_slot: u8
 }
 
@@ -139,7 +195,7 @@ pub struct UnmaskTransactionData {
 pub struct InterUnmaskTransactionData {
     sender_account_id: AccountId,
     target_account_id: AccountId,
-    cards: [[Felt; 4]; 3],
+    cards: [[Felt; 4]; MAX_HOLE_CARDS],
     requester_id: AccountId,
 }
 
@@ -147,7 +203,7 @@ pub struct InterUnmaskTransactionData {
 pub struct SetHandTransactionData {
     sender_account_id: AccountId,
     target_account_id: AccountId,
-    cards: [[Felt; 4]; 2],
+    cards: [[Felt; 4]; MAX_HOLE_CARDS],
     player_hand: u8,
     player_index: u8
 }
@@ -218,7 +274,7 @@ impl SendCardTransactionData {
     pub fn new(
         sender_account_id: AccountId,
         target_account_id: AccountId,
-        cards: &[[Felt; 4]; 2],
+        cards: &[[Felt; 4]; MAX_HOLE_CARDS],
     ) -> Self {
         Self {
             sender_account_id,
@@ -286,6 +342,120 @@ impl PlayFoldTransactionData {
     }
 }
 
+impl StartHandTransactionData {
+    pub fn account_id(&self) -> AccountId {
+        self.sender_account_id
+    }
+    pub fn new(
+        sender_account_id: AccountId,
+        target_account_id: AccountId,
+        small_blind: u64,
+        big_blind: u64,
+    ) -> Self {
+        Self {
+            sender_account_id,
+            target_account_id,
+            small_blind,
+            big_blind,
+        }
+    }
+}
+
+impl JoinSeatTransactionData {
+    pub fn account_id(&self) -> AccountId {
+        self.sender_account_id
+    }
+    pub fn new(
+        sender_account_id: AccountId,
+        target_account_id: AccountId,
+        seat: u8,
+        buy_in: FungibleAsset,
+    ) -> Self {
+        Self {
+            sender_account_id,
+            target_account_id,
+            seat,
+            buy_in,
+        }
+    }
+}
+
+impl LeaveSeatTransactionData {
+    pub fn account_id(&self) -> AccountId {
+        self.sender_account_id
+    }
+    pub fn new(sender_account_id: AccountId, target_account_id: AccountId, seat: u8) -> Self {
+        Self {
+            sender_account_id,
+            target_account_id,
+            seat,
+        }
+    }
+}
+
+impl SitOutTransactionData {
+    pub fn account_id(&self) -> AccountId {
+        self.sender_account_id
+    }
+    pub fn new(
+        sender_account_id: AccountId,
+        target_account_id: AccountId,
+        seat: u8,
+        sitting_out: bool,
+    ) -> Self {
+        Self {
+            sender_account_id,
+            target_account_id,
+            seat,
+            sitting_out,
+        }
+    }
+}
+
+impl EndHandTransactionData {
+    pub fn account_id(&self) -> AccountId {
+        self.sender_account_id
+    }
+    pub fn new(
+        sender_account_id: AccountId,
+        target_account_id: AccountId,
+        pot: u64,
+        winnings: [u64; NO_OF_PLAYERS as usize],
+    ) -> Self {
+        Self {
+            sender_account_id,
+            target_account_id,
+            pot,
+            winnings,
+        }
+    }
+}
+
+impl WithdrawRakeTransactionData {
+    pub fn account_id(&self) -> AccountId {
+        self.sender_account_id
+    }
+    pub fn new(sender_account_id: AccountId, target_account_id: AccountId) -> Self {
+        Self {
+            sender_account_id,
+            target_account_id,
+        }
+    }
+}
+
+impl CashOutTransactionData {
+    pub fn account_id(&self) -> AccountId {
+        self.sender_account_id
+    }
+    pub fn new(sender_account_id: AccountId, target_account_id: AccountId, seat: u8) -> Self {
+        Self {
+            sender_account_id,
+            target_account_id,
+            seat,
+        }
+    }
+}
+
 impl PlayCheckTransactionData {
     pub fn account_id(&self) -> AccountId {
         self.sender_account_id
@@ -322,7 +492,7 @@ impl SendUnmaskedCardsTransactionData {
     pub fn new(
         sender_account_id: AccountId,
         target_account_id: AccountId,
-        cards: &[[Felt; 4]; 3],
+        cards: &[[Felt; 4]; MAX_HOLE_CARDS],
     ) -> Self {
         Self {
             sender_account_id,
@@ -339,7 +509,7 @@ impl UnmaskTransactionData {
     pub fn new(
         sender_account_id: AccountId,
         target_account_id: AccountId,
-        cards: &[[Felt; 4]; 3],
+        cards: &[[Felt; 4]; MAX_HOLE_CARDS],
         card_slot: u8
     ) -> Self {
         Self {
@@ -358,7 +528,7 @@ impl InterUnmaskTransactionData {
     pub fn new(
         sender_account_id: AccountId,
         target_account_id: AccountId,
-        cards: &[[Felt; 4]; 3],
+        cards: &[[Felt; 4]; MAX_HOLE_CARDS],
         requester_id: AccountId,
     ) -> Self {
         Self {
@@ -377,7 +547,7 @@ impl SetHandTransactionData {
     pub fn new(
         sender_account_id: AccountId,
         target_account_id: AccountId,
-        cards: &[[Felt; 4]; 2],
+        cards: &[[Felt; 4]; MAX_HOLE_CARDS],
         player_hand: u8,
         player_index: u8
     ) -> Self {
@@ -459,6 +629,34 @@ pub trait AzeGameMethods {
         // auth_info: AuthSecretKey,
         transaction_template: AzeTransactionTemplate,
     ) -> Result<TransactionRequest, ClientError>;
+    fn build_aze_start_hand_tx_request(
+        &mut self,
+        transaction_template: AzeTransactionTemplate,
+    ) -> Result<TransactionRequest, ClientError>;
+    fn build_aze_join_seat_tx_request(
+        &mut self,
+        transaction_template: AzeTransactionTemplate,
+    ) -> Result<TransactionRequest, ClientError>;
+    fn build_aze_leave_seat_tx_request(
+        &mut self,
+        transaction_template: AzeTransactionTemplate,
+    ) -> Result<TransactionRequest, ClientError>;
+    fn build_aze_sit_out_tx_request(
+        &mut self,
+        transaction_template: AzeTransactionTemplate,
+    ) -> Result<TransactionRequest, ClientError>;
+    fn build_aze_end_hand_tx_request(
+        &mut self,
+        transaction_template: AzeTransactionTemplate,
+    ) -> Result<TransactionRequest, ClientError>;
+    fn build_aze_cash_out_tx_request(
+        &mut self,
+        transaction_template: AzeTransactionTemplate,
+    ) -> Result<TransactionRequest, ClientError>;
+    fn build_aze_withdraw_rake_tx_request(
+        &mut self,
+        transaction_template: AzeTransactionTemplate,
+    ) -> Result<TransactionRequest, ClientError>;
     fn build_aze_set_cards_tx_request(
         &mut self,
         // auth_info: AuthSecretKey,
@@ -532,8 +730,38 @@ pub fn create_aze_client() -> AzeClient {
         .unwrap();
     current_dir.push(CLIENT_CONFIG_FILE_NAME);
     let client_config = load_config(current_dir.as_path()).unwrap();
+    create_aze_client_with_rpc(TonicRpcClient::new(&client_config.rpc), &client_config)
+}
+
+// rpc settings of the node the client config in the working directory points at
+pub fn node_rpc_config() -> RpcConfig {
+    let mut current_dir = std::env::current_dir()
+        .map_err(|err| err.to_string())
+        .unwrap();
+    current_dir.push(CLIENT_CONFIG_FILE_NAME);
+    load_config(current_dir.as_path()).unwrap().rpc
+}
+
+// client backed by the in-process mock node, with its own throwaway store
+pub fn create_mock_aze_client(node: &MockNode) -> AzeClient<MockRpcClient> {
+    let client_config = ClientConfig::new(
+        create_aze_store_path()
+            .into_os_string()
+            .into_string()
+            .unwrap()
+            .try_into()
+            .unwrap(),
+        RpcConfig::default(),
+    );
+    create_aze_client_with_rpc(node.rpc_client(), &client_config)
+}
+
+pub fn create_aze_client_with_rpc<N: NodeRpcClient>(
+    rpc_api: N,
+    client_config: &ClientConfig,
+) -> AzeClient<N> {
     let store = {
-        let sqlite_store = SqliteStore::new((&client_config).into()).unwrap();
+        let sqlite_store = SqliteStore::new(client_config.into()).unwrap();
         Rc::new(sqlite_store)
     };
 
@@ -541,7 +769,7 @@ pub fn create_aze_client() -> AzeClient {
 
     let authenticator = StoreAuthenticator::new_with_rng(store.clone(), rng);
     AzeClient::new(
-        TonicRpcClient::new(&client_config.rpc),
+        rpc_api,
         rng,
         store,
         authenticator,
@@ -657,7 +885,7 @@ impl<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator> AzeGam
         transaction_template: AzeTransactionTemplate,
     ) -> Result<TransactionRequest, ClientError> {
         let account_id = transaction_template.account_id();
-        let account_auth = self.store().get_account_auth(account_id)?;
+        let account_auth = self.get_account_auth(account_id)?;
 
         let (sender_account_id, target_account_id, cards) = match transaction_template {
             AzeTransactionTemplate::SendCard(SendCardTransactionData {
@@ -729,7 +957,7 @@ impl<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator> AzeGam
         transaction_template: AzeTransactionTemplate,
     ) -> Result<TransactionRequest, ClientError> {
         let account_id = transaction_template.account_id();
-        let account_auth = self.store().get_account_auth(account_id)?;
+        let account_auth = self.get_account_auth(account_id)?;
 
         let (sender_account_id, target_account_id) = match transaction_template {
             AzeTransactionTemplate::GenKey(GenPrivateKeyTransactionData {
@@ -800,7 +1028,7 @@ impl<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator> AzeGam
         transaction_template: AzeTransactionTemplate,
     ) -> Result<TransactionRequest, ClientError> {
         let account_id = transaction_template.account_id();
-        let account_auth = self.store().get_account_auth(account_id)?;
+        let account_auth = self.get_account_auth(account_id)?;
 
         let (sender_account_id, target_account_id, player_data) = match transaction_template {
             AzeTransactionTemplate::ShuffleCard(ShuffleCardTransactionData {
@@ -871,7 +1099,7 @@ impl<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator> AzeGam
         transaction_template: AzeTransactionTemplate,
     ) -> Result<TransactionRequest, ClientError> {
         let account_id = transaction_template.account_id();
-        let account_auth = self.store().get_account_auth(account_id)?;
+        let account_auth = self.get_account_auth(account_id)?;
 
         let (sender_account_id, target_account_id, cards, player_data) = match transaction_template {
             AzeTransactionTemplate::Remask(RemaskTransactionData {
@@ -944,7 +1172,7 @@ impl<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator> AzeGam
         transaction_template: AzeTransactionTemplate,
     ) -> Result<TransactionRequest, ClientError> {
         let account_id = transaction_template.account_id();
-        let account_auth = self.store().get_account_auth(account_id)?;
+        let account_auth = self.get_account_auth(account_id)?;
 
         let (sender_account_id, target_account_id, player_bet) = match transaction_template {
             AzeTransactionTemplate::PlayBet(PlayBetTransactionData {
@@ -1016,7 +1244,7 @@ impl<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator> AzeGam
         transaction_template: AzeTransactionTemplate,
     ) -> Result<TransactionRequest, ClientError> {
         let account_id = transaction_template.account_id();
-        let account_auth = self.store().get_account_auth(account_id)?;
+        let account_auth = self.get_account_auth(account_id)?;
 
         let (sender_account_id, target_account_id, player_bet) = match transaction_template {
             AzeTransactionTemplate::PlayRaise(PlayRaiseTransactionData {
@@ -1086,7 +1314,7 @@ impl<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator> AzeGam
         transaction_template: AzeTransactionTemplate,
     ) -> Result<TransactionRequest, ClientError> {
         let account_id = transaction_template.account_id();
-        let account_auth = self.store().get_account_auth(account_id)?;
+        let account_auth = self.get_account_auth(account_id)?;
 
         let (sender_account_id, target_account_id) = match transaction_template {
             AzeTransactionTemplate::PlayCall(PlayCallTransactionData {
@@ -1150,13 +1378,529 @@ impl<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator> AzeGam
         ))
     }
 
+    fn build_aze_start_hand_tx_request(
+        &mut self,
+        transaction_template: AzeTransactionTemplate,
+    ) -> Result<TransactionRequest, ClientError> {
+        let account_id = transaction_template.account_id();
+        let account_auth = self.get_account_auth(account_id)?;
+
+        let (sender_account_id, target_account_id, small_blind, big_blind) =
+            match transaction_template {
+                AzeTransactionTemplate::StartHand(StartHandTransactionData {
+                    sender_account_id,
+                    target_account_id,
+                    small_blind,
+                    big_blind,
+                }) => (sender_account_id, target_account_id, small_blind, big_blind),
+                _ => panic!("Invalid transaction template"),
+            };
+
+        let random_coin = self.get_random_coin();
+
+        let created_note = create_start_hand_note(
+            self,
+            sender_account_id,
+            target_account_id,
+            NoteType::Public,
+            random_coin,
+            small_blind,
+            big_blind,
+        )?;
+
+        let recipient = created_note
+            .recipient()
+            .digest()
+            .iter()
+            .map(|x| x.as_int().to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+
+        let note_tag = created_note.metadata().tag().inner();
+        let note_type = NoteType::Public;
+
+        let tx_script = ProgramAst::parse(
+            &AUTH_SEND_NOTE_SCRIPT
+                .replace("{recipient}", &recipient)
+                .replace("{note_type}", &Felt::from(note_type as u8).to_string())
+                .replace("{tag}", &Felt::new(note_tag.into()).to_string())
+        ).unwrap();
+
+        let (pubkey_input, advice_map): (Word, Vec<Felt>) = match account_auth {
+            AuthSecretKey::RpoFalcon512(key) => (
+                key.public_key().into(),
+                key.to_bytes()
+                    .iter()
+                    .map(|a| Felt::new(*a as u64))
+                    .collect::<Vec<Felt>>(),
+            ),
+        };
+
+        let tx_script = {
+            let script_inputs = vec![(pubkey_input, advice_map)];
+            self.compile_tx_script(tx_script, script_inputs, vec![])?
+        };
+
+        Ok(TransactionRequest::new(
+            sender_account_id,
+            BTreeMap::new(),
+            vec![created_note],
+            vec![],
+            Some(tx_script),
+        ))
+    }
+
+    fn build_aze_join_seat_tx_request(
+        &mut self,
+        transaction_template: AzeTransactionTemplate,
+    ) -> Result<TransactionRequest, ClientError> {
+        let account_id = transaction_template.account_id();
+        let account_auth = self.get_account_auth(account_id)?;
+
+        let (sender_account_id, target_account_id, seat, buy_in) = match transaction_template {
+            AzeTransactionTemplate::JoinSeat(JoinSeatTransactionData {
+                sender_account_id,
+                target_account_id,
+                seat,
+                buy_in,
+            }) => (sender_account_id, target_account_id, seat, buy_in),
+            _ => panic!("Invalid transaction template"),
+        };
+
+        let random_coin = self.get_random_coin();
+
+        let created_note = create_join_seat_note(
+            self,
+            sender_account_id,
+            target_account_id,
+            NoteType::Public,
+            random_coin,
+            seat,
+            buy_in,
+        )?;
+
+        let recipient = created_note
+            .recipient()
+            .digest()
+            .iter()
+            .map(|x| x.as_int().to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+
+        let note_tag = created_note.metadata().tag().inner();
+        let note_type = NoteType::Public;
+
+        // the buy-in leaves the player's vault with the note
+        let tx_script = ProgramAst::parse(
+            &AUTH_SEND_ASSET_SCRIPT
+                .replace("{recipient}", &recipient)
+                .replace("{note_type}", &Felt::from(note_type as u8).to_string())
+                .replace("{tag}", &Felt::new(note_tag.into()).to_string())
+                .replace("{asset}", &prepare_word(&buy_in.into()))
+        ).unwrap();
+
+        let (pubkey_input, advice_map): (Word, Vec<Felt>) = match account_auth {
+            AuthSecretKey::RpoFalcon512(key) => (
+                key.public_key().into(),
+                key.to_bytes()
+                    .iter()
+                    .map(|a| Felt::new(*a as u64))
+                    .collect::<Vec<Felt>>(),
+            ),
+        };
+
+        let tx_script = {
+            let script_inputs = vec![(pubkey_input, advice_map)];
+            self.compile_tx_script(tx_script, script_inputs, vec![])?
+        };
+
+        Ok(TransactionRequest::new(
+            sender_account_id,
+            BTreeMap::new(),
+            vec![created_note],
+            vec![],
+            Some(tx_script),
+        ))
+    }
+
+    fn build_aze_leave_seat_tx_request(
+        &mut self,
+        transaction_template: AzeTransactionTemplate,
+    ) -> Result<TransactionRequest, ClientError> {
+        let account_id = transaction_template.account_id();
+        let account_auth = self.get_account_auth(account_id)?;
+
+        let (sender_account_id, target_account_id, seat) = match transaction_template {
+            AzeTransactionTemplate::LeaveSeat(LeaveSeatTransactionData {
+                sender_account_id,
+                target_account_id,
+                seat,
+            }) => (sender_account_id, target_account_id, seat),
+            _ => panic!("Invalid transaction template"),
+        };
+
+        let random_coin = self.get_random_coin();
+
+        let created_note = create_leave_seat_note(
+            self,
+            sender_account_id,
+            target_account_id,
+            NoteType::Public,
+            random_coin,
+            seat,
+        )?;
+
+        let recipient = created_note
+            .recipient()
+            .digest()
+            .iter()
+            .map(|x| x.as_int().to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+
+        let note_tag = created_note.metadata().tag().inner();
+        let note_type = NoteType::Public;
+
+        let tx_script = ProgramAst::parse(
+            &AUTH_SEND_NOTE_SCRIPT
+                .replace("{recipient}", &recipient)
+                .replace("{note_type}", &Felt::from(note_type as u8).to_string())
+                .replace("{tag}", &Felt::new(note_tag.into()).to_string())
+        ).unwrap();
+
+        let (pubkey_input, advice_map): (Word, Vec<Felt>) = match account_auth {
+            AuthSecretKey::RpoFalcon512(key) => (
+                key.public_key().into(),
+                key.to_bytes()
+                    .iter()
+                    .map(|a| Felt::new(*a as u64))
+                    .collect::<Vec<Felt>>(),
+            ),
+        };
+
+        let tx_script = {
+            let script_inputs = vec![(pubkey_input, advice_map)];
+            self.compile_tx_script(tx_script, script_inputs, vec![])?
+        };
+
+        Ok(TransactionRequest::new(
+            sender_account_id,
+            BTreeMap::new(),
+            vec![created_note],
+            vec![],
+            Some(tx_script),
+        ))
+    }
+
+    fn build_aze_sit_out_tx_request(
+        &mut self,
+        transaction_template: AzeTransactionTemplate,
+    ) -> Result<TransactionRequest, ClientError> {
+        let account_id = transaction_template.account_id();
+        let account_auth = self.get_account_auth(account_id)?;
+
+        let (sender_account_id, target_account_id, seat, sitting_out) = match transaction_template {
+            AzeTransactionTemplate::SitOut(SitOutTransactionData {
+                sender_account_id,
+                target_account_id,
+                seat,
+                sitting_out,
+            }) => (sender_account_id, target_account_id, seat, sitting_out),
+            _ => panic!("Invalid transaction template"),
+        };
+
+        let random_coin = self.get_random_coin();
+
+        let created_note = create_sit_out_note(
+            self,
+            sender_account_id,
+            target_account_id,
+            NoteType::Public,
+            random_coin,
+            seat,
+            sitting_out,
+        )?;
+
+        let recipient = created_note
+            .recipient()
+            .digest()
+            .iter()
+            .map(|x| x.as_int().to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+
+        let note_tag = created_note.metadata().tag().inner();
+        let note_type = NoteType::Public;
+
+        let tx_script = ProgramAst::parse(
+            &AUTH_SEND_NOTE_SCRIPT
+                .replace("{recipient}", &recipient)
+                .replace("{note_type}", &Felt::from(note_type as u8).to_string())
+                .replace("{tag}", &Felt::new(note_tag.into()).to_string())
+        ).unwrap();
+
+        let (pubkey_input, advice_map): (Word, Vec<Felt>) = match account_auth {
+            AuthSecretKey::RpoFalcon512(key) => (
+                key.public_key().into(),
+                key.to_bytes()
+                    .iter()
+                    .map(|a| Felt::new(*a as u64))
+                    .collect::<Vec<Felt>>(),
+            ),
+        };
+
+        let tx_script = {
+            let script_inputs = vec![(pubkey_input, advice_map)];
+            self.compile_tx_script(tx_script, script_inputs, vec![])?
+        };
+
+        Ok(TransactionRequest::new(
+            sender_account_id,
+            BTreeMap::new(),
+            vec![created_note],
+            vec![],
+            Some(tx_script),
+        ))
+    }
+
+    fn build_aze_end_hand_tx_request(
+        &mut self,
+        transaction_template: AzeTransactionTemplate,
+    ) -> Result<TransactionRequest, ClientError> {
+        let account_id = transaction_template.account_id();
+        let account_auth = self.get_account_auth(account_id)?;
+
+        let (sender_account_id, target_account_id, pot, winnings) = match transaction_template {
+            AzeTransactionTemplate::EndHand(EndHandTransactionData {
+                sender_account_id,
+                target_account_id,
+                pot,
+                winnings,
+            }) => (sender_account_id, target_account_id, pot, winnings),
+            _ => panic!("Invalid transaction template"),
+        };
+
+        let random_coin = self.get_random_coin();
+
+        let created_note = create_end_hand_note(
+            self,
+            sender_account_id,
+            target_account_id,
+            NoteType::Public,
+            random_coin,
+            pot,
+            winnings,
+        )?;
+
+        let recipient = created_note
+            .recipient()
+            .digest()
+            .iter()
+            .map(|x| x.as_int().to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+
+        let note_tag = created_note.metadata().tag().inner();
+        let note_type = NoteType::Public;
+
+        let tx_script = ProgramAst::parse(
+            &AUTH_SEND_NOTE_SCRIPT
+                .replace("{recipient}", &recipient)
+                .replace("{note_type}", &Felt::from(note_type as u8).to_string())
+                .replace("{tag}", &Felt::new(note_tag.into()).to_string())
+        ).unwrap();
+
+        let (pubkey_input, advice_map): (Word, Vec<Felt>) = match account_auth {
+            AuthSecretKey::RpoFalcon512(key) => (
+                key.public_key().into(),
+                key.to_bytes()
+                    .iter()
+                    .map(|a| Felt::new(*a as u64))
+                    .collect::<Vec<Felt>>(),
+            ),
+        };
+
+        let tx_script = {
+            let script_inputs = vec![(pubkey_input, advice_map)];
+            self.compile_tx_script(tx_script, script_inputs, vec![])?
+        };
+
+        Ok(TransactionRequest::new(
+            sender_account_id,
+            BTreeMap::new(),
+            vec![created_note],
+            vec![],
+            Some(tx_script),
+        ))
+    }
+
+    fn build_aze_cash_out_tx_request(
+        &mut self,
+        transaction_template: AzeTransactionTemplate,
+    ) -> Result<TransactionRequest, ClientError> {
+        let account_id = transaction_template.account_id();
+        let account_auth = self.get_account_auth(account_id)?;
+
+        let (sender_account_id, target_account_id, seat) = match transaction_template {
+            AzeTransactionTemplate::CashOut(CashOutTransactionData {
+                sender_account_id,
+                target_account_id,
+                seat,
+            }) => (sender_account_id, target_account_id, seat),
+            _ => panic!("Invalid transaction template"),
+        };
+
+        // the game account fills the payout with the seat balance when it consumes the note
+        let random_coin = self.get_random_coin();
+        let payout = create_p2id_note(
+            self,
+            target_account_id,
+            sender_account_id,
+            vec![],
+            NoteType::Public,
+            random_coin,
+        )?;
+
+        let random_coin = self.get_random_coin();
+
+        let created_note = create_cash_out_note(
+            self,
+            sender_account_id,
+            target_account_id,
+            NoteType::Public,
+            random_coin,
+            seat,
+            &payout,
+        )?;
+
+        let recipient = created_note
+            .recipient()
+            .digest()
+            .iter()
+            .map(|x| x.as_int().to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+
+        let note_tag = created_note.metadata().tag().inner();
+        let note_type = NoteType::Public;
+
+        let tx_script = ProgramAst::parse(
+            &AUTH_SEND_NOTE_SCRIPT
+                .replace("{recipient}", &recipient)
+                .replace("{note_type}", &Felt::from(note_type as u8).to_string())
+                .replace("{tag}", &Felt::new(note_tag.into()).to_string())
+        ).unwrap();
+
+        let (pubkey_input, advice_map): (Word, Vec<Felt>) = match account_auth {
+            AuthSecretKey::RpoFalcon512(key) => (
+                key.public_key().into(),
+                key.to_bytes()
+                    .iter()
+                    .map(|a| Felt::new(*a as u64))
+                    .collect::<Vec<Felt>>(),
+            ),
+        };
+
+        let tx_script = {
+            let script_inputs = vec![(pubkey_input, advice_map)];
+            self.compile_tx_script(tx_script, script_inputs, vec![])?
+        };
+
+        Ok(TransactionRequest::new(
+            sender_account_id,
+            BTreeMap::new(),
+            vec![created_note],
+            vec![],
+            Some(tx_script),
+        ))
+    }
+
+    fn build_aze_withdraw_rake_tx_request(
+        &mut self,
+        transaction_template: AzeTransactionTemplate,
+    ) -> Result<TransactionRequest, ClientError> {
+        let account_id = transaction_template.account_id();
+        let account_auth = self.get_account_auth(account_id)?;
+
+        let (sender_account_id, target_account_id) = match transaction_template {
+            AzeTransactionTemplate::WithdrawRake(WithdrawRakeTransactionData {
+                sender_account_id,
+                target_account_id,
+            }) => (sender_account_id, target_account_id),
+            _ => panic!("Invalid transaction template"),
+        };
+
+        // the game account fills the payout with the collected rake when it consumes the note
+        let random_coin = self.get_random_coin();
+        let payout = create_p2id_note(
+            self,
+            target_account_id,
+            sender_account_id,
+            vec![],
+            NoteType::Public,
+            random_coin,
+        )?;
+
+        let random_coin = self.get_random_coin();
+
+        let created_note = create_withdraw_rake_note(
+            self,
+            sender_account_id,
+            target_account_id,
+            NoteType::Public,
+            random_coin,
+            &payout,
+        )?;
+
+        let recipient = created_note
+            .recipient()
+            .digest()
+            .iter()
+            .map(|x| x.as_int().to_string())
+            .collect::<Vec<_>>()
+            .join(".");
+
+        let note_tag = created_note.metadata().tag().inner();
+        let note_type = NoteType::Public;
+
+        let tx_script = ProgramAst::parse(
+            &AUTH_SEND_NOTE_SCRIPT
+                .replace("{recipient}", &recipient)
+                .replace("{note_type}", &Felt::from(note_type as u8).to_string())
+                .replace("{tag}", &Felt::new(note_tag.into()).to_string())
+        ).unwrap();
+
+        let (pubkey_input, advice_map): (Word, Vec<Felt>) = match account_auth {
+            AuthSecretKey::RpoFalcon512(key) => (
+                key.public_key().into(),
+                key.to_bytes()
+                    .iter()
+                    .map(|a| Felt::new(*a as u64))
+                    .collect::<Vec<Felt>>(),
+            ),
+        };
+
+        let tx_script = {
+            let script_inputs = vec![(pubkey_input, advice_map)];
+            self.compile_tx_script(tx_script, script_inputs, vec![])?
+        };
+
+        Ok(TransactionRequest::new(
+            sender_account_id,
+            BTreeMap::new(),
+            vec![created_note],
+            vec![],
+            Some(tx_script),
+        ))
+    }
+
     fn build_aze_play_fold_tx_request(
         &mut self,
         // auth_info: AuthSecretKey,
         transaction_template: AzeTransactionTemplate,
     ) -> Result<TransactionRequest, ClientError> {
         let account_id = transaction_template.account_id();
-        let account_auth = self.store().get_account_auth(account_id)?;
+        let account_auth = self.get_account_auth(account_id)?;
 
         let (sender_account_id, target_account_id) = match transaction_template {
             AzeTransactionTemplate::PlayFold(PlayFoldTransactionData {
@@ -1224,7 +1968,7 @@ impl<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator> AzeGam
         transaction_template: AzeTransactionTemplate,
     ) -> Result<TransactionRequest, ClientError> {
         let account_id = transaction_template.account_id();
-        let account_auth = self.store().get_account_auth(account_id)?;
+        let account_auth = self.get_account_auth(account_id)?;
 
         let (sender_account_id, target_account_id) = match transaction_template {
             AzeTransactionTemplate::PlayCheck(PlayCheckTransactionData {
@@ -1292,7 +2036,7 @@ impl<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator> AzeGam
         transaction_template: AzeTransactionTemplate,
     ) -> Result<TransactionRequest, ClientError> {
         let account_id = transaction_template.account_id();
-        let account_auth = self.store().get_account_auth(account_id)?;
+        let account_auth = self.get_account_auth(account_id)?;
 
         let (sender_account_id, target_account_id, cards) = match transaction_template {
             AzeTransactionTemplate::SetCards(SetCardsTransactionData {
@@ -1363,7 +2107,7 @@ impl<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator> AzeGam
         transaction_template: AzeTransactionTemplate,
     ) -> Result<TransactionRequest, ClientError> {
         let account_id = transaction_template.account_id();
-        let account_auth = self.store().get_account_auth(account_id)?;
+        let account_auth = self.get_account_auth(account_id)?;
 
         let (sender_account_id, target_account_id, cards) = match transaction_template {
             AzeTransactionTemplate::SendUnmaskedCards(SendUnmaskedCardsTransactionData {
@@ -1434,7 +2178,7 @@ impl<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator> AzeGam
         transaction_template: AzeTransactionTemplate,
     ) -> Result<TransactionRequest, ClientError> {
         let account_id = transaction_template.account_id();
-        let account_auth = self.store().get_account_auth(account_id)?;
+        let account_auth = self.get_account_auth(account_id)?;
 
         let (sender_account_id, target_account_id, cards, card_slot) = match transaction_template {
             AzeTransactionTemplate::Unmask(UnmaskTransactionData {
@@ -1507,7 +2251,7 @@ impl<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator> AzeGam
         transaction_template: AzeTransactionTemplate,
     ) -> Result<TransactionRequest, ClientError> {
         let account_id = transaction_template.account_id();
-        let account_auth = self.store().get_account_auth(account_id)?;
+        let account_auth = self.get_account_auth(account_id)?;
 
         let (sender_account_id, target_account_id, cards, current_phase) = match transaction_template {
             AzeTransactionTemplate::SendCommunityCards(SendCommunityCardsTransactionData {
@@ -1580,7 +2324,7 @@ impl<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator> AzeGam
         transaction_template: AzeTransactionTemplate,
     ) -> Result<TransactionRequest, ClientError> {
         let account_id = transaction_template.account_id();
-        let account_auth = self.store().get_account_auth(account_id)?;
+        let account_auth = self.get_account_auth(account_id)?;
 
         let (sender_account_id, target_account_id, cards, card_slot) = match transaction_template {
             AzeTransactionTemplate::Unmask(UnmaskTransactionData {
@@ -1653,7 +2397,7 @@ impl<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator> AzeGam
         transaction_template: AzeTransactionTemplate,
     ) -> Result<TransactionRequest, ClientError> {
         let account_id = transaction_template.account_id();
-        let account_auth = self.store().get_account_auth(account_id)?;
+        let account_auth = self.get_account_auth(account_id)?;
 
         let (sender_account_id, target_account_id, cards, requester_id) = match transaction_template {
             AzeTransactionTemplate::InterUnmask(InterUnmaskTransactionData {
@@ -1726,7 +2470,7 @@ impl<N: NodeRpcClient, R: FeltRng, S: Store, A: TransactionAuthenticator> AzeGam
         transaction_template: AzeTransactionTemplate,
     ) -> Result<TransactionRequest, ClientError> {
         let account_id = transaction_template.account_id();
-        let account_auth = self.store().get_account_auth(account_id)?;
+        let account_auth = self.get_account_auth(account_id)?;
 
         let (sender_account_id, target_account_id, cards, player_hand, player_index) = match transaction_template {
             AzeTransactionTemplate::SetHand(SetHandTransactionData {
@@ -1812,6 +2556,13 @@ pub enum AzeTransactionTemplate {
     PlayCall(PlayCallTransactionData),
     PlayFold(PlayFoldTransactionData),
     PlayCheck(PlayCheckTransactionData),
+    StartHand(StartHandTransactionData),
+    JoinSeat(JoinSeatTransactionData),
+    LeaveSeat(LeaveSeatTransactionData),
+    SitOut(SitOutTransactionData),
+    EndHand(EndHandTransactionData),
+    CashOut(CashOutTransactionData),
+    WithdrawRake(WithdrawRakeTransactionData),
     GenKey(GenPrivateKeyTransactionData),
     ShuffleCard(ShuffleCardTransactionData),
     Remask(RemaskTransactionData),
@@ -1833,6 +2584,13 @@ impl AzeTransactionTemplate {
             AzeTransactionTemplate::PlayCall(p) => p.account_id(),
             AzeTransactionTemplate::PlayFold(p) => p.account_id(),
             AzeTransactionTemplate::PlayCheck(p) => p.account_id(),
+            AzeTransactionTemplate::StartHand(p) => p.account_id(),
+            AzeTransactionTemplate::JoinSeat(p) => p.account_id(),
+            AzeTransactionTemplate::LeaveSeat(p) => p.account_id(),
+            AzeTransactionTemplate::SitOut(p) => p.account_id(),
+            AzeTransactionTemplate::EndHand(p) => p.account_id(),
+            AzeTransactionTemplate::CashOut(p) => p.account_id(),
+            AzeTransactionTemplate::WithdrawRake(p) => p.account_id(),
             AzeTransactionTemplate::GenKey(p) => p.account_id(),
             AzeTransactionTemplate::ShuffleCard(p) => p.account_id(),
             AzeTransactionTemplate::Remask(p) => p.account_id(),
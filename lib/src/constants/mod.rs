@@ -7,6 +7,7 @@ pub const DEFAULT_AUTH_SCRIPT: &str = "
 ";
 
 pub const AUTH_SEND_NOTE_SCRIPT: &str = include_str!("../../asm/tx_scripts/auth_send_note.masm");
+pub const AUTH_SEND_ASSET_SCRIPT: &str = include_str!("../../asm/tx_scripts/auth_send_asset.masm");
 pub const CLIENT_CONFIG_FILE_NAME: &str = "miden-client.toml";
 pub const BUY_IN_AMOUNT: u64 = 1000;
 pub const SMALL_BUY_IN_AMOUNT: u8 = 1;
@@ -17,22 +18,79 @@ pub const HIGHEST_BET: u8 = SMALL_BLIND_AMOUNT;
 pub const NO_OF_PLAYERS: u8 = 4;
 pub const FLOP_NO_OF_CARDS: u8 = 3;
 pub const FLOP_INDEX: u8 = NO_OF_PLAYERS * 2 + 1;
+// hole cards of an Omaha player, Hold'em uses the first two
+pub const MAX_HOLE_CARDS: usize = 4;
 pub const PLAYER_BET_OFFSET: u8 = 3;
 pub const IS_FOLD_OFFSET: u8 = 10;
 pub const HAND_OFFSET: u8 = 11;
+// [seat state, player id, 0, 0] of every seat
+pub const SEAT_OFFSET: u8 = 12;
+pub const SEAT_EMPTY: u64 = 0;
+pub const SEAT_TAKEN: u64 = 1;
+pub const SEAT_SITTING_OUT: u64 = 2;
+// out of a tournament, the player keeps masking the deck until it ends
+pub const SEAT_ELIMINATED: u64 = 3;
 pub const PLAYER_STATS_SLOTS: u8 = 13;
 pub const FIRST_PLAYER_INDEX: u8 = 64;
 pub const LAST_PLAYER_INDEX: u8 = FIRST_PLAYER_INDEX + (NO_OF_PLAYERS - 1) * PLAYER_STATS_SLOTS;
+// blinds of the hand being played
+pub const SMALL_BLIND_SLOT: u8 = 54;
+pub const BIG_BLIND_SLOT: u8 = 55;
 pub const RAISER_INDEX_SLOT: u8 = 58;
+// faucet whose asset players buy in with, 0 for tables without escrow
+pub const BUY_IN_FAUCET_SLOT: u8 = 59;
 pub const CURRENT_TURN_INDEX_SLOT: u8 = 60;
 pub const HIGHEST_BET_SLOT: u8 = 61;
 pub const CURRENT_PHASE_SLOT: u8 = 62;
+// phase from the pot being paid out until the next hand is dealt, seats may be cashed out
+pub const HAND_OVER_PHASE: u64 = 4;
 pub const CHECK_COUNTER_SLOT: u8 = 63;
 pub const PLAYER_BALANCE_SLOT: u8 = 68;
 pub const PLAYER_HANDS: u8 = 75;
+// [card 3, card 4, 0, 0] of an Omaha hand, next to the hand slot holding the first two
+pub const EXTRA_HOLE_CARDS_OFFSET: u8 = 5;
 pub const POT_VALUE: u8 = 73;
 pub const COMMUNITY_CARDS: [u8;5] = [116, 117, 118, 119, 120];
 pub const FLOP_SLOT: u8 = 116;
+// percentage of each pot that reaches the flop taken as rake, and the most taken from one pot
+pub const RAKE_PERCENT_SLOT: u8 = 121;
+pub const RAKE_CAP_SLOT: u8 = 122;
+// account the collected rake is withdrawn to
+pub const RAKE_OPERATOR_SLOT: u8 = 123;
+// rake collected and not withdrawn yet
+pub const RAKE_SLOT: u8 = 124;
+// stack every tournament seat buys in for, 0 at cash tables
+pub const STARTING_STACK_SLOT: u8 = 125;
+pub const HANDS_PLAYED_SLOT: u8 = 126;
+// [kind, small bet, big bet, raise cap] of the betting structure the table is played with
+pub const BETTING_STRUCTURE_SLOT: u8 = 127;
+pub const NO_LIMIT: u64 = 0;
+pub const POT_LIMIT: u64 = 1;
+pub const FIXED_LIMIT: u64 = 2;
+// [raises, phase, 0, 0] raises made on the street being played
+pub const RAISES_SLOT: u8 = 128;
+// poker variant the table plays, see `GameVariant`
+pub const GAME_VARIANT_SLOT: u8 = 129;
+pub const HOLDEM: u64 = 0;
+pub const OMAHA: u64 = 1;
+pub const OMAHA_HI_LO: u64 = 2;
+// [ante kind, ante, straddle, 0] forced bets posted at the start of every hand
+pub const FORCED_BETS_SLOT: u8 = 130;
+pub const NO_ANTE: u64 = 0;
+pub const PER_PLAYER_ANTE: u64 = 1;
+pub const BIG_BLIND_ANTE: u64 = 2;
+// player index of the seat holding the dealer button, it moves on every hand
+pub const BUTTON_SLOT: u8 = 131;
+// seconds a player has to act before being checked or folded, 0 disables it
+pub const DEFAULT_ACTION_TIMEOUT: u64 = 60;
+pub const DEFAULT_SERVER_PORT: u16 = 12044;
+// seconds spectators lag behind the table
+pub const DEFAULT_SPECTATOR_DELAY: u64 = 30;
+// characters in a chat message
+pub const MAX_CHAT_LENGTH: usize = 280;
+// chat messages a player may send within CHAT_RATE_WINDOW_SECS
+pub const CHAT_RATE_LIMIT: usize = 5;
+pub const CHAT_RATE_WINDOW_SECS: u64 = 10;
 
 // PLAYER ACCOUNT
 pub const SECRET_KEY_SLOT: u8 = 53;
@@ -42,7 +100,11 @@ pub const DEFAULT_MASKING_FACTOR: u8 = 9;
 pub const PLAYER_DATA_SLOT: u8 = 56;
 pub const PHASE_DATA_SLOT: u8 = 57;
 pub const DEFAULT_ACTION_TYPE: u64 = 1;
-pub const PLAYER_CARD1_SLOT: u8 = 100;
-pub const PLAYER_CARD2_SLOT: u8 = 101;
 pub const REQUESTER_SLOT: u8 = 102;
-pub const TEMP_CARD_SLOT: u8 = 103;
\ No newline at end of file
+// cards being unmasked, MAX_HOLE_CARDS of them at most
+pub const TEMP_CARD_SLOT: u8 = 103;
+// hole cards, Hold'em only deals the first two
+pub const PLAYER_CARD1_SLOT: u8 = 107;
+pub const PLAYER_CARD2_SLOT: u8 = 108;
+pub const PLAYER_CARD3_SLOT: u8 = 109;
+pub const PLAYER_CARD4_SLOT: u8 = 110;
\ No newline at end of file
@@ -1,9 +1,13 @@
 use crate::client::AzeClient;
 use miden_client::{
-    client::transactions::transaction_request::TransactionRequest, store::TransactionFilter,
+    client::{rpc::NodeRpcClient, transactions::transaction_request::TransactionRequest},
+    store::TransactionFilter,
 };
 
-pub async fn execute_tx_and_sync(client: &mut AzeClient, tx_request: TransactionRequest) {
+pub async fn execute_tx_and_sync<N: NodeRpcClient>(
+    client: &mut AzeClient<N>,
+    tx_request: TransactionRequest,
+) {
     let _ = match client.sync_state().await {
         Ok(_) => (),
         Err(e) => {
@@ -1,4 +1,5 @@
 
+use crate::hands::GameVariant;
 use aze_types::actions::ActionType;
 use serde::{Deserialize, Serialize};
 
@@ -8,15 +9,152 @@ pub struct Check_Action {
     pub amount: Option<u64>, // Only used for Raise, others will be None
 }
 
-#[derive(Debug, Clone)]
+/// Who holds a seat, mirroring the seat state the game account keeps for every player.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeatState {
+    Empty,
+    #[default]
+    Taken,
+    // keeps the seat and balance but isn't dealt in
+    SittingOut,
+    // out of the tournament, the seat isn't dealt in again
+    Eliminated,
+}
+
+/// How much a player may raise. Pot-limit raises to at most the pot after calling, fixed-limit
+/// raises by the small bet before the turn and the big bet from the turn on, `raise_cap` times a
+/// street at most.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BettingStructure {
+    #[default]
+    NoLimit,
+    PotLimit,
+    FixedLimit {
+        small_bet: u64,
+        big_bet: u64,
+        raise_cap: u64,
+    },
+}
+
+impl BettingStructure {
+    /// Checks a raise of `amount` over the current bet, `pot_after_call` being the pot once the
+    /// raiser called and `raises` the raises already made on the street.
+    pub fn check_raise(
+        &self,
+        amount: u64,
+        pot_after_call: u64,
+        street: u64,
+        raises: u64,
+    ) -> Result<(), String> {
+        match *self {
+            BettingStructure::NoLimit => Ok(()),
+            BettingStructure::PotLimit => {
+                if amount > pot_after_call {
+                    return Err(format!("A pot-limit raise is at most {}", pot_after_call));
+                }
+                Ok(())
+            }
+            BettingStructure::FixedLimit {
+                small_bet,
+                big_bet,
+                raise_cap,
+            } => {
+                // the turn and the river are played with the big bet
+                let bet = if street < 2 { small_bet } else { big_bet };
+                if amount != bet {
+                    return Err(format!("Raises are {} on this street", bet));
+                }
+                if raises >= raise_cap {
+                    return Err(format!("The street is capped at {} raises", raise_cap));
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// The table posts the straddle as a single on-chain bet, which is at most a byte.
+pub fn check_straddle(straddle: bool, big_blind: u64) -> Result<(), String> {
+    if straddle && big_blind * 2 > u8::MAX as u64 {
+        return Err(format!(
+            "A straddle of {} is more than a single bet of {} the game account takes",
+            big_blind * 2,
+            u8::MAX
+        ));
+    }
+    Ok(())
+}
+
+/// Chips the pot starts every hand with before the blinds: an ante from every player dealt in,
+/// or a single ante the big blind posts for the whole table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Ante {
+    #[default]
+    None,
+    PerPlayer(u64),
+    BigBlind(u64),
+}
+
+impl Ante {
+    pub fn amount(&self) -> u64 {
+        match *self {
+            Ante::None => 0,
+            Ante::PerPlayer(amount) | Ante::BigBlind(amount) => amount,
+        }
+    }
+}
+
+/// How a player still in the hand placed at a hi-lo showdown, the lowest rank winning: `high`
+/// among the high hands and `low` among the qualifying lows, None without one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShowdownRank {
+    pub player_id: u64,
+    pub high: u64,
+    pub low: Option<u64>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
 struct Player {
     id: u64,
     balance: u64,
     current_bet: u64,
     has_folded: bool,
+    #[serde(default)]
+    seat: SeatState,
+    // balance when the hand was dealt, which ranks players busting out in the same hand
+    #[serde(default)]
+    hand_stack: u64,
+}
+
+impl Player {
+    fn empty_seat() -> Self {
+        Player {
+            id: 0,
+            balance: 0,
+            current_bet: 0,
+            has_folded: true,
+            seat: SeatState::Empty,
+            hand_stack: 0,
+        }
+    }
+}
+
+/// What the game account's storage says about a game, seat by seat. The chain wins whenever it
+/// disagrees with the off-chain state.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OnChainState {
+    pub current_bet: u64,
+    pub current_player_index: usize,
+    pub balances: Vec<u64>,
+    pub folded: Vec<bool>,
+    // state and player of every seat, left empty for tables seated at creation
+    pub seats: Vec<(SeatState, u64)>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct PokerGame {
     players: Vec<Player>,
     small_blind: u64,
@@ -24,6 +162,43 @@ pub struct PokerGame {
     pot: u64,
     current_bet: u64,
     current_player_index: usize,
+    // seats only change between hands
+    #[serde(default)]
+    hand_started: bool,
+    // the rake is only taken from pots that reached the flop
+    #[serde(default)]
+    flop_seen: bool,
+    #[serde(default)]
+    rake_percent: u64,
+    #[serde(default)]
+    rake_cap: u64,
+    // rake taken from every pot so far
+    #[serde(default)]
+    rake: u64,
+    // tournament players in the order they went out
+    #[serde(default)]
+    eliminated: Vec<u64>,
+    #[serde(default)]
+    betting: BettingStructure,
+    // streets dealt so far in the hand, 0 before the flop
+    #[serde(default)]
+    street: u64,
+    // raises made on the street being played
+    #[serde(default)]
+    raises: u64,
+    #[serde(default)]
+    variant: GameVariant,
+    #[serde(default)]
+    ante: Ante,
+    // the player after the big blind posts twice the big blind before seeing their cards
+    #[serde(default)]
+    straddle: bool,
+    // antes in the pot of the hand being played, they aren't bets anyone has to match
+    #[serde(default)]
+    antes: u64,
+    // seat of the dealer button, the blinds sit to its left
+    #[serde(default)]
+    button: usize,
 }
 
 impl PokerGame {
@@ -33,6 +208,7 @@ impl PokerGame {
         small_blind: u64,
         big_blind: u64,
     ) -> Self {
+        let player_count = player_ids.len();
         let players = player_ids
             .into_iter()
             .zip(initial_balances.into_iter())
@@ -41,9 +217,13 @@ impl PokerGame {
                 balance,
                 current_bet: 0,
                 has_folded: false,
+                seat: SeatState::Taken,
+                hand_stack: balance,
             })
             .collect();
 
+        // the button starts on the last seat, so the first seat posts the small blind
+        let button = player_count.saturating_sub(1);
         PokerGame {
             players,
             small_blind,
@@ -51,10 +231,637 @@ impl PokerGame {
             pot: 0,
             current_bet: 0,
             current_player_index: 0,
+            hand_started: true,
+            flop_seen: false,
+            rake_percent: 0,
+            rake_cap: 0,
+            rake: 0,
+            eliminated: vec![],
+            betting: BettingStructure::NoLimit,
+            street: 0,
+            raises: 0,
+            variant: GameVariant::Holdem,
+            ante: Ante::None,
+            straddle: false,
+            antes: 0,
+            button,
+        }
+    }
+
+    /// A table nobody sits at yet, players join with `join_seat` before the first hand.
+    pub fn with_empty_seats(seat_count: usize, small_blind: u64, big_blind: u64) -> Self {
+        PokerGame {
+            players: vec![Player::empty_seat(); seat_count],
+            small_blind,
+            big_blind,
+            pot: 0,
+            current_bet: 0,
+            current_player_index: 0,
+            hand_started: false,
+            flop_seen: false,
+            rake_percent: 0,
+            rake_cap: 0,
+            rake: 0,
+            eliminated: vec![],
+            betting: BettingStructure::NoLimit,
+            street: 0,
+            raises: 0,
+            variant: GameVariant::Holdem,
+            ante: Ante::None,
+            straddle: false,
+            antes: 0,
+            // the first hand dealt moves the button to the first seat dealt in
+            button: seat_count.saturating_sub(1),
+        }
+    }
+
+    pub fn current_player_id(&self) -> u64 {
+        self.players[self.current_player_index].id
+    }
+
+    pub fn pot(&self) -> u64 {
+        self.pot
+    }
+
+    pub fn current_bet(&self) -> u64 {
+        self.current_bet
+    }
+
+    pub fn small_blind(&self) -> u64 {
+        self.small_blind
+    }
+
+    pub fn big_blind(&self) -> u64 {
+        self.big_blind
+    }
+
+    /// Takes `percent` of every pot that reaches the flop, at most `cap`, the same way the game
+    /// account does when the hand ends.
+    pub fn set_rake(&mut self, percent: u64, cap: u64) {
+        self.rake_percent = percent;
+        self.rake_cap = cap;
+    }
+
+    /// Blinds of the hands dealt from now on, tournaments raise them between hands.
+    pub fn set_blinds(&mut self, small_blind: u64, big_blind: u64) -> Result<(), String> {
+        if self.hand_started {
+            return Err(String::from("Blinds can only change between hands"));
+        }
+        check_straddle(self.straddle, big_blind)?;
+        self.small_blind = small_blind;
+        self.big_blind = big_blind;
+        Ok(())
+    }
+
+    /// Limits raises the same way the game account does.
+    pub fn set_betting_structure(&mut self, betting: BettingStructure) {
+        self.betting = betting;
+    }
+
+    pub fn betting_structure(&self) -> BettingStructure {
+        self.betting
+    }
+
+    /// Variant the game account deals, which decides how many hole cards players get.
+    pub fn set_variant(&mut self, variant: GameVariant) {
+        self.variant = variant;
+    }
+
+    pub fn variant(&self) -> GameVariant {
+        self.variant
+    }
+
+    /// Antes and straddle of the hands dealt from now on, the game account posts them as well.
+    pub fn set_forced_bets(&mut self, ante: Ante, straddle: bool) -> Result<(), String> {
+        check_straddle(straddle, self.big_blind)?;
+        self.ante = ante;
+        self.straddle = straddle;
+        Ok(())
+    }
+
+    pub fn ante(&self) -> Ante {
+        self.ante
+    }
+
+    pub fn straddle(&self) -> bool {
+        self.straddle
+    }
+
+    pub fn straddle_amount(&self) -> u64 {
+        self.big_blind * 2
+    }
+
+    // antes in the pot of the hand being played
+    pub fn antes(&self) -> u64 {
+        self.antes
+    }
+
+    pub fn rake(&self) -> u64 {
+        self.rake
+    }
+
+    // rake the pot gives up, the winners share the rest
+    fn rake_of(&self, pot: u64) -> u64 {
+        if !self.flop_seen {
+            return 0;
+        }
+        (pot * self.rake_percent / 100).min(self.rake_cap)
+    }
+
+    // every chip at the table, in front of the players or in the pot
+    pub fn chips(&self) -> u64 {
+        self.players.iter().map(|player| player.balance).sum::<u64>() + self.pot
+    }
+
+    pub fn balance(&self, player_id: u64) -> Option<u64> {
+        self.player(player_id).map(|player| player.balance)
+    }
+
+    // amount the player has to put in to match the current bet
+    pub fn amount_to_call(&self, player_id: u64) -> Option<u64> {
+        self.player(player_id)
+            .map(|player| self.current_bet.saturating_sub(player.current_bet))
+    }
+
+    // raise bots make: the street's bet at fixed-limit tables, a big blind otherwise
+    pub fn standard_raise(&self) -> u64 {
+        match self.betting {
+            BettingStructure::FixedLimit { small_bet, big_bet, .. } => {
+                if self.street < 2 {
+                    small_bet
+                } else {
+                    big_bet
+                }
+            }
+            _ => self.big_blind,
+        }
+    }
+
+    // the current player may raise by `amount` under the table's betting structure
+    pub fn can_raise(&self, amount: u64) -> bool {
+        let player = &self.players[self.current_player_index];
+        let pot_after_call = self.pot + self.current_bet - player.current_bet;
+        self.betting
+            .check_raise(amount, pot_after_call, self.street, self.raises)
+            .is_ok()
+    }
+
+    // blind the current player still has to post, if any
+    pub fn pending_blind(&self) -> Option<ActionType> {
+        let seat = Some(self.current_player_index);
+        // a short stack posts what they have left of a blind
+        let posted = |blind: usize| {
+            self.blind_seat(blind).map_or(0, |seat| self.players[seat].current_bet)
+        };
+        if self.pot == self.antes && seat == self.blind_seat(0) {
+            Some(ActionType::SmallBlind)
+        } else if self.pot == self.antes + posted(0) && seat == self.blind_seat(1) {
+            Some(ActionType::BigBlind)
+        } else if self.straddle
+            && self.pot == self.antes + posted(0) + posted(1)
+            && self.current_bet == self.big_blind
+            && seat == self.blind_seat(2)
+        {
+            Some(ActionType::Straddle)
+        } else {
+            None
+        }
+    }
+
+    // the small blind is posted by the first seat dealt in after the button, the big blind by the
+    // second and the straddle by the third
+    fn blind_seat(&self, blind: usize) -> Option<usize> {
+        self.dealt_in_after(self.button, blind)
+    }
+
+    // the `nth` seat dealt in after `seat`, going round the table
+    fn dealt_in_after(&self, seat: usize, nth: usize) -> Option<usize> {
+        let seats = self.players.len();
+        (1..=seats)
+            .map(|offset| (seat + offset) % seats)
+            .filter(|seat| {
+                let player = &self.players[*seat];
+                player.seat == SeatState::Taken && !player.has_folded
+            })
+            .nth(nth)
+    }
+
+    // move played for the current player when they run out of time: a check if it's legal,
+    // a fold otherwise
+    pub fn timeout_action(&self) -> Check_Action {
+        let player = &self.players[self.current_player_index];
+        let action_type = if player.current_bet >= self.current_bet || player.balance == 0 {
+            ActionType::Check
+        } else {
+            ActionType::Fold
+        };
+        Check_Action {
+            action_type,
+            amount: None,
         }
     }
 
+    // players holding a seat, in seat order
+    pub fn player_ids(&self) -> Vec<u64> {
+        self.players
+            .iter()
+            .filter(|player| player.seat != SeatState::Empty)
+            .map(|player| player.id)
+            .collect()
+    }
+
+    pub fn seat_count(&self) -> usize {
+        self.players.len()
+    }
+
+    // seat the player holds, if any
+    pub fn seat_of(&self, player_id: u64) -> Option<usize> {
+        self.players
+            .iter()
+            .position(|player| player.seat != SeatState::Empty && player.id == player_id)
+    }
+
+    // player holding the seat, if it isn't empty
+    pub fn seated_player(&self, seat: usize) -> Option<u64> {
+        self.players
+            .get(seat)
+            .filter(|player| player.seat != SeatState::Empty)
+            .map(|player| player.id)
+    }
+
+    pub fn seat_state(&self, seat: usize) -> Option<SeatState> {
+        self.players.get(seat).map(|player| player.seat)
+    }
+
+    pub fn is_between_hands(&self) -> bool {
+        !self.hand_started
+    }
+
+    /// Seats the player with the buy-in as balance. They are dealt in from the next hand on.
+    pub fn join_seat(&mut self, player_id: u64, seat: usize, buy_in: u64) -> Result<(), String> {
+        if self.hand_started {
+            return Err(String::from("Seats can only be taken between hands"));
+        }
+        if self.seat_of(player_id).is_some() {
+            return Err(format!("Player {} already has a seat", player_id));
+        }
+        match self.players.get_mut(seat) {
+            Some(player) if player.seat == SeatState::Empty => {
+                *player = Player {
+                    id: player_id,
+                    balance: buy_in,
+                    current_bet: 0,
+                    has_folded: true,
+                    seat: SeatState::Taken,
+                    hand_stack: buy_in,
+                };
+                Ok(())
+            }
+            Some(_) => Err(format!("Seat {} is taken", seat)),
+            None => Err(format!("There is no seat {}", seat)),
+        }
+    }
+
+    /// Frees the player's seat and returns the balance they leave with.
+    pub fn leave_seat(&mut self, player_id: u64) -> Result<u64, String> {
+        if self.hand_started {
+            return Err(String::from("Players can only leave between hands"));
+        }
+        let seat = self
+            .seat_of(player_id)
+            .ok_or_else(|| format!("Player {} has no seat", player_id))?;
+        let balance = self.players[seat].balance;
+        self.players[seat] = Player::empty_seat();
+        Ok(balance)
+    }
+
+    /// Keeps the seat and balance but leaves the player out of the next hands, or deals them
+    /// in again.
+    pub fn sit_out(&mut self, player_id: u64, sitting_out: bool) -> Result<(), String> {
+        if self.hand_started {
+            return Err(String::from("Players can only sit out between hands"));
+        }
+        let seat = self
+            .seat_of(player_id)
+            .ok_or_else(|| format!("Player {} has no seat", player_id))?;
+        if self.players[seat].seat == SeatState::Eliminated {
+            return Err(format!("Player {} is out of the tournament", player_id));
+        }
+        self.players[seat].seat = if sitting_out {
+            SeatState::SittingOut
+        } else {
+            SeatState::Taken
+        };
+        Ok(())
+    }
+
+    /// Deals in every taken seat with chips left, the small blind acts first. Fails when fewer
+    /// than two players can play.
+    pub fn start_hand(&mut self) -> Result<(), String> {
+        if self.hand_started {
+            return Err(String::from("A hand is already being played"));
+        }
+        for player in self.players.iter_mut() {
+            player.current_bet = 0;
+            player.has_folded = player.seat != SeatState::Taken || player.balance == 0;
+            player.hand_stack = player.balance;
+        }
+        if self.active_player_ids().len() < 2 {
+            return Err(String::from("At least two players are needed to deal a hand"));
+        }
+        self.pot = 0;
+        self.current_bet = 0;
+        // the button moves on to the next seat dealt in, the same way the game account moves it
+        self.button = self.dealt_in_after(self.button, 0).unwrap_or(self.button);
+        self.current_player_index = self.blind_seat(0).unwrap_or(0);
+        self.hand_started = true;
+        self.flop_seen = false;
+        self.street = 0;
+        self.raises = 0;
+        self.post_antes()
+    }
+
+    /// Puts the antes in the pot before the blinds are posted, a player short of the ante puts
+    /// in what they have left.
+    pub fn post_antes(&mut self) -> Result<(), String> {
+        if self.pot != 0 {
+            return Err(String::from("Antes are posted before the blinds"));
+        }
+        let seats: Vec<usize> = match self.ante {
+            Ante::None => vec![],
+            Ante::PerPlayer(_) => (0..self.players.len())
+                .filter(|seat| !self.players[*seat].has_folded)
+                .collect(),
+            Ante::BigBlind(_) => self.blind_seat(1).into_iter().collect(),
+        };
+        for seat in seats {
+            let player = &mut self.players[seat];
+            let amount = self.ante.amount().min(player.balance);
+            player.balance -= amount;
+            self.pot += amount;
+        }
+        self.antes = self.pot;
+        Ok(())
+    }
+
+    // players still in the hand
+    pub fn active_player_ids(&self) -> Vec<u64> {
+        self.players
+            .iter()
+            .filter(|player| !player.has_folded)
+            .map(|player| player.id)
+            .collect()
+    }
+
+    // every player still in the hand matched the current bet, or can't put in more
+    pub fn is_betting_settled(&self) -> bool {
+        self.players
+            .iter()
+            .filter(|player| !player.has_folded)
+            .all(|player| player.current_bet == self.current_bet || player.balance == 0)
+    }
+
+    // clears the bets of the finished betting round, the first player still in the hand acts next
+    pub fn next_street(&mut self) {
+        for player in self.players.iter_mut() {
+            player.current_bet = 0;
+        }
+        self.current_bet = 0;
+        self.current_player_index = self
+            .players
+            .iter()
+            .position(|player| !player.has_folded)
+            .unwrap_or(0);
+        self.flop_seen = true;
+        self.street += 1;
+        self.raises = 0;
+    }
+
+    // takes the rake and splits the rest of the pot evenly between the winners, the first winner
+    // gets what can't be split. A winner all-in for less only wins the pots they are in, the
+    // side pots above them are split by the other players still in the hand. Returns what each
+    // player was paid
+    pub fn award_pot(&mut self, winner_ids: &[u64]) -> Vec<(u64, u64)> {
+        if winner_ids.is_empty() {
+            return vec![];
+        }
+        let losers = self
+            .players
+            .iter()
+            .filter(|player| !player.has_folded && !winner_ids.contains(&player.id))
+            .map(|player| (player.id, 1));
+        let ranks: Vec<ShowdownRank> = winner_ids
+            .iter()
+            .map(|winner_id| (*winner_id, 0))
+            .chain(losers)
+            .map(|(player_id, high)| ShowdownRank {
+                player_id,
+                high,
+                low: None,
+            })
+            .collect();
+        self.award_split_pots(&ranks)
+    }
+
+    /// Pays a hand out by how the players placed: the pot is split into a main pot and a side
+    /// pot for every all-in level, each going to its best high hand, or in hi-lo half to its
+    /// best high hand and half to its best low when a low qualifies. Tied halves are split again, so a shared low is
+    /// quartered. The high half gets the odd chip, and the rake comes off the main pot first.
+    pub fn award_split_pots(&mut self, ranks: &[ShowdownRank]) -> Vec<(u64, u64)> {
+        if ranks.is_empty() {
+            return vec![];
+        }
+        let rake = self.rake_of(self.pot);
+        self.rake += rake;
+        let contributed = |player: &Player| player.hand_stack.saturating_sub(player.balance);
+        let contenders: Vec<(ShowdownRank, u64)> = ranks
+            .iter()
+            .filter_map(|rank| {
+                let player = self.player(rank.player_id)?;
+                Some((*rank, contributed(player)))
+            })
+            .collect();
+        let mut levels: Vec<u64> = contenders.iter().map(|(_, amount)| *amount).collect();
+        levels.sort();
+        levels.dedup();
+
+        let mut paid: Vec<(u64, u64)> = vec![];
+        let mut pay = |player_id: u64, amount: u64| {
+            match paid.iter_mut().find(|(paid_id, _)| *paid_id == player_id) {
+                Some((_, total)) => *total += amount,
+                None => paid.push((player_id, amount)),
+            }
+        };
+        let (mut allocated, mut rake_left, mut previous) = (0, rake, 0);
+        for (i, level) in levels.iter().enumerate() {
+            // the last pot also takes what folded players put in above every all-in
+            let mut amount = if i + 1 == levels.len() {
+                self.pot - allocated
+            } else {
+                self.players
+                    .iter()
+                    .map(|player| contributed(player).min(*level) - contributed(player).min(previous))
+                    .sum()
+            };
+            allocated += amount;
+            previous = *level;
+            let raked = amount.min(rake_left);
+            amount -= raked;
+            rake_left -= raked;
+
+            let eligible: Vec<ShowdownRank> = contenders
+                .iter()
+                .filter(|(_, amount_in)| amount_in >= level)
+                .map(|(rank, _)| *rank)
+                .collect();
+            let best_high = eligible.iter().map(|rank| rank.high).min();
+            let best_low = eligible.iter().filter_map(|rank| rank.low).min();
+            let high_winners: Vec<u64> = eligible
+                .iter()
+                .filter(|rank| Some(rank.high) == best_high)
+                .map(|rank| rank.player_id)
+                .collect();
+            let low_winners: Vec<u64> = eligible
+                .iter()
+                .filter(|rank| best_low.is_some() && rank.low == best_low)
+                .map(|rank| rank.player_id)
+                .collect();
+            let low_half = if low_winners.is_empty() { 0 } else { amount / 2 };
+            for (winners, half) in [(high_winners, amount - low_half), (low_winners, low_half)] {
+                if winners.is_empty() {
+                    continue;
+                }
+                let share = half / winners.len() as u64;
+                let remainder = half % winners.len() as u64;
+                for (j, winner_id) in winners.iter().enumerate() {
+                    pay(*winner_id, share + if j == 0 { remainder } else { 0 });
+                }
+            }
+        }
+        for (winner_id, amount) in paid.iter() {
+            if let Some(player) = self.players.iter_mut().find(|player| player.id == *winner_id) {
+                player.balance += amount;
+            }
+        }
+        self.pot = 0;
+        self.hand_started = false;
+        paid
+    }
+
+    /// Takes the seats of tournament players left without chips once the hand is paid out and
+    /// returns them with the place they finished in. Of the players busting out in the same
+    /// hand, the one who started it with more chips places higher.
+    pub fn eliminate_busted(&mut self) -> Vec<(u64, usize)> {
+        if self.hand_started {
+            return vec![];
+        }
+        let mut busted: Vec<usize> = (0..self.players.len())
+            .filter(|seat| {
+                let player = &self.players[*seat];
+                player.balance == 0
+                    && matches!(player.seat, SeatState::Taken | SeatState::SittingOut)
+            })
+            .collect();
+        busted.sort_by_key(|seat| std::cmp::Reverse(self.players[*seat].hand_stack));
+        for seat in busted.iter() {
+            self.players[*seat].seat = SeatState::Eliminated;
+            self.players[*seat].has_folded = true;
+        }
+        let players_left = self.players_left();
+        let places: Vec<(u64, usize)> = busted
+            .iter()
+            .enumerate()
+            .map(|(i, seat)| (self.players[*seat].id, players_left + i + 1))
+            .collect();
+        self.eliminated
+            .extend(places.iter().rev().map(|(player_id, _)| *player_id));
+        places
+    }
+
+    // players still in the tournament, sitting out or not
+    pub fn players_left(&self) -> usize {
+        self.players
+            .iter()
+            .filter(|player| matches!(player.seat, SeatState::Taken | SeatState::SittingOut))
+            .count()
+    }
+
+    /// Players still in from the biggest stack down, then those eliminated from the last one out.
+    pub fn placements(&self) -> Vec<u64> {
+        let mut players_left: Vec<&Player> = self
+            .players
+            .iter()
+            .filter(|player| matches!(player.seat, SeatState::Taken | SeatState::SittingOut))
+            .collect();
+        players_left.sort_by_key(|player| std::cmp::Reverse(player.balance));
+        players_left
+            .into_iter()
+            .map(|player| player.id)
+            .chain(self.eliminated.iter().rev().copied())
+            .collect()
+    }
+
+    // the last player standing once everybody else was eliminated
+    pub fn tournament_winner(&self) -> Option<u64> {
+        if self.eliminated.is_empty() || self.players_left() != 1 {
+            return None;
+        }
+        self.placements().first().copied()
+    }
+
+    // takes over whatever the chain disagrees with and describes what changed
+    pub fn reconcile(&mut self, chain: &OnChainState) -> Vec<String> {
+        let mut changes = vec![];
+        if self.current_bet != chain.current_bet {
+            changes.push(format!(
+                "current bet {} -> {}",
+                self.current_bet, chain.current_bet
+            ));
+            self.current_bet = chain.current_bet;
+        }
+        if chain.current_player_index < self.players.len()
+            && self.current_player_index != chain.current_player_index
+        {
+            changes.push(format!(
+                "current seat {} -> {}",
+                self.current_player_index, chain.current_player_index
+            ));
+            self.current_player_index = chain.current_player_index;
+        }
+        for (seat, player) in self.players.iter_mut().enumerate() {
+            if let Some((seat_state, player_id)) = chain.seats.get(seat) {
+                if player.seat != *seat_state || player.id != *player_id {
+                    changes.push(format!(
+                        "seat {} {:?} {} -> {:?} {}",
+                        seat, player.seat, player.id, seat_state, player_id
+                    ));
+                    player.seat = *seat_state;
+                    player.id = *player_id;
+                }
+            }
+            if let Some(balance) = chain.balances.get(seat) {
+                if player.balance != *balance {
+                    changes.push(format!("seat {} balance {} -> {}", seat, player.balance, balance));
+                    player.balance = *balance;
+                }
+            }
+            if let Some(folded) = chain.folded.get(seat) {
+                if player.has_folded != *folded {
+                    changes.push(format!("seat {} folded {} -> {}", seat, player.has_folded, folded));
+                    player.has_folded = *folded;
+                }
+            }
+        }
+        changes
+    }
+
+    fn player(&self, player_id: u64) -> Option<&Player> {
+        self.players.iter().find(|player| player.id == player_id)
+    }
+
     pub fn check_move(&mut self, check_action: Check_Action, player_id: u64) -> bool {
+        let (small_blind_seat, big_blind_seat) = (self.blind_seat(0), self.blind_seat(1));
+        let pending_blind = self.pending_blind();
         let player = &mut self.players[self.current_player_index];
         if player.id != player_id {
             eprintln!("Not your turn");
@@ -64,23 +871,27 @@ impl PokerGame {
             eprintln!("Player has already folded");
             return false;
         }
+        if pending_blind == Some(ActionType::Straddle)
+            && check_action.action_type != ActionType::Straddle
+        {
+            eprintln!("The straddle is posted before the player acts");
+            return false;
+        }
 
         match check_action.action_type {
             ActionType::Fold => {
                 player.has_folded = true;
             }
             ActionType::Check => {
-                if player.current_bet < self.current_bet {
+                // a player who is all-in has nothing left to call with
+                if player.current_bet < self.current_bet && player.balance > 0 {
                     eprintln!("Cannot check, must call or raise");
                     return false;
                 }
             }
             ActionType::Call => {
-                let call_amount = self.current_bet - player.current_bet;
-                if player.balance < call_amount {
-                    eprintln!("Not enough balance to call");
-                    return false;
-                }
+                // a player short of the call goes all-in for what they have left
+                let call_amount = (self.current_bet - player.current_bet).min(player.balance);
                 player.balance -= call_amount;
                 player.current_bet += call_amount;
                 self.pot += call_amount;
@@ -88,13 +899,24 @@ impl PokerGame {
             ActionType::Raise => {
                 if let Some(amount) = check_action.amount {
                     let total_bet = self.current_bet + amount;
-                    if player.balance < total_bet {
+                    // what the player already put in this street counts towards the raise
+                    let raise_amount = total_bet - player.current_bet;
+                    if player.balance < raise_amount {
                         eprintln!("Not enough balance to raise");
                         return false;
                     }
-                    player.balance -= total_bet - player.current_bet;
+                    let pot_after_call = self.pot + self.current_bet - player.current_bet;
+                    if let Err(e) =
+                        self.betting
+                            .check_raise(amount, pot_after_call, self.street, self.raises)
+                    {
+                        eprintln!("{}", e);
+                        return false;
+                    }
+                    self.raises += 1;
+                    player.balance -= raise_amount;
                     player.current_bet = total_bet;
-                    self.pot += total_bet - self.current_bet;
+                    self.pot += raise_amount;
                     self.current_bet = total_bet;
                 } else {
                     eprintln!("Raise amount not specified");
@@ -102,34 +924,39 @@ impl PokerGame {
                 }
             }
             ActionType::SmallBlind => {
-                if self.current_player_index != 0 {
+                if Some(self.current_player_index) != small_blind_seat {
                     eprintln!("Only P1 can post the small blind");
                     return false;
                 }
-                let small_blind_amount = self.small_blind;
-                if player.balance < small_blind_amount {
-                    eprintln!("Not enough balance to post the small blind");
-                    return false;
-                }
+                // all-in for less when short, the others still have to match the whole blind
+                let small_blind_amount = self.small_blind.min(player.balance);
                 player.balance -= small_blind_amount;
                 player.current_bet = small_blind_amount;
                 self.pot += small_blind_amount;
-                self.current_bet = small_blind_amount;
+                self.current_bet = self.small_blind;
             }
             ActionType::BigBlind => {
-                if self.current_player_index != 1 {
+                if Some(self.current_player_index) != big_blind_seat {
                     eprintln!("Only P2 can post the big blind");
                     return false;
                 }
-                let big_blind_amount = self.big_blind;
-                if player.balance < big_blind_amount {
-                    eprintln!("Not enough balance to post the big blind");
-                    return false;
-                }
+                // all-in for less when short, the others still have to match the whole blind
+                let big_blind_amount = self.big_blind.min(player.balance);
                 player.balance -= big_blind_amount;
                 player.current_bet = big_blind_amount;
                 self.pot += big_blind_amount;
-                self.current_bet = big_blind_amount;
+                self.current_bet = self.big_blind;
+            }
+            ActionType::Straddle => {
+                if pending_blind != Some(ActionType::Straddle) {
+                    eprintln!("Only the player after the big blind straddles, once the blinds are in");
+                    return false;
+                }
+                let straddle_amount = (self.big_blind * 2).min(player.balance);
+                player.balance -= straddle_amount;
+                player.current_bet = straddle_amount;
+                self.pot += straddle_amount;
+                self.current_bet = self.big_blind * 2;
             }
         }
 
@@ -196,11 +1023,54 @@ mod tests {
         game.check_move(Check_Action { action_type: ActionType::Call, amount: None }, 3);
         assert!(game.check_move(Check_Action { action_type: ActionType::Raise, amount: Some(30) }, 4));
         assert_eq!(game.players[3].balance, 950);
-        assert_eq!(game.pot, 80);
+        assert_eq!(game.pot, 100);
         assert_eq!(game.current_bet, 50);
         assert_eq!(game.current_player_index, 0);
     }
 
+    #[test]
+    fn test_reraise_keeps_chips() {
+        let mut game = PokerGame::new(vec![1, 2, 3, 4], vec![1000, 1000, 1000, 1000], 10, 20);
+
+        game.check_move(Check_Action { action_type: ActionType::SmallBlind, amount: None }, 1);
+        game.check_move(Check_Action { action_type: ActionType::BigBlind, amount: None }, 2);
+        assert!(game.check_move(Check_Action { action_type: ActionType::Raise, amount: Some(40) }, 3));
+        assert!(game.check_move(Check_Action { action_type: ActionType::Raise, amount: Some(60) }, 4));
+        // the small blind already has 10 in and pays 210 to raise to 220
+        assert!(game.check_move(Check_Action { action_type: ActionType::Raise, amount: Some(100) }, 1));
+        assert_eq!(game.balance(1), Some(780));
+        assert!(game.check_move(Check_Action { action_type: ActionType::Call, amount: None }, 2));
+        assert_eq!(game.balance(2), Some(780));
+        assert_eq!(game.pot(), 620);
+        assert_eq!(game.chips(), 4000);
+    }
+
+    #[test]
+    fn test_all_in_for_less_than_the_blind() {
+        let mut game = PokerGame::new(vec![1, 2, 3], vec![1000, 15, 30], 10, 20);
+
+        assert!(game.check_move(Check_Action { action_type: ActionType::SmallBlind, amount: None }, 1));
+        // the big blind only has 15, the others still have to call 20
+        assert!(game.check_move(Check_Action { action_type: ActionType::BigBlind, amount: None }, 2));
+        assert_eq!(game.balance(2), Some(0));
+        assert_eq!(game.current_bet(), 20);
+        assert!(game.check_move(Check_Action { action_type: ActionType::Call, amount: None }, 3));
+        assert!(game.check_move(Check_Action { action_type: ActionType::Raise, amount: Some(20) }, 1));
+        assert_eq!(game.timeout_action().action_type, ActionType::Check);
+        assert!(game.check_move(Check_Action { action_type: ActionType::Check, amount: None }, 2));
+        // 10 short of the raise, the player calls all-in
+        assert!(game.check_move(Check_Action { action_type: ActionType::Call, amount: None }, 3));
+        assert_eq!(game.balance(3), Some(0));
+        assert!(game.is_betting_settled());
+        assert_eq!(game.pot(), 85);
+
+        // 15 from each player is the main pot the big blind wins, the others tie for the side
+        // pot of 30 and the 10 nobody matched goes back to who bet them
+        assert_eq!(game.award_pot(&[2]), vec![(2, 45), (1, 25), (3, 15)]);
+        assert_eq!(game.balance(1), Some(985));
+        assert_eq!(game.chips(), 1045);
+    }
+
     #[test]
     fn test_fold_action() {
         let player_ids = vec![1, 2, 3, 4];
@@ -226,4 +1096,351 @@ mod tests {
         assert!(game.check_move(Check_Action { action_type: ActionType::BigBlind, amount: None }, 2));
         assert!(!game.check_move(Check_Action { action_type: ActionType::Check, amount: None }, 3)); // Cannot check, must call or raise
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_game_view() {
+        let player_ids = vec![1, 2, 3, 4];
+        let initial_balances = vec![1000, 1000, 1000, 1000];
+        let mut game = PokerGame::new(player_ids, initial_balances, 10, 20);
+
+        assert_eq!(game.pending_blind(), Some(ActionType::SmallBlind));
+        game.check_move(Check_Action { action_type: ActionType::SmallBlind, amount: None }, 1);
+        assert_eq!(game.pending_blind(), Some(ActionType::BigBlind));
+        game.check_move(Check_Action { action_type: ActionType::BigBlind, amount: None }, 2);
+        assert_eq!(game.pending_blind(), None);
+
+        assert_eq!(game.current_player_id(), 3);
+        assert_eq!(game.amount_to_call(3), Some(20));
+        assert_eq!(game.amount_to_call(1), Some(10));
+        assert_eq!(game.amount_to_call(5), None);
+        assert_eq!(game.balance(2), Some(980));
+        assert_eq!(game.pot(), 30);
+    }
+
+    #[test]
+    fn test_street_and_pot() {
+        let player_ids = vec![1, 2, 3, 4];
+        let initial_balances = vec![1000, 1000, 1000, 1000];
+        let mut game = PokerGame::new(player_ids, initial_balances, 10, 20);
+
+        game.check_move(Check_Action { action_type: ActionType::SmallBlind, amount: None }, 1);
+        game.check_move(Check_Action { action_type: ActionType::BigBlind, amount: None }, 2);
+        assert!(!game.is_betting_settled());
+        game.check_move(Check_Action { action_type: ActionType::Call, amount: None }, 3);
+        game.check_move(Check_Action { action_type: ActionType::Fold, amount: None }, 4);
+        game.check_move(Check_Action { action_type: ActionType::Fold, amount: None }, 1);
+        assert!(game.is_betting_settled());
+        assert_eq!(game.active_player_ids(), vec![2, 3]);
+
+        game.next_street();
+        assert_eq!(game.current_player_id(), 2);
+        assert_eq!(game.amount_to_call(3), Some(0));
+
+        assert_eq!(game.award_pot(&[2, 3]), vec![(2, 25), (3, 25)]);
+        assert_eq!(game.pot(), 0);
+        assert_eq!(game.balance(2), Some(1005));
+        assert_eq!(game.balance(3), Some(1005));
+    }
+
+    #[test]
+    fn test_timeout_action() {
+        let player_ids = vec![1, 2, 3, 4];
+        let initial_balances = vec![1000, 1000, 1000, 1000];
+        let mut game = PokerGame::new(player_ids, initial_balances, 10, 20);
+
+        assert_eq!(game.timeout_action().action_type, ActionType::Check);
+        game.check_move(Check_Action { action_type: ActionType::SmallBlind, amount: None }, 1);
+        game.check_move(Check_Action { action_type: ActionType::BigBlind, amount: None }, 2);
+        assert_eq!(game.timeout_action().action_type, ActionType::Fold);
+        assert!(game.check_move(game.timeout_action(), 3));
+
+        game.check_move(Check_Action { action_type: ActionType::Call, amount: None }, 4);
+        game.check_move(Check_Action { action_type: ActionType::Call, amount: None }, 1);
+        // the big blind already matched the bet
+        assert_eq!(game.timeout_action().action_type, ActionType::Check);
+        assert!(game.check_move(game.timeout_action(), 2));
+    }
+
+    #[test]
+    fn test_reconcile_with_chain() {
+        let mut game = PokerGame::new(vec![1, 2, 3, 4], vec![1000, 1000, 1000, 1000], 10, 20);
+        assert!(game.check_move(Check_Action { action_type: ActionType::SmallBlind, amount: None }, 1));
+
+        // the big blind and a fold went through on-chain while the server was down
+        let chain = OnChainState {
+            current_bet: 20,
+            current_player_index: 3,
+            balances: vec![990, 980, 1000, 1000],
+            folded: vec![false, false, true, false],
+            seats: vec![],
+        };
+        assert_eq!(game.reconcile(&chain).len(), 4);
+        assert_eq!(game.current_bet(), 20);
+        assert_eq!(game.current_player_id(), 4);
+        assert_eq!(game.balance(2), Some(980));
+        assert_eq!(game.active_player_ids(), vec![1, 2, 4]);
+        // nothing left to take over
+        assert!(game.reconcile(&chain).is_empty());
+    }
+
+    #[test]
+    fn test_join_and_leave_seats() {
+        let mut game = PokerGame::with_empty_seats(4, 10, 20);
+        assert!(game.player_ids().is_empty());
+        assert!(game.start_hand().is_err());
+
+        assert!(game.join_seat(7, 2, 500).is_ok());
+        assert!(game.join_seat(8, 2, 500).is_err());
+        assert!(game.join_seat(7, 0, 500).is_err());
+        assert!(game.join_seat(9, 4, 500).is_err());
+        assert!(game.join_seat(9, 0, 300).is_ok());
+        assert_eq!(game.player_ids(), vec![9, 7]);
+        assert_eq!(game.seat_of(7), Some(2));
+        assert_eq!(game.seated_player(2), Some(7));
+        assert_eq!(game.seated_player(1), None);
+        assert_eq!(game.chips(), 800);
+        assert_eq!(game.seat_state(1), Some(SeatState::Empty));
+
+        // the button moves to the first seat dealt in, the next one posts the small blind
+        assert!(game.start_hand().is_ok());
+        assert!(!game.is_between_hands());
+        assert_eq!(game.current_player_id(), 7);
+        assert_eq!(game.pending_blind(), Some(ActionType::SmallBlind));
+        assert!(game.leave_seat(7).is_err());
+        assert!(game.join_seat(10, 1, 500).is_err());
+        assert!(game.check_move(Check_Action { action_type: ActionType::SmallBlind, amount: None }, 7));
+        assert_eq!(game.current_player_id(), 9);
+        assert_eq!(game.pending_blind(), Some(ActionType::BigBlind));
+        assert!(game.check_move(Check_Action { action_type: ActionType::BigBlind, amount: None }, 9));
+        assert!(game.check_move(Check_Action { action_type: ActionType::Fold, amount: None }, 7));
+        assert_eq!(game.chips(), 800);
+        game.award_pot(&[9]);
+        assert!(game.is_between_hands());
+
+        // the balance leaves with the player and the seat opens up again
+        assert_eq!(game.leave_seat(7), Ok(490));
+        assert_eq!(game.seat_state(2), Some(SeatState::Empty));
+        assert!(game.leave_seat(7).is_err());
+        assert!(game.join_seat(10, 2, 500).is_ok());
+    }
+
+    #[test]
+    fn test_sitting_out() {
+        let mut game = PokerGame::with_empty_seats(4, 10, 20);
+        for (seat, player_id) in [1, 2, 3].into_iter().enumerate() {
+            game.join_seat(player_id, seat, 500).unwrap();
+        }
+        assert!(game.sit_out(1, true).is_ok());
+        assert!(game.sit_out(4, true).is_err());
+        game.start_hand().unwrap();
+        assert_eq!(game.active_player_ids(), vec![2, 3]);
+        // player 2 has the button
+        assert_eq!(game.current_player_id(), 3);
+        assert_eq!(game.player_ids(), vec![1, 2, 3]);
+        assert!(game.sit_out(1, false).is_err());
+    }
+
+    #[test]
+    fn test_rake() {
+        let mut game = PokerGame::with_empty_seats(4, 10, 20);
+        game.set_rake(5, 3);
+        for (seat, player_id) in [1, 2].into_iter().enumerate() {
+            game.join_seat(player_id, seat, 500).unwrap();
+        }
+
+        // a pot that ends before the flop isn't raked
+        game.start_hand().unwrap();
+        game.check_move(Check_Action { action_type: ActionType::SmallBlind, amount: None }, 2);
+        game.check_move(Check_Action { action_type: ActionType::BigBlind, amount: None }, 1);
+        assert_eq!(game.award_pot(&[1]), vec![(1, 30)]);
+        assert_eq!(game.rake(), 0);
+
+        // 5% of 40 is 2, below the cap
+        game.start_hand().unwrap();
+        game.check_move(Check_Action { action_type: ActionType::SmallBlind, amount: None }, 1);
+        game.check_move(Check_Action { action_type: ActionType::BigBlind, amount: None }, 2);
+        game.check_move(Check_Action { action_type: ActionType::Call, amount: None }, 1);
+        game.next_street();
+        assert_eq!(game.award_pot(&[1]), vec![(1, 38)]);
+        assert_eq!(game.rake(), 2);
+        assert_eq!(game.chips(), 998);
+
+        // 5% of 240 is 12, capped at 3
+        game.start_hand().unwrap();
+        game.check_move(Check_Action { action_type: ActionType::SmallBlind, amount: None }, 2);
+        game.check_move(Check_Action { action_type: ActionType::BigBlind, amount: None }, 1);
+        game.check_move(Check_Action { action_type: ActionType::Raise, amount: Some(100) }, 2);
+        game.check_move(Check_Action { action_type: ActionType::Call, amount: None }, 1);
+        game.next_street();
+        assert_eq!(game.pot(), 240);
+        assert_eq!(game.award_pot(&[1, 2]), vec![(1, 119), (2, 118)]);
+        assert_eq!(game.rake(), 5);
+    }
+
+    #[test]
+    fn test_split_pots() {
+        let call = Check_Action { action_type: ActionType::Call, amount: None };
+        let rank = |player_id, high, low| ShowdownRank { player_id, high, low };
+        let mut game = PokerGame::new(vec![1, 2, 3, 4], vec![1000; 4], 10, 20);
+        game.check_move(Check_Action { action_type: ActionType::SmallBlind, amount: None }, 1);
+        game.check_move(Check_Action { action_type: ActionType::BigBlind, amount: None }, 2);
+        game.check_move(call, 3);
+        game.check_move(call, 4);
+        game.check_move(call, 1);
+        assert_eq!(game.pot(), 80);
+
+        // half to the best high hand, the shared low half is quartered
+        let ranks = [rank(1, 1, Some(0)), rank(2, 2, Some(0)), rank(3, 0, None), rank(4, 3, Some(1))];
+        assert_eq!(game.clone().award_split_pots(&ranks), vec![(3, 40), (1, 20), (2, 20)]);
+
+        // without a qualifying low the high hand scoops
+        let ranks = [rank(1, 1, None), rank(3, 0, None)];
+        assert_eq!(game.clone().award_split_pots(&ranks), vec![(3, 80)]);
+
+        // player 1 all-in for 10 only plays for the main pot, the folded player 4 paid into both
+        game.players[0].hand_stack = game.players[0].balance + 10;
+        game.pot = 70;
+        let ranks = [rank(1, 0, Some(0)), rank(2, 1, None), rank(3, 2, Some(1))];
+        assert_eq!(game.award_split_pots(&ranks), vec![(1, 40), (2, 15), (3, 15)]);
+        assert_eq!(game.pot(), 0);
+        assert_eq!(game.chips(), 3990);
+    }
+
+    #[test]
+    fn test_betting_structures() {
+        let mut game = PokerGame::new(vec![1, 2], vec![1000, 1000], 10, 20);
+        game.set_betting_structure(BettingStructure::PotLimit);
+        game.check_move(Check_Action { action_type: ActionType::SmallBlind, amount: None }, 1);
+        game.check_move(Check_Action { action_type: ActionType::BigBlind, amount: None }, 2);
+        // the pot is 40 once the small blind called
+        assert!(!game.check_move(Check_Action { action_type: ActionType::Raise, amount: Some(41) }, 1));
+        assert!(game.check_move(Check_Action { action_type: ActionType::Raise, amount: Some(40) }, 1));
+
+        let mut game = PokerGame::new(vec![1, 2], vec![1000, 1000], 10, 20);
+        game.set_betting_structure(BettingStructure::FixedLimit { small_bet: 20, big_bet: 40, raise_cap: 2 });
+        game.check_move(Check_Action { action_type: ActionType::SmallBlind, amount: None }, 1);
+        game.check_move(Check_Action { action_type: ActionType::BigBlind, amount: None }, 2);
+        assert!(!game.check_move(Check_Action { action_type: ActionType::Raise, amount: Some(30) }, 1));
+        assert!(game.check_move(Check_Action { action_type: ActionType::Raise, amount: Some(20) }, 1));
+        assert!(game.check_move(Check_Action { action_type: ActionType::Raise, amount: Some(20) }, 2));
+        // capped at two raises
+        assert!(!game.check_move(Check_Action { action_type: ActionType::Raise, amount: Some(20) }, 1));
+        assert!(game.check_move(Check_Action { action_type: ActionType::Call, amount: None }, 1));
+
+        // the turn is played with the big bet
+        game.next_street();
+        game.next_street();
+        assert!(!game.check_move(Check_Action { action_type: ActionType::Raise, amount: Some(20) }, 1));
+        assert!(game.check_move(Check_Action { action_type: ActionType::Raise, amount: Some(40) }, 1));
+    }
+
+    #[test]
+    fn test_antes_and_straddle() {
+        let mut game = PokerGame::with_empty_seats(4, 10, 20);
+        for seat in 0..4 {
+            game.join_seat(seat as u64 + 1, seat, 1000).unwrap();
+        }
+        game.set_forced_bets(Ante::PerPlayer(5), true).unwrap();
+        game.start_hand().unwrap();
+        assert_eq!(game.pot(), 20);
+        assert_eq!(game.balance(4), Some(995));
+        // player 1 has the button
+        assert_eq!(game.pending_blind(), Some(ActionType::SmallBlind));
+        game.check_move(Check_Action { action_type: ActionType::SmallBlind, amount: None }, 2);
+        game.check_move(Check_Action { action_type: ActionType::BigBlind, amount: None }, 3);
+
+        // the straddler can't act before the straddle is in
+        assert_eq!(game.pending_blind(), Some(ActionType::Straddle));
+        assert!(!game.check_move(Check_Action { action_type: ActionType::Call, amount: None }, 4));
+        assert!(game.check_move(Check_Action { action_type: ActionType::Straddle, amount: None }, 4));
+        assert_eq!(game.pot(), 90);
+        assert_eq!(game.current_bet(), 40);
+        assert_eq!(game.amount_to_call(3), Some(20));
+        assert!(!game.check_move(Check_Action { action_type: ActionType::Straddle, amount: None }, 1));
+        assert!(game.check_move(Check_Action { action_type: ActionType::Call, amount: None }, 1));
+        game.check_move(Check_Action { action_type: ActionType::Fold, amount: None }, 2);
+        game.check_move(Check_Action { action_type: ActionType::Fold, amount: None }, 3);
+        // the straddler has the option
+        assert_eq!(game.current_player_id(), 4);
+        assert_eq!(game.timeout_action().action_type, ActionType::Check);
+        assert_eq!(game.award_pot(&[1]), vec![(1, 130)]);
+
+        // the button moved to player 2, the big blind posts one ante for the table
+        game.set_forced_bets(Ante::BigBlind(20), false).unwrap();
+        game.start_hand().unwrap();
+        assert_eq!(game.pot(), 20);
+        assert_eq!(game.balance(4), Some(935));
+        assert_eq!(game.balance(3), Some(975));
+        assert!(game.post_antes().is_err());
+        game.check_move(Check_Action { action_type: ActionType::SmallBlind, amount: None }, 3);
+        game.check_move(Check_Action { action_type: ActionType::BigBlind, amount: None }, 4);
+        assert_eq!(game.pending_blind(), None);
+        assert_eq!(game.pot(), 50);
+        assert_eq!(game.chips(), 4000);
+    }
+
+    #[test]
+    fn test_straddle_fits_a_bet() {
+        let mut game = PokerGame::with_empty_seats(4, 100, 200);
+        assert!(game.set_forced_bets(Ante::None, true).is_err());
+        assert!(!game.straddle());
+
+        game.set_blinds(50, 100).unwrap();
+        game.set_forced_bets(Ante::None, true).unwrap();
+        assert_eq!(game.straddle_amount(), 200);
+        // the blinds can't go up past a straddle the table can post
+        assert!(game.set_blinds(100, 200).is_err());
+        assert_eq!(game.big_blind(), 100);
+    }
+
+    #[test]
+    fn test_tournament_eliminations() {
+        let mut game = PokerGame::with_empty_seats(4, 10, 20);
+        let stacks = [(4, 100), (1, 10), (2, 20), (3, 100)];
+        for (seat, (player_id, stack)) in stacks.into_iter().enumerate() {
+            game.join_seat(player_id, seat, stack).unwrap();
+        }
+
+        // both blinds are all in and lose to the call
+        game.start_hand().unwrap();
+        game.check_move(Check_Action { action_type: ActionType::SmallBlind, amount: None }, 1);
+        game.check_move(Check_Action { action_type: ActionType::BigBlind, amount: None }, 2);
+        game.check_move(Check_Action { action_type: ActionType::Call, amount: None }, 3);
+        game.check_move(Check_Action { action_type: ActionType::Fold, amount: None }, 4);
+        assert!(game.is_betting_settled());
+        assert!(game.eliminate_busted().is_empty());
+        game.award_pot(&[3]);
+        // the big blind started the hand with more chips
+        assert_eq!(game.eliminate_busted(), vec![(2, 3), (1, 4)]);
+        assert!(game.eliminate_busted().is_empty());
+        assert_eq!(game.seat_state(1), Some(SeatState::Eliminated));
+        assert_eq!(game.players_left(), 2);
+        assert_eq!(game.placements(), vec![3, 4, 2, 1]);
+        assert_eq!(game.tournament_winner(), None);
+        assert!(game.join_seat(5, 1, 100).is_err());
+        assert!(game.sit_out(1, true).is_err());
+
+        // the table plays on heads-up
+        game.start_hand().unwrap();
+        assert_eq!(game.active_player_ids(), vec![4, 3]);
+        assert_eq!(game.pending_blind(), Some(ActionType::SmallBlind));
+        assert!(game.set_blinds(55, 110).is_err());
+        game.check_move(Check_Action { action_type: ActionType::SmallBlind, amount: None }, 4);
+        game.check_move(Check_Action { action_type: ActionType::BigBlind, amount: None }, 3);
+        game.check_move(Check_Action { action_type: ActionType::Fold, amount: None }, 4);
+        game.award_pot(&[3]);
+        assert_eq!(game.balance(4), Some(90));
+
+        // the blinds went up and put the big blind all in
+        assert!(game.set_blinds(55, 110).is_ok());
+        game.start_hand().unwrap();
+        game.check_move(Check_Action { action_type: ActionType::SmallBlind, amount: None }, 3);
+        game.check_move(Check_Action { action_type: ActionType::BigBlind, amount: None }, 4);
+        game.check_move(Check_Action { action_type: ActionType::Call, amount: None }, 3);
+        game.award_pot(&[3]);
+        assert_eq!(game.eliminate_busted(), vec![(4, 2)]);
+        assert_eq!(game.tournament_winner(), Some(3));
+        assert_eq!(game.placements(), vec![3, 4, 2, 1]);
+        assert_eq!(game.chips(), 230);
+    }
+}
@@ -0,0 +1,355 @@
+use crate::constants::{HOLDEM, OMAHA, OMAHA_HI_LO};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+// Hand evaluation on the 1..=52 card numbers used on-chain: suit is (card - 1) / 13 and rank is
+// (card - 1) % 13 + 1 with aces as 1, see `card_from_number`.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum HandCategory {
+    HighCard,
+    Pair,
+    TwoPair,
+    ThreeOfAKind,
+    Straight,
+    Flush,
+    FullHouse,
+    FourOfAKind,
+    StraightFlush,
+    RoyalFlush,
+}
+
+impl HandCategory {
+    pub const ALL: [HandCategory; 10] = [
+        HandCategory::RoyalFlush,
+        HandCategory::StraightFlush,
+        HandCategory::FourOfAKind,
+        HandCategory::FullHouse,
+        HandCategory::Flush,
+        HandCategory::Straight,
+        HandCategory::ThreeOfAKind,
+        HandCategory::TwoPair,
+        HandCategory::Pair,
+        HandCategory::HighCard,
+    ];
+
+    // hand type committed to the game account, 0 is a royal flush and 9 a high card
+    pub fn to_hand_type(&self) -> u8 {
+        HandCategory::ALL.iter().position(|category| category == self).unwrap() as u8
+    }
+
+    pub fn from_hand_type(hand_type: u64) -> Option<Self> {
+        HandCategory::ALL.get(hand_type as usize).copied()
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            HandCategory::RoyalFlush => "Royal Flush",
+            HandCategory::StraightFlush => "Straight Flush",
+            HandCategory::FourOfAKind => "4 of Kind",
+            HandCategory::FullHouse => "Full House",
+            HandCategory::Flush => "Flush",
+            HandCategory::Straight => "Straight",
+            HandCategory::ThreeOfAKind => "3 of Kind",
+            HandCategory::TwoPair => "Two Pair",
+            HandCategory::Pair => "Pair",
+            HandCategory::HighCard => "High Card",
+        }
+    }
+}
+
+/// Poker variant a table plays, which decides how many hole cards are dealt and how a hand is
+/// made out of them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GameVariant {
+    #[default]
+    Holdem,
+    // four hole cards, a hand takes exactly two of them and three of the board
+    Omaha,
+    // Omaha splitting the pot between the best high hand and the best 8-or-better low
+    OmahaHiLo,
+}
+
+impl GameVariant {
+    pub fn hole_cards(&self) -> usize {
+        match self {
+            GameVariant::Holdem => 2,
+            GameVariant::Omaha | GameVariant::OmahaHiLo => 4,
+        }
+    }
+
+    /// Best hand a player makes out of their hole cards and the board.
+    pub fn best_hand(&self, hole_cards: &[u64], board: &[u64]) -> HandValue {
+        match self {
+            GameVariant::Holdem => best_hand(&[hole_cards, board].concat()),
+            GameVariant::Omaha | GameVariant::OmahaHiLo => best_omaha_hand(hole_cards, board),
+        }
+    }
+
+    /// Best qualifying low the player makes, None when the variant plays for the high hand only.
+    pub fn best_low_hand(&self, hole_cards: &[u64], board: &[u64]) -> Option<LowHand> {
+        match self {
+            GameVariant::OmahaHiLo => best_omaha_low(hole_cards, board),
+            _ => None,
+        }
+    }
+
+    pub fn is_hi_lo(&self) -> bool {
+        *self == GameVariant::OmahaHiLo
+    }
+
+    // value the game account keeps the variant as
+    pub fn to_slot(&self) -> u64 {
+        match self {
+            GameVariant::Holdem => HOLDEM,
+            GameVariant::Omaha => OMAHA,
+            GameVariant::OmahaHiLo => OMAHA_HI_LO,
+        }
+    }
+
+    // tables created before variants were stored play Hold'em
+    pub fn from_slot(value: u64) -> Self {
+        match value {
+            OMAHA => GameVariant::Omaha,
+            OMAHA_HI_LO => GameVariant::OmahaHiLo,
+            _ => GameVariant::Holdem,
+        }
+    }
+}
+
+/// Five different ranks of eight or below, aces playing low. The ranks are kept highest first,
+/// so the lower of two hands is the better low.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LowHand {
+    ranks: Vec<u64>,
+}
+
+impl LowHand {
+    // 1..=8 highest first, e.g. [5, 4, 3, 2, 1] for the wheel
+    pub fn ranks(&self) -> &[u64] {
+        &self.ranks
+    }
+}
+
+/// Value of a five card hand, hands compare by category first and then by the ranks that
+/// break ties within it.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct HandValue {
+    pub category: HandCategory,
+    ranks: Vec<u64>,
+}
+
+// 2..=14, aces high
+pub fn card_rank(card: u64) -> u64 {
+    match (card - 1) % 13 + 1 {
+        1 => 14,
+        rank => rank,
+    }
+}
+
+pub fn card_suit(card: u64) -> u64 {
+    (card - 1) / 13
+}
+
+pub fn evaluate_five(cards: [u64; 5]) -> HandValue {
+    let mut counts: HashMap<u64, u64> = HashMap::new();
+    for card in cards.iter() {
+        *counts.entry(card_rank(*card)).or_insert(0) += 1;
+    }
+    // ranks grouped by how often they appear, most frequent and then highest first
+    let mut groups: Vec<(u64, u64)> = counts.into_iter().map(|(rank, count)| (count, rank)).collect();
+    groups.sort_by(|a, b| b.cmp(a));
+    let ranks: Vec<u64> = groups.iter().map(|(_, rank)| *rank).collect();
+
+    let is_flush = cards.iter().all(|card| card_suit(*card) == card_suit(cards[0]));
+    let straight_high = if groups.len() == 5 {
+        if ranks[0] - ranks[4] == 4 {
+            Some(ranks[0])
+        } else if ranks == [14, 5, 4, 3, 2] {
+            // the wheel, ace plays low
+            Some(5)
+        } else {
+            None
+        }
+    } else {
+        None
+    };
+
+    let category = match (straight_high, is_flush, groups[0].0, groups[1].0) {
+        (Some(14), true, _, _) => HandCategory::RoyalFlush,
+        (Some(_), true, _, _) => HandCategory::StraightFlush,
+        (_, _, 4, _) => HandCategory::FourOfAKind,
+        (_, _, 3, 2) => HandCategory::FullHouse,
+        (_, true, _, _) => HandCategory::Flush,
+        (Some(_), _, _, _) => HandCategory::Straight,
+        (_, _, 3, _) => HandCategory::ThreeOfAKind,
+        (_, _, 2, 2) => HandCategory::TwoPair,
+        (_, _, 2, _) => HandCategory::Pair,
+        _ => HandCategory::HighCard,
+    };
+
+    let ranks = match straight_high {
+        Some(high) => vec![high],
+        None => ranks,
+    };
+
+    HandValue { category, ranks }
+}
+
+/// Best five card hand out of the given cards.
+pub fn best_hand(cards: &[u64]) -> HandValue {
+    combinations(cards, 5)
+        .into_iter()
+        .map(|hand| evaluate_five([hand[0], hand[1], hand[2], hand[3], hand[4]]))
+        .max()
+        .expect("at least five cards are needed to make a hand")
+}
+
+/// Best Omaha hand, made of exactly two of the hole cards and three of the board.
+pub fn best_omaha_hand(hole_cards: &[u64], board: &[u64]) -> HandValue {
+    omaha_hands(hole_cards, board)
+        .into_iter()
+        .map(evaluate_five)
+        .max()
+        .expect("two hole cards and three board cards are needed to make a hand")
+}
+
+/// Best 8-or-better low out of two hole cards and three of the board, if any qualifies.
+pub fn best_omaha_low(hole_cards: &[u64], board: &[u64]) -> Option<LowHand> {
+    omaha_hands(hole_cards, board)
+        .into_iter()
+        .filter_map(evaluate_low)
+        .min()
+}
+
+// straights and flushes don't count against a low, pairs do
+pub fn evaluate_low(cards: [u64; 5]) -> Option<LowHand> {
+    let mut ranks: Vec<u64> = cards.iter().map(|card| (card - 1) % 13 + 1).collect();
+    ranks.sort_by(|a, b| b.cmp(a));
+    ranks.dedup();
+    (ranks.len() == 5 && ranks[0] <= 8).then_some(LowHand { ranks })
+}
+
+// every five card hand taking two of the hole cards and three of the board
+fn omaha_hands(hole_cards: &[u64], board: &[u64]) -> Vec<[u64; 5]> {
+    let boards = combinations(board, 3);
+    combinations(hole_cards, 2)
+        .iter()
+        .flat_map(|hole| {
+            boards
+                .iter()
+                .map(move |board| [hole[0], hole[1], board[0], board[1], board[2]])
+        })
+        .collect()
+}
+
+pub fn combinations(cards: &[u64], k: usize) -> Vec<Vec<u64>> {
+    if k == 0 {
+        return vec![vec![]];
+    }
+    if cards.len() < k {
+        return vec![];
+    }
+    let mut with_first: Vec<Vec<u64>> = combinations(&cards[1..], k - 1)
+        .into_iter()
+        .map(|mut rest| {
+            rest.insert(0, cards[0]);
+            rest
+        })
+        .collect();
+    with_first.extend(combinations(&cards[1..], k));
+    with_first
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // card number from a rank (2..=14) and a suit (0..4)
+    fn card(rank: u64, suit: u64) -> u64 {
+        suit * 13 + if rank == 14 { 1 } else { rank }
+    }
+
+    #[test]
+    fn test_categories() {
+        let royal = [card(14, 0), card(13, 0), card(12, 0), card(11, 0), card(10, 0)];
+        assert_eq!(evaluate_five(royal).category, HandCategory::RoyalFlush);
+
+        let wheel = [card(14, 0), card(2, 1), card(3, 2), card(4, 3), card(5, 0)];
+        assert_eq!(evaluate_five(wheel).category, HandCategory::Straight);
+
+        let full_house = [card(9, 0), card(9, 1), card(9, 2), card(4, 3), card(4, 0)];
+        assert_eq!(evaluate_five(full_house).category, HandCategory::FullHouse);
+
+        let two_pair = [card(9, 0), card(9, 1), card(4, 2), card(4, 3), card(2, 0)];
+        assert_eq!(evaluate_five(two_pair).category, HandCategory::TwoPair);
+
+        let high_card = [card(9, 0), card(7, 1), card(4, 2), card(3, 3), card(2, 0)];
+        assert_eq!(evaluate_five(high_card).category, HandCategory::HighCard);
+    }
+
+    #[test]
+    fn test_hand_order() {
+        let wheel = [card(14, 0), card(2, 1), card(3, 2), card(4, 3), card(5, 0)];
+        let six_high = [card(6, 0), card(2, 1), card(3, 2), card(4, 3), card(5, 0)];
+        assert!(evaluate_five(six_high) > evaluate_five(wheel));
+
+        let kings = [card(13, 0), card(13, 1), card(3, 2), card(4, 3), card(9, 0)];
+        let queens = [card(12, 0), card(12, 1), card(14, 2), card(4, 3), card(9, 0)];
+        assert!(evaluate_five(kings) > evaluate_five(queens));
+    }
+
+    #[test]
+    fn test_best_hand_of_seven() {
+        let cards = [card(14, 0), card(14, 1), card(10, 2), card(10, 3), card(10, 0), card(2, 1), card(3, 2)];
+        let value = best_hand(&cards);
+        assert_eq!(value.category, HandCategory::FullHouse);
+        assert_eq!(value.category.to_hand_type(), 3);
+        assert_eq!(HandCategory::from_hand_type(3), Some(HandCategory::FullHouse));
+    }
+
+    #[test]
+    fn test_omaha_takes_two_hole_cards() {
+        // four aces in hand only make a pair
+        let aces = [card(14, 0), card(14, 1), card(14, 2), card(14, 3)];
+        let board = [card(13, 0), card(12, 1), card(7, 2), card(3, 3), card(2, 0)];
+        assert_eq!(GameVariant::Holdem.best_hand(&aces[..2], &board).category, HandCategory::Pair);
+        assert_eq!(GameVariant::Omaha.best_hand(&aces, &board).category, HandCategory::Pair);
+
+        // four hearts on the board and one in hand is no flush
+        let hole = [card(14, 2), card(9, 0), card(8, 1), card(5, 3)];
+        let board = [card(13, 2), card(11, 2), card(7, 2), card(3, 2), card(2, 0)];
+        assert_eq!(GameVariant::Holdem.best_hand(&hole[..2], &board).category, HandCategory::Flush);
+        assert_eq!(GameVariant::Omaha.best_hand(&hole, &board).category, HandCategory::HighCard);
+
+        // three of the board and two of the hand make the straight
+        let hole = [card(9, 0), card(8, 1), card(2, 2), card(2, 3)];
+        let board = [card(7, 2), card(6, 0), card(5, 1), card(13, 3), card(13, 0)];
+        assert_eq!(GameVariant::Omaha.best_hand(&hole, &board).category, HandCategory::Straight);
+        assert_eq!(GameVariant::from_slot(GameVariant::Omaha.to_slot()), GameVariant::Omaha);
+    }
+
+    #[test]
+    fn test_eight_or_better_low() {
+        let board = [card(2, 0), card(5, 1), card(7, 2), card(13, 3), card(12, 0)];
+        // ace-three with the 7-5-2 of the board
+        let hole = [card(14, 1), card(3, 2), card(13, 0), card(13, 1)];
+        let low = GameVariant::OmahaHiLo.best_low_hand(&hole, &board).unwrap();
+        assert_eq!(low.ranks(), &[7, 5, 3, 2, 1]);
+        assert_eq!(GameVariant::Omaha.best_low_hand(&hole, &board), None);
+
+        // lows compare from the highest card down, ace-four loses to ace-three
+        let hole = [card(14, 2), card(4, 3), card(9, 0), card(10, 1)];
+        let worse = GameVariant::OmahaHiLo.best_low_hand(&hole, &board).unwrap();
+        assert_eq!(worse.ranks(), &[7, 5, 4, 2, 1]);
+        assert!(worse > low);
+
+        // only one low card in hand, or a pair, doesn't qualify
+        let hole = [card(14, 1), card(9, 2), card(10, 0), card(11, 1)];
+        assert_eq!(GameVariant::OmahaHiLo.best_low_hand(&hole, &board), None);
+        let hole = [card(2, 1), card(2, 2), card(10, 0), card(11, 1)];
+        assert_eq!(GameVariant::OmahaHiLo.best_low_hand(&hole, &board), None);
+        assert!(GameVariant::OmahaHiLo.is_hi_lo());
+    }
+}
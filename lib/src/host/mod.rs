@@ -0,0 +1,867 @@
+use crate::broadcast::{registry, seat_on_chain, GameTable};
+use crate::client::{
+    create_aze_client, AzeAccountTemplate, AzeClient, AzeGameMethods, AzeTransactionTemplate,
+    EndHandTransactionData, PlayBetTransactionData, PlayCheckTransactionData, PlayFoldTransactionData,
+    SendCommunityCardsTransactionData, ShuffleCardTransactionData, StartHandTransactionData,
+};
+use crate::constants::{
+    BETTING_STRUCTURE_SLOT, BUY_IN_FAUCET_SLOT, COMMUNITY_CARDS, CURRENT_PHASE_SLOT,
+    CURRENT_TURN_INDEX_SLOT, DEFAULT_ACTION_TYPE, EXTRA_HOLE_CARDS_OFFSET, FIRST_PLAYER_INDEX,
+    FORCED_BETS_SLOT, GAME_VARIANT_SLOT, HANDS_PLAYED_SLOT, HAND_OFFSET, HAND_OVER_PHASE,
+    HIGHEST_BET, HIGHEST_BET_SLOT, IS_FOLD_OFFSET, NO_OF_PLAYERS, PLAYER_BALANCE_SLOT, PLAYER_INITIAL_BALANCE,
+    PLAYER_STATS_SLOTS, POT_VALUE, RAKE_CAP_SLOT, RAKE_PERCENT_SLOT, RAKE_SLOT,
+};
+use crate::executor::execute_tx_and_sync;
+use crate::gamestate::{Ante, BettingStructure, Check_Action, PokerGame, SeatState, ShowdownRank};
+use crate::hands::{GameVariant, HandValue, LowHand};
+use crate::storage::{betting_structure_from_word, forced_bets_from_word, GameStorageSlotData};
+use crate::tournament::BlindSchedule;
+use aze_types::accounts::AccountCreationError;
+use aze_types::actions::ActionType;
+use aze_types::events::GameEvent;
+use miden_client::client::accounts::AccountStorageMode;
+use miden_client::client::transactions::transaction_request::TransactionTemplate;
+use miden_objects::{
+    accounts::{Account, AccountId},
+    Felt, FieldElement,
+};
+use serde::{Deserialize, Serialize};
+use tokio::sync::watch;
+use tokio::time::{sleep, Duration, Instant};
+
+// The dealer side of a game: consumes the notes sent to the game account, reveals community
+// cards as streets advance, posts the straddle, enforces the action timeout and publishes what
+// happens to the table.
+// At open tables it also follows the seats taken and left on-chain and deals once all are taken.
+// Tournament tables raise the blinds between hands and eliminate players left without chips.
+// The account must be in the local store and the table hosted on this process' server.
+
+/// Everything needed to host a game again after a restart.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct HostedGame {
+    pub game_id: String,
+    // players seated at creation, empty for an open table
+    pub player_ids: Vec<u64>,
+    pub small_blind: u8,
+    pub buy_in: u64,
+    // seconds a player has to act, 0 to wait forever
+    pub action_timeout: u64,
+    // blind levels of a tournament, the buy-in being every seat's starting stack
+    #[serde(default)]
+    pub blind_schedule: Option<BlindSchedule>,
+}
+
+/// Rake taken from every pot that reaches the flop, which the operator withdraws from the game
+/// account.
+#[derive(Debug, Clone, Copy)]
+pub struct RakeConfig {
+    pub percent: u64,
+    // most taken from a single pot
+    pub cap: u64,
+    pub operator: AccountId,
+}
+
+/// Runs the host loop until `shutdown` is set.
+pub async fn run_host(hosted_game: HostedGame, shutdown: watch::Receiver<bool>) {
+    let game_account_id = match AccountId::from_hex(&hosted_game.game_id) {
+        Ok(game_account_id) => game_account_id,
+        Err(e) => {
+            eprintln!("Invalid game id {}: {}", hosted_game.game_id, e);
+            return;
+        }
+    };
+    let table = match registry().get(&hosted_game.game_id) {
+        Some(table) => table,
+        None => {
+            eprintln!("Game {} is not hosted on this server", hosted_game.game_id);
+            return;
+        }
+    };
+    let open_seats = hosted_game.player_ids.is_empty();
+    let action_timeout = hosted_game.action_timeout;
+
+    let client: AzeClient = create_aze_client();
+    {
+        // the table takes the same rake, limits raises, deals the variant and posts the forced
+        // bets the way the game account was created with
+        let (game_account, _) = client.get_account(game_account_id).unwrap();
+        let storage = game_account.storage();
+        let rake_percent = storage.get_item(RAKE_PERCENT_SLOT).as_elements()[0].as_int();
+        let rake_cap = storage.get_item(RAKE_CAP_SLOT).as_elements()[0].as_int();
+        let betting_word = storage.get_item(BETTING_STRUCTURE_SLOT);
+        let betting_structure = betting_structure_from_word(
+            [0, 1, 2, 3].map(|i| betting_word.as_elements()[i].as_int()),
+        );
+        let variant =
+            GameVariant::from_slot(storage.get_item(GAME_VARIANT_SLOT).as_elements()[0].as_int());
+        let forced_bets_word = storage.get_item(FORCED_BETS_SLOT);
+        let (ante, straddle) = forced_bets_from_word(
+            [0, 1, 2, 3].map(|i| forced_bets_word.as_elements()[i].as_int()),
+        );
+        let mut game = table.game.lock().unwrap();
+        game.set_rake(rake_percent, rake_cap);
+        game.set_betting_structure(betting_structure);
+        game.set_variant(variant);
+        if let Err(e) = game.set_forced_bets(ante, straddle) {
+            eprintln!("Game {}: {}", hosted_game.game_id, e);
+            return;
+        }
+        // the first hand of a table created with players was dealt with the antes in the pot,
+        // a table resumed from its snapshot already has them
+        if !open_seats && !game.is_between_hands() && game.pot() == 0 {
+            if let Err(e) = game.post_antes() {
+                eprintln!("Game {}: {}", hosted_game.game_id, e);
+            }
+        }
+    }
+    let mut last_nonce = client.get_account(game_account_id).unwrap().0.nonce();
+    let mut last_phase = None;
+    let mut turn_started = Instant::now();
+    let mut showdown_announced = false;
+    let mut escrow_mismatch = None;
+    // minute based levels count from the first hand this process deals
+    let mut tournament_clock: Option<Instant> = None;
+    while !*shutdown.borrow() {
+        let (game_account, _) = client.get_account(game_account_id).unwrap();
+        let phase_data = game_account
+            .storage()
+            .get_item(CURRENT_PHASE_SLOT)
+            .as_elements()
+            .to_vec();
+        let pre_phase = phase_data[0].as_int();
+        // every consumed note bumps the nonce, so any move restarts the clock, and it only runs
+        // while the hand waits on the current player to bet
+        if game_account.nonce() != last_nonce
+            || last_phase != Some(pre_phase)
+            || !awaiting_bet(&game_account, &table)
+        {
+            last_nonce = game_account.nonce();
+            last_phase = Some(pre_phase);
+            turn_started = Instant::now();
+        } else if action_timeout > 0
+            && turn_started.elapsed() >= Duration::from_secs(action_timeout)
+        {
+            enforce_action_timeout(game_account_id, &table).await;
+            turn_started = Instant::now();
+        }
+        consume_game_notes(game_account_id).await;
+        let (game_account, _) = client.get_account(game_account_id).unwrap();
+        let phase_data = game_account
+            .storage()
+            .get_item(CURRENT_PHASE_SLOT)
+            .as_elements()
+            .to_vec();
+        let phase = phase_data[0].as_int();
+
+        if open_seats {
+            sync_seats(&game_account, &table).await;
+            let mismatch = escrow_mismatch_of(&game_account, &table.game.lock().unwrap());
+            if mismatch.is_some() && mismatch != escrow_mismatch {
+                eprintln!(
+                    "Game {}: {}, no more hands are dealt",
+                    hosted_game.game_id,
+                    mismatch.as_ref().unwrap()
+                );
+            }
+            escrow_mismatch = mismatch;
+            if let Some(schedule) = &hosted_game.blind_schedule {
+                let hands_played =
+                    game_account.storage().get_item(HANDS_PLAYED_SLOT).as_elements()[0].as_int();
+                let elapsed = tournament_clock.map_or(Duration::ZERO, |clock| clock.elapsed());
+                raise_blinds(schedule, hands_played, elapsed, &table).await;
+            }
+            let tournament_over = table.game.lock().unwrap().tournament_winner().is_some();
+            // the previous hand has to be paid out on-chain before the next one is dealt, and
+            // chips the vault doesn't back must never be played for
+            if phase == HAND_OVER_PHASE
+                && !tournament_over
+                && escrow_mismatch.is_none()
+                && start_open_hand(game_account_id, &table).await
+            {
+                tournament_clock.get_or_insert_with(Instant::now);
+                showdown_announced = false;
+                continue;
+            }
+        }
+        if phase == 0 {
+            post_straddle(game_account_id, &game_account, &table).await;
+        }
+        let (player_ids, variant) = {
+            let game = table.game.lock().unwrap();
+            (game.player_ids(), game.variant())
+        };
+
+        // once the game ended, announce the winners as soon as every hand is in
+        if phase == 3 && !showdown_announced {
+            if let Some((showdown, ranks)) =
+                showdown_result(&game_account, &player_ids, variant)
+            {
+                if matches!(showdown, GameEvent::ShowdownResult { .. }) {
+                    // the hand is over, seats may change again
+                    let (pot, winnings) = {
+                        let mut game = table.game.lock().unwrap();
+                        let pot = game.pot();
+                        let mut winnings = [0; NO_OF_PLAYERS as usize];
+                        // all-in players only win the pots they are in
+                        let paid = game.award_split_pots(&ranks);
+                        for (player_id, amount) in paid {
+                            if let Some(seat) = game.seat_of(player_id) {
+                                winnings[seat] += amount;
+                            }
+                        }
+                        (pot, winnings)
+                    };
+                    end_hand(game_account_id, pot, winnings).await;
+                }
+                table.publish(showdown).await;
+                showdown_announced = true;
+                if hosted_game.blind_schedule.is_some() {
+                    eliminate_busted(&table).await;
+                }
+            }
+        }
+
+        // nothing is revealed between hands
+        if phase == HAND_OVER_PHASE {
+            sleep(Duration::from_secs(5)).await;
+            continue;
+        }
+
+        // if phase is not incremented post consumption, continue
+        if pre_phase + 1 != phase {
+            sleep(Duration::from_secs(5)).await;
+            let revealed_slots = match pre_phase {
+                0 => &COMMUNITY_CARDS[0..3],
+                1 => &COMMUNITY_CARDS[3..4],
+                2 => &COMMUNITY_CARDS[4..5],
+                _ => &COMMUNITY_CARDS[0..0],
+            };
+            if !revealed_slots.is_empty() {
+                let cards = revealed_slots
+                    .iter()
+                    .map(|slot| game_account.storage().get_item(*slot).as_elements()[0].as_int())
+                    .collect();
+                table
+                    .publish(GameEvent::CommunityCardsRevealed { cards })
+                    .await;
+            }
+            continue;
+        }
+
+        table.game.lock().unwrap().next_street();
+        table.publish(GameEvent::StreetAdvanced { phase }).await;
+
+        // broadcast message if game ends
+        if phase == 3 {
+            table.publish(GameEvent::GameEnded).await;
+        }
+
+        // if phase changes, send community cards for unmasking
+        let player_account_id = AccountId::try_from(player_ids[0]).unwrap();
+        let mut cards: [[Felt; 4]; 3] = [[Felt::ZERO; 4]; 3];
+        for (i, slot) in (1..4).enumerate() {
+            let card_digest = game_account.storage().get_item(slot);
+            cards[i] = card_digest.into();
+        }
+        // send community cards
+        send_community_cards(game_account_id, player_account_id, cards, phase as u8).await;
+        sleep(Duration::from_secs(5)).await;
+    }
+}
+
+// takes over the seats players joined, left or sat out of on-chain, once the hand is over
+async fn sync_seats(game_account: &Account, table: &GameTable) {
+    let mut events = vec![];
+    {
+        let mut game = table.game.lock().unwrap();
+        if !game.is_between_hands() {
+            return;
+        }
+        for seat in 0..game.seat_count() {
+            let (seat_state, player_id) = seat_on_chain(game_account, seat);
+            let left = game
+                .seated_player(seat)
+                .filter(|seated| seat_state == SeatState::Empty || *seated != player_id);
+            if let Some(seated) = left {
+                if let Ok(balance) = game.leave_seat(seated) {
+                    events.push(GameEvent::SeatLeft {
+                        player_id: seated,
+                        seat: seat as u64,
+                        balance,
+                    });
+                }
+            }
+            if seat_state == SeatState::Empty {
+                continue;
+            }
+
+            if game.seated_player(seat).is_none() {
+                let balance_slot = PLAYER_BALANCE_SLOT + seat as u8 * PLAYER_STATS_SLOTS;
+                let buy_in = game_account.storage().get_item(balance_slot).as_elements()[0].as_int();
+                if game.join_seat(player_id, seat, buy_in).is_ok() {
+                    events.push(GameEvent::SeatTaken {
+                        player_id,
+                        seat: seat as u64,
+                        buy_in,
+                    });
+                }
+            }
+            if game.seat_state(seat) != Some(seat_state) {
+                let sitting_out = seat_state == SeatState::SittingOut;
+                if game.sit_out(player_id, sitting_out).is_ok() {
+                    events.push(GameEvent::SittingOut {
+                        player_id,
+                        sitting_out,
+                    });
+                }
+            }
+        }
+    }
+    for event in events {
+        table.publish(event).await;
+    }
+}
+
+// the chips at the table and the rake not withdrawn yet have to be backed by the buy-in asset
+// held in the game account's vault, which only holds between hands as seats change on-chain first
+fn escrow_mismatch_of(game_account: &Account, game: &PokerGame) -> Option<String> {
+    let faucet_id = game_account.storage().get_item(BUY_IN_FAUCET_SLOT).as_elements()[0].as_int();
+    if faucet_id == 0 || !game.is_between_hands() {
+        return None;
+    }
+    let faucet_id = AccountId::try_from(faucet_id).ok()?;
+    let chips = game.chips();
+    let rake = game_account.storage().get_item(RAKE_SLOT).as_elements()[0].as_int();
+    let escrowed = game_account.vault().get_balance(faucet_id).unwrap_or(0);
+    (escrowed != chips + rake).then(|| {
+        format!(
+            "{} chips at the table and {} rake but {} of faucet {} escrowed",
+            chips, rake, escrowed, faucet_id
+        )
+    })
+}
+
+// moves the tournament on to the level it reached, the blinds only ever go up
+async fn raise_blinds(
+    schedule: &BlindSchedule,
+    hands_played: u64,
+    elapsed: Duration,
+    table: &GameTable,
+) {
+    let level = schedule.level_at(hands_played, elapsed);
+    let blinds = schedule.blinds_at(level);
+    let (small_blind, big_blind) = (blinds.small_blind as u64, blinds.big_blind as u64);
+    {
+        let mut game = table.game.lock().unwrap();
+        if game.small_blind() >= small_blind || game.set_blinds(small_blind, big_blind).is_err() {
+            return;
+        }
+    }
+    table
+        .publish(GameEvent::BlindsRaised {
+            level: level as u64 + 1,
+            small_blind,
+            big_blind,
+        })
+        .await;
+}
+
+// takes the seats of the players the last hand left without chips, the game account eliminates
+// them as well when it pays the hand out
+async fn eliminate_busted(table: &GameTable) {
+    let (eliminated, placements) = {
+        let mut game = table.game.lock().unwrap();
+        let eliminated = game.eliminate_busted();
+        let placements = game.tournament_winner().map(|_| game.placements());
+        (eliminated, placements)
+    };
+    for (player_id, place) in eliminated {
+        table
+            .publish(GameEvent::PlayerEliminated {
+                player_id,
+                place: place as u64,
+            })
+            .await;
+    }
+    if let Some(placements) = placements {
+        table.publish(GameEvent::TournamentEnded { placements }).await;
+    }
+}
+
+// deals a hand once every seat of the open table is taken, true if it did
+async fn start_open_hand(game_account_id: AccountId, table: &GameTable) -> bool {
+    let (player_ids, small_blind, big_blind) = {
+        let mut game = table.game.lock().unwrap();
+        // the cards are masked by every seat, so nobody may be missing. Eliminated tournament
+        // players keep masking without being dealt in
+        let full = (0..game.seat_count()).all(|seat| {
+            matches!(
+                game.seat_state(seat),
+                Some(SeatState::Taken) | Some(SeatState::Eliminated)
+            )
+        });
+        if !game.is_between_hands() || !full {
+            return false;
+        }
+        if let Err(e) = game.start_hand() {
+            eprintln!("Cannot deal game {}: {}", table.game_id, e);
+            return false;
+        }
+        (game.player_ids(), game.small_blind(), game.big_blind())
+    };
+
+    let mut client: AzeClient = create_aze_client();
+    let start_hand_data =
+        StartHandTransactionData::new(game_account_id, game_account_id, small_blind, big_blind);
+    let transaction_template = AzeTransactionTemplate::StartHand(start_hand_data);
+    let txn_request = client
+        .build_aze_start_hand_tx_request(transaction_template)
+        .unwrap();
+    execute_tx_and_sync(&mut client, txn_request).await;
+    deal_cards(&mut client, game_account_id, &player_ids).await;
+
+    table.publish(GameEvent::HandStarted { player_ids }).await;
+    true
+}
+
+// pays the pot out to the winners' seats on-chain, after which players may cash out. The game
+// account takes the rake itself and rejects winnings that don't add up to the rest of the pot
+async fn end_hand(game_account_id: AccountId, pot: u64, winnings: [u64; NO_OF_PLAYERS as usize]) {
+    let mut client: AzeClient = create_aze_client();
+    let end_hand_data =
+        EndHandTransactionData::new(game_account_id, game_account_id, pot, winnings);
+    let transaction_template = AzeTransactionTemplate::EndHand(end_hand_data);
+    let txn_request = client
+        .build_aze_end_hand_tx_request(transaction_template)
+        .unwrap();
+    execute_tx_and_sync(&mut client, txn_request).await;
+}
+
+// posts the straddle for the player after the big blind once the big blind is in on-chain
+async fn post_straddle(game_account_id: AccountId, game_account: &Account, table: &GameTable) {
+    let storage = game_account.storage();
+    let current_turn_index = storage.get_item(CURRENT_TURN_INDEX_SLOT).as_elements()[0].as_int();
+    let highest_bet = storage.get_item(HIGHEST_BET_SLOT).as_elements()[0].as_int();
+    let (player_id, amount, bet_amount, next_player_id) = {
+        let mut game = table.game.lock().unwrap();
+        if game.pending_blind() != Some(ActionType::Straddle) {
+            return;
+        }
+        let player_id = game.current_player_id();
+        let straddler_index = game
+            .seat_of(player_id)
+            .map(|seat| FIRST_PLAYER_INDEX as u64 + seat as u64 * PLAYER_STATS_SLOTS as u64);
+        if straddler_index != Some(current_turn_index) || highest_bet != game.big_blind() {
+            return;
+        }
+        let amount = game.straddle_amount();
+        // set_forced_bets and set_blinds keep the straddle within a single on-chain bet
+        let bet_amount = match u8::try_from(amount) {
+            Ok(bet_amount) => bet_amount,
+            Err(_) => return,
+        };
+        let straddle = Check_Action {
+            action_type: ActionType::Straddle,
+            amount: None,
+        };
+        if !game.check_move(straddle, player_id) {
+            return;
+        }
+        (player_id, amount, bet_amount, game.current_player_id())
+    };
+
+    let mut client: AzeClient = create_aze_client();
+    let playbet_txn_data =
+        PlayBetTransactionData::new(game_account_id, game_account_id, bet_amount);
+    let transaction_template = AzeTransactionTemplate::PlayBet(playbet_txn_data);
+    let txn_request = client
+        .build_aze_play_bet_tx_request(transaction_template)
+        .unwrap();
+    execute_tx_and_sync(&mut client, txn_request).await;
+
+    table
+        .publish(GameEvent::PlayerActed {
+            player_id,
+            action: ActionType::Straddle,
+            amount: Some(amount),
+        })
+        .await;
+    table
+        .publish(GameEvent::TurnChanged {
+            player_id: next_player_id,
+        })
+        .await;
+}
+
+// true once the current player is to bet: the blinds are in, the table posted the straddle and
+// the board of the street is unmasked. Dealing and unmasking go around every seat peer to peer
+// and can take longer than any player is given to act
+fn awaiting_bet(game_account: &Account, table: &GameTable) -> bool {
+    let storage = game_account.storage();
+    let board_len = match storage.get_item(CURRENT_PHASE_SLOT).as_elements()[0].as_int() {
+        0 => 0,
+        1 => 3,
+        2 => 4,
+        // the river is only shown down
+        _ => return false,
+    };
+    let board_unmasked = COMMUNITY_CARDS[..board_len]
+        .iter()
+        .all(|slot| (1..=52).contains(&storage.get_item(*slot).as_elements()[0].as_int()));
+    let game = table.game.lock().unwrap();
+    // no blind posted yet means the hole cards are still being dealt
+    board_unmasked
+        && game.pot() != game.antes()
+        && game.pending_blind() != Some(ActionType::Straddle)
+}
+
+// checks or folds for the player whose turn it is and lets the table know
+async fn enforce_action_timeout(game_account_id: AccountId, table: &GameTable) {
+    let (player_id, timeout_action, next_player_id) = {
+        let mut game = table.game.lock().unwrap();
+        let player_id = game.current_player_id();
+        let timeout_action = game.timeout_action();
+        if !game.check_move(timeout_action, player_id) {
+            return;
+        }
+        (player_id, timeout_action, game.current_player_id())
+    };
+
+    table
+        .publish(GameEvent::PlayerActed {
+            player_id,
+            action: timeout_action.action_type,
+            amount: None,
+        })
+        .await;
+    table
+        .publish(GameEvent::TurnChanged {
+            player_id: next_player_id,
+        })
+        .await;
+    send_timeout_action(game_account_id, timeout_action.action_type).await;
+}
+
+// winners among the players still in the hand, None until all of them committed their hand.
+// Once every hole card and the board are unmasked the hands are evaluated the way the variant
+// makes them, otherwise the hand types the players committed decide. Also returns how every
+// player placed, which the main and side pots are split by
+fn showdown_result(
+    game_account: &Account,
+    player_ids: &[u64],
+    variant: GameVariant,
+) -> Option<(GameEvent, Vec<ShowdownRank>)> {
+    let storage = game_account.storage();
+    let card_at = |slot: u8| storage.get_item(slot).as_elements()[0].as_int();
+    let is_unmasked = |card: &u64| (1..=52).contains(card);
+    let board: Vec<u64> = COMMUNITY_CARDS.iter().map(|slot| card_at(*slot)).collect();
+
+    let mut hands: Vec<(u64, u64, Option<(HandValue, Option<LowHand>)>)> = vec![];
+    for (i, player_id) in player_ids.iter().enumerate() {
+        let player_index = FIRST_PLAYER_INDEX + i as u8 * PLAYER_STATS_SLOTS;
+        let folded = card_at(player_index + IS_FOLD_OFFSET);
+        if folded != 0 {
+            continue;
+        }
+        // Hand Slot storage structure: [player card 1 index, player card 2 index, hand type, 0]
+        let hand = storage.get_item(player_index + HAND_OFFSET);
+        let hand_type = hand.as_elements()[2].as_int();
+        if hand_type == 0 {
+            return None;
+        }
+        // [player card 3 index, player card 4 index, 0, 0], only dealt in Omaha
+        let extra_cards = storage.get_item(player_index + EXTRA_HOLE_CARDS_OFFSET);
+        let hole_cards: Vec<u64> = [hand, extra_cards]
+            .iter()
+            .flat_map(|word| word.as_elements()[0..2].iter().map(|card| card.as_int()))
+            .take(variant.hole_cards())
+            .collect();
+        let value = (hole_cards.iter().all(is_unmasked) && board.iter().all(is_unmasked)).then(|| {
+            (
+                variant.best_hand(&hole_cards, &board),
+                variant.best_low_hand(&hole_cards, &board),
+            )
+        });
+        hands.push((*player_id, hand_type, value));
+    }
+
+    // evaluated hands rank by how many hands beat them, so the best one ranks 0
+    let (ranks, evaluated_type) = if hands.iter().all(|(_, _, value)| value.is_some()) {
+        let values: Vec<(u64, HandValue, Option<LowHand>)> = hands
+            .into_iter()
+            .filter_map(|(player_id, _, value)| value.map(|(high, low)| (player_id, high, low)))
+            .collect();
+        // committed hand types start at 1 for a royal flush
+        let best = values.iter().map(|(_, high, _)| high.clone()).max()?;
+        let ranks = values
+            .iter()
+            .map(|(player_id, high, low)| ShowdownRank {
+                player_id: *player_id,
+                high: values.iter().filter(|(_, other, _)| other > high).count() as u64,
+                low: low.as_ref().map(|low| {
+                    values
+                        .iter()
+                        .filter(|(_, _, other)| matches!(other, Some(other) if other < low))
+                        .count() as u64
+                }),
+            })
+            .collect();
+        (ranks, Some(best.category.to_hand_type() as u64 + 1))
+    } else {
+        // lower hand types are stronger, a royal flush is 1
+        let ranks = hands
+            .iter()
+            .map(|(player_id, hand_type, _)| ShowdownRank {
+                player_id: *player_id,
+                high: *hand_type,
+                low: None,
+            })
+            .collect::<Vec<ShowdownRank>>();
+        (ranks, None)
+    };
+    let best_high = ranks.iter().map(|rank| rank.high).min()?;
+    let best_low = ranks.iter().filter_map(|rank| rank.low).min();
+    let winners = ranks
+        .iter()
+        .filter(|rank| rank.high == best_high)
+        .map(|rank| rank.player_id)
+        .collect::<Vec<u64>>();
+    let low_winners = ranks
+        .iter()
+        .filter(|rank| best_low.is_some() && rank.low == best_low)
+        .map(|rank| rank.player_id)
+        .collect();
+    let hand_type = evaluated_type.unwrap_or(best_high);
+    let pot = storage.get_item(POT_VALUE).as_elements()[0].as_int();
+    let showdown = GameEvent::ShowdownResult {
+        winners,
+        hand_type,
+        pot,
+        low_winners,
+    };
+    Some((showdown, ranks))
+}
+
+/// Creates a game account with the players seated in order and deals them in. Without players
+/// the seats stay empty until players send join notes, buying in with the faucet's asset. A
+/// starting stack makes it a tournament table every seat buys in for exactly that stack.
+/// Omaha tables deal four hole cards instead of two. Antes and the straddle are posted at the
+/// start of every hand.
+pub async fn create_aze_game_account(
+    player_account_ids: Vec<u64>,
+    small_blind: u8,
+    buy_in: u64,
+    buy_in_faucet: Option<AccountId>,
+    rake: Option<RakeConfig>,
+    starting_stack: Option<u64>,
+    betting_structure: BettingStructure,
+    variant: GameVariant,
+    ante: Ante,
+    straddle: bool,
+) -> Result<AccountId, AccountCreationError> {
+    let mut client: AzeClient = create_aze_client();
+    let slot_data = GameStorageSlotData::new(
+        small_blind,
+        buy_in,
+        NO_OF_PLAYERS,
+        FIRST_PLAYER_INDEX,
+        HIGHEST_BET,
+        PLAYER_INITIAL_BALANCE,
+    )
+    .with_seated_players(&player_account_ids)
+    .with_buy_in_faucet(buy_in_faucet.map_or(0, u64::from))
+    .with_starting_stack(starting_stack.unwrap_or(0))
+    .with_betting_structure(betting_structure)
+    .with_variant(variant)
+    .with_forced_bets(ante, straddle);
+    let slot_data = match rake {
+        Some(rake) => slot_data.with_rake(rake.percent, rake.cap, rake.operator.into()),
+        None => slot_data,
+    };
+
+    let (game_account, _) = client
+        .new_game_account(
+            AzeAccountTemplate::GameAccount {
+                mutable_code: false,
+                storage_mode: AccountStorageMode::Local,
+            },
+            Some(slot_data),
+        )
+        .unwrap();
+
+    let game_account_id = game_account.id();
+    if !player_account_ids.is_empty() {
+        deal_cards(&mut client, game_account_id, &player_account_ids).await;
+    }
+
+    Ok(game_account_id)
+}
+
+// sends the note for shuffling and encryption, which goes around every seat
+async fn deal_cards(client: &mut AzeClient, game_account_id: AccountId, player_account_ids: &[u64]) {
+    let sender_account_id = game_account_id;
+    let target_account_id = AccountId::try_from(player_account_ids[0]).unwrap();
+    let shuffle_card_data = ShuffleCardTransactionData::new(
+        sender_account_id,
+        target_account_id,
+        [DEFAULT_ACTION_TYPE, player_account_ids[1], player_account_ids[2], player_account_ids[3]]
+    );
+
+    let transaction_template = AzeTransactionTemplate::ShuffleCard(shuffle_card_data);
+    let txn_request = client
+        .build_aze_shuffle_card_tx_request(transaction_template)
+        .unwrap();
+    execute_tx_and_sync(client, txn_request.clone()).await;
+}
+
+pub async fn consume_game_notes(account_id: AccountId) {
+    let mut client: AzeClient = create_aze_client();
+    client.sync_state().await.unwrap();
+    let account = client.get_account(account_id).unwrap();
+    let consumable_notes = client.get_consumable_notes(Some(account_id)).unwrap();
+    println!("Consumable notes: {:?}", consumable_notes.len());
+
+    for consumable_note in consumable_notes {
+        let tx_template = TransactionTemplate::ConsumeNotes(account_id, vec![consumable_note.note.id()]);
+        let tx_request = client.build_transaction_request(tx_template).unwrap();
+        execute_tx_and_sync(&mut client, tx_request).await;
+        sleep(Duration::from_secs(5)).await;
+    }
+}
+
+pub async fn send_community_cards(account_id: AccountId, receiver_account_id: AccountId, cards: [[Felt; 4]; 3], phase: u8) {
+    let mut client: AzeClient = create_aze_client();
+    let (player_account, _) = client.get_account(account_id).unwrap();
+
+    // send set cards note to game account
+    let send_cards_data = SendCommunityCardsTransactionData::new(
+        account_id,
+        receiver_account_id,
+        &cards,
+        phase
+    );
+    let transaction_template = AzeTransactionTemplate::SendCommunityCards(send_cards_data);
+    let txn_request = client
+        .build_send_community_cards_tx_request(transaction_template)
+        .unwrap();
+    execute_tx_and_sync(&mut client, txn_request.clone()).await;
+}
+
+// plays a check or fold for the player whose turn it is, from the game account itself
+pub async fn send_timeout_action(game_account_id: AccountId, action_type: ActionType) {
+    let mut client: AzeClient = create_aze_client();
+    let txn_request = match action_type {
+        ActionType::Check => {
+            let playcheck_txn_data = PlayCheckTransactionData::new(
+                game_account_id,
+                game_account_id,
+            );
+            let transaction_template = AzeTransactionTemplate::PlayCheck(playcheck_txn_data);
+            client
+                .build_aze_play_check_tx_request(transaction_template)
+                .unwrap()
+        }
+        _ => {
+            let playfold_txn_data = PlayFoldTransactionData::new(
+                game_account_id,
+                game_account_id,
+            );
+            let transaction_template = AzeTransactionTemplate::PlayFold(playfold_txn_data);
+            client
+                .build_aze_play_fold_tx_request(transaction_template)
+                .unwrap()
+        }
+    };
+    execute_tx_and_sync(&mut client, txn_request.clone()).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accounts::create_basic_aze_game_account;
+    use crate::client::create_mock_aze_client;
+    use crate::mock::MockNode;
+    use miden_client::client::accounts::AccountTemplate;
+    use miden_lib::AuthScheme;
+    use miden_objects::accounts::AccountType;
+    use miden_objects::assets::{AssetVault, FungibleAsset, TokenSymbol};
+    use miden_objects::crypto::dsa::rpo_falcon512::SecretKey;
+
+    fn buy_in_faucet() -> AccountId {
+        let node = MockNode::new();
+        let mut client = create_mock_aze_client(&node);
+        let (faucet, _) = client
+            .new_account(AccountTemplate::FungibleFaucet {
+                token_symbol: TokenSymbol::new("AZE").unwrap(),
+                decimals: 0,
+                max_supply: 1 << 62,
+                storage_mode: AccountStorageMode::Local,
+            })
+            .unwrap();
+        faucet.id()
+    }
+
+    // an open table taking buy-ins of the faucet's asset, with `escrowed` of it in its vault
+    fn game_account(faucet_id: AccountId, escrowed: u64) -> Account {
+        let auth_scheme = AuthScheme::RpoFalcon512 {
+            pub_key: SecretKey::with_rng(&mut rand::thread_rng()).public_key(),
+        };
+        let slot_data = GameStorageSlotData::new(
+            5,
+            100,
+            NO_OF_PLAYERS,
+            FIRST_PLAYER_INDEX,
+            HIGHEST_BET,
+            PLAYER_INITIAL_BALANCE,
+        )
+        .with_seated_players(&[])
+        .with_buy_in_faucet(faucet_id.into());
+        let (account, _) = create_basic_aze_game_account(
+            [0; 32],
+            auth_scheme,
+            AccountType::RegularAccountImmutableCode,
+            slot_data,
+        )
+        .unwrap();
+        let escrow = FungibleAsset::new(faucet_id, escrowed).unwrap();
+        Account::new(
+            account.id(),
+            AssetVault::new(&[escrow.into()]).unwrap(),
+            account.storage().clone(),
+            account.code().clone(),
+            account.nonce(),
+        )
+    }
+
+    fn table_with_stacks(stacks: &[u64]) -> PokerGame {
+        let mut game = PokerGame::with_empty_seats(NO_OF_PLAYERS as usize, 5, 10);
+        for (seat, stack) in stacks.iter().enumerate() {
+            game.join_seat(seat as u64 + 1, seat, *stack).unwrap();
+        }
+        game
+    }
+
+    #[test]
+    fn test_escrow_backs_the_chips() {
+        let faucet_id = buy_in_faucet();
+        let mut game = table_with_stacks(&[100, 100]);
+        assert_eq!(escrow_mismatch_of(&game_account(faucet_id, 200), &game), None);
+
+        let short = escrow_mismatch_of(&game_account(faucet_id, 150), &game).unwrap();
+        assert!(short.contains("200 chips at the table and 0 rake but 150"));
+
+        // seats only change on-chain between hands, so a hand being played is never flagged
+        game.start_hand().unwrap();
+        assert_eq!(escrow_mismatch_of(&game_account(faucet_id, 150), &game), None);
+    }
+
+    #[test]
+    fn test_escrow_of_buy_ins_wider_than_a_byte() {
+        let faucet_id = buy_in_faucet();
+        let buy_in = 1 << 40;
+        let game = table_with_stacks(&[buy_in, buy_in, buy_in]);
+        assert_eq!(escrow_mismatch_of(&game_account(faucet_id, 3 * buy_in), &game), None);
+        assert!(escrow_mismatch_of(&game_account(faucet_id, 3 * buy_in - 1), &game).is_some());
+    }
+}
+
@@ -7,4 +7,12 @@ pub mod client;
 pub mod executor;
 pub mod storage;
 pub mod broadcast;
-pub mod gamestate;
\ No newline at end of file
+pub mod gamestate;
+pub mod protocol;
+pub mod mock;
+pub mod strategy;
+pub mod hands;
+pub mod simulator;
+pub mod auth;
+pub mod host;pub mod lobby;
+pub mod tournament;
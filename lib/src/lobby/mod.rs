@@ -0,0 +1,160 @@
+use crate::constants::NO_OF_PLAYERS;
+use aze_types::lobby::{LobbyUpdate, Stakes};
+use std::collections::HashMap;
+use tokio::sync::mpsc;
+
+// Players queue per stakes until a table is full, the table is then handed to whoever creates
+// game accounts on this server, which seats the players once the account exists.
+
+/// A player waiting for a table, updates go to their lobby connection.
+pub struct LobbyPlayer {
+    pub player_id: u64,
+    pub identifier: String,
+    updates: mpsc::UnboundedSender<LobbyUpdate>,
+}
+
+impl LobbyPlayer {
+    pub fn new(
+        player_id: u64,
+        identifier: String,
+        updates: mpsc::UnboundedSender<LobbyUpdate>,
+    ) -> Self {
+        Self {
+            player_id,
+            identifier,
+            updates,
+        }
+    }
+
+    pub fn notify(&self, update: LobbyUpdate) {
+        // a player who left simply misses the update
+        let _ = self.updates.send(update);
+    }
+}
+
+/// Players the lobby put together, in the order they joined.
+pub struct FormedTable {
+    pub stakes: Stakes,
+    pub players: Vec<LobbyPlayer>,
+}
+
+impl FormedTable {
+    pub fn player_ids(&self) -> Vec<u64> {
+        self.players.iter().map(|player| player.player_id).collect()
+    }
+
+    pub fn notify(&self, update: LobbyUpdate) {
+        for player in self.players.iter() {
+            player.notify(update.clone());
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct Lobby {
+    waiting: HashMap<Stakes, Vec<LobbyPlayer>>,
+}
+
+impl Lobby {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Queues the player, replacing an earlier entry of theirs, and returns the table once
+    /// enough players wait at the same stakes.
+    pub fn join(&mut self, stakes: Stakes, player: LobbyPlayer) -> Option<FormedTable> {
+        self.leave(player.player_id);
+        let queue = self.waiting.entry(stakes).or_default();
+        queue.push(player);
+        if queue.len() < NO_OF_PLAYERS as usize {
+            let update = LobbyUpdate::Waiting {
+                waiting: queue.len(),
+                needed: NO_OF_PLAYERS as usize,
+            };
+            for player in queue.iter() {
+                player.notify(update.clone());
+            }
+            return None;
+        }
+
+        let players = queue.drain(..NO_OF_PLAYERS as usize).collect();
+        Some(FormedTable { stakes, players })
+    }
+
+    pub fn leave(&mut self, player_id: u64) {
+        for queue in self.waiting.values_mut() {
+            queue.retain(|player| player.player_id != player_id);
+        }
+        self.waiting.retain(|_, queue| !queue.is_empty());
+    }
+
+    pub fn waiting(&self, stakes: &Stakes) -> usize {
+        self.waiting.get(stakes).map_or(0, |queue| queue.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player(player_id: u64) -> (LobbyPlayer, mpsc::UnboundedReceiver<LobbyUpdate>) {
+        let (updates, receiver) = mpsc::unbounded_channel();
+        (
+            LobbyPlayer::new(player_id, format!("player{}", player_id), updates),
+            receiver,
+        )
+    }
+
+    #[test]
+    fn test_tables_form_per_stakes() {
+        let low: Stakes = "5/10".parse().unwrap();
+        let high: Stakes = "10/20".parse().unwrap();
+        let mut lobby = Lobby::new();
+
+        let (first, mut first_updates) = player(1);
+        assert!(lobby.join(low, first).is_none());
+        assert_eq!(
+            first_updates.try_recv(),
+            Ok(LobbyUpdate::Waiting {
+                waiting: 1,
+                needed: NO_OF_PLAYERS as usize
+            })
+        );
+        assert!(lobby.join(high, player(2).0).is_none());
+        // joining again doesn't take a second seat
+        assert!(lobby.join(low, player(1).0).is_none());
+        assert_eq!(lobby.waiting(&low), 1);
+
+        for player_id in 3..(NO_OF_PLAYERS as u64 + 1) {
+            assert!(lobby.join(low, player(player_id).0).is_none());
+        }
+        let table = lobby.join(low, player(100).0).unwrap();
+        assert_eq!(table.stakes, low);
+        assert_eq!(table.player_ids(), vec![1, 3, 4, 100]);
+        assert_eq!(table.players.len(), NO_OF_PLAYERS as usize);
+        assert_eq!(lobby.waiting(&low), 0);
+        assert_eq!(lobby.waiting(&high), 1);
+    }
+
+    #[test]
+    fn test_leave() {
+        let stakes: Stakes = "5/10".parse().unwrap();
+        let mut lobby = Lobby::new();
+        lobby.join(stakes, player(1).0);
+        lobby.leave(1);
+        assert_eq!(lobby.waiting(&stakes), 0);
+    }
+
+    #[test]
+    fn test_parse_stakes() {
+        assert_eq!(
+            "5/10".parse::<Stakes>(),
+            Ok(Stakes {
+                small_blind: 5,
+                big_blind: 10
+            })
+        );
+        assert!("5/15".parse::<Stakes>().is_err());
+        assert!("5".parse::<Stakes>().is_err());
+    }
+}
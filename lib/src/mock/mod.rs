@@ -0,0 +1,362 @@
+use miden_client::{
+    client::rpc::{
+        AccountDetails, CommittedNote, NodeRpcClient, NoteDetails, NoteInclusionDetails,
+        StateSyncInfo, TransactionUpdate,
+    },
+    errors::NodeRpcClientError,
+};
+use miden_objects::{
+    accounts::AccountId,
+    block::{BlockNoteIndex, BlockNoteTree},
+    crypto::merkle::{Mmr, MmrProof},
+    notes::{Note, NoteId, NoteMetadata, NoteTag},
+    transaction::{OutputNote, ProvenTransaction, TransactionId},
+    BlockHeader, Digest,
+};
+use std::{cell::RefCell, rc::Rc};
+
+// In-process stand-in for the miden node, so full hands can be played in `cargo test` without a
+// network. Every submitted transaction is put in its own block right away, which is enough for
+// `execute_tx_and_sync` to see it committed on the next sync.
+
+struct MockNote {
+    id: NoteId,
+    metadata: NoteMetadata,
+    // only public notes carry their details
+    note: Option<Note>,
+    index: BlockNoteIndex,
+}
+
+struct MockBlock {
+    header: BlockHeader,
+    notes: Vec<MockNote>,
+    note_tree: BlockNoteTree,
+    nullifiers: Vec<Digest>,
+    account_updates: Vec<(AccountId, Digest)>,
+    transactions: Vec<(TransactionId, AccountId)>,
+}
+
+struct MockChain {
+    blocks: Vec<MockBlock>,
+    chain_mmr: Mmr,
+}
+
+impl MockChain {
+    fn new() -> Self {
+        let mut chain = MockChain {
+            blocks: vec![],
+            chain_mmr: Mmr::new(),
+        };
+        chain.add_block(vec![], vec![], vec![], vec![]);
+        chain
+    }
+
+    fn chain_tip(&self) -> u32 {
+        (self.blocks.len() - 1) as u32
+    }
+
+    fn add_block(
+        &mut self,
+        notes: Vec<(NoteId, NoteMetadata, Option<Note>)>,
+        nullifiers: Vec<Digest>,
+        account_updates: Vec<(AccountId, Digest)>,
+        transactions: Vec<(TransactionId, AccountId)>,
+    ) {
+        let block_num = self.blocks.len() as u32;
+
+        let notes: Vec<MockNote> = notes
+            .into_iter()
+            .enumerate()
+            .map(|(i, (id, metadata, note))| MockNote {
+                id,
+                metadata,
+                note,
+                index: BlockNoteIndex::new(0, i),
+            })
+            .collect();
+        let note_tree = BlockNoteTree::with_entries(
+            notes
+                .iter()
+                .map(|note| (note.index, note.id.into(), note.metadata)),
+        )
+        .unwrap();
+
+        // the chain root of a block commits to all the blocks before it
+        let chain_root = self
+            .chain_mmr
+            .peaks(self.chain_mmr.forest())
+            .unwrap()
+            .hash_peaks();
+        let header = BlockHeader::mock(block_num, Some(chain_root), Some(note_tree.root()), &[]);
+        self.chain_mmr.add(header.hash());
+
+        self.blocks.push(MockBlock {
+            header,
+            notes,
+            note_tree,
+            nullifiers,
+            account_updates,
+            transactions,
+        });
+    }
+
+    fn submit(&mut self, proven_transaction: ProvenTransaction) {
+        let notes = proven_transaction
+            .output_notes()
+            .iter()
+            .map(|output_note| match output_note {
+                OutputNote::Public(note) => (note.id(), *note.metadata(), Some(note.clone())),
+                OutputNote::Private(header) => (header.id(), *header.metadata(), None),
+            })
+            .collect();
+        let nullifiers = proven_transaction
+            .input_notes()
+            .iter()
+            .map(|nullifier| nullifier.inner())
+            .collect();
+        let account_updates = vec![(
+            proven_transaction.account_id(),
+            proven_transaction.account_update().final_state_hash(),
+        )];
+        let transactions = vec![(proven_transaction.id(), proven_transaction.account_id())];
+
+        self.add_block(notes, nullifiers, account_updates, transactions);
+    }
+
+    fn note_inclusion(&self, block_num: u32, note: &MockNote) -> NoteInclusionDetails {
+        let block = &self.blocks[block_num as usize];
+        NoteInclusionDetails::new(
+            block_num,
+            note.index.to_absolute_index() as u32,
+            block.note_tree.get_note_path(note.index).unwrap(),
+        )
+    }
+
+    fn sync_state(
+        &self,
+        block_num: u32,
+        account_ids: &[AccountId],
+        note_tags: &[NoteTag],
+        nullifiers_tags: &[u16],
+    ) -> StateSyncInfo {
+        let is_relevant_note = |note: &MockNote| note_tags.contains(&note.metadata.tag());
+        let is_relevant_nullifier =
+            |nullifier: &Digest| nullifiers_tags.contains(&nullifier_prefix(nullifier));
+
+        // stop at the first block the client cares about, like the node does
+        let target_block = (block_num + 1..=self.chain_tip())
+            .find(|num| {
+                let block = &self.blocks[*num as usize];
+                block.notes.iter().any(is_relevant_note)
+                    || block.nullifiers.iter().any(is_relevant_nullifier)
+            })
+            .unwrap_or(self.chain_tip());
+
+        let mut account_hash_updates = vec![];
+        let mut nullifiers = vec![];
+        let mut transactions = vec![];
+        for num in block_num + 1..=target_block {
+            let block = &self.blocks[num as usize];
+            account_hash_updates.extend(
+                block
+                    .account_updates
+                    .iter()
+                    .filter(|(account_id, _)| account_ids.contains(account_id))
+                    .cloned(),
+            );
+            nullifiers.extend(
+                block
+                    .nullifiers
+                    .iter()
+                    .filter(|nullifier| is_relevant_nullifier(nullifier))
+                    .cloned(),
+            );
+            transactions.extend(
+                block
+                    .transactions
+                    .iter()
+                    .filter(|(_, account_id)| account_ids.contains(account_id))
+                    .map(|(transaction_id, account_id)| TransactionUpdate {
+                        transaction_id: *transaction_id,
+                        block_num: num,
+                        account_id: *account_id,
+                    }),
+            );
+        }
+
+        let block = &self.blocks[target_block as usize];
+        let note_inclusions = block
+            .notes
+            .iter()
+            .filter(|note| is_relevant_note(note))
+            .map(|note| {
+                CommittedNote::new(
+                    note.id,
+                    note.index.to_absolute_index() as u32,
+                    block.note_tree.get_note_path(note.index).unwrap(),
+                    note.metadata,
+                )
+            })
+            .collect();
+
+        let mmr_delta = self
+            .chain_mmr
+            .get_delta((block_num + 1) as usize, target_block as usize)
+            .unwrap();
+
+        StateSyncInfo {
+            chain_tip: self.chain_tip(),
+            block_header: block.header,
+            mmr_delta,
+            account_hash_updates,
+            note_inclusions,
+            nullifiers,
+            transactions,
+        }
+    }
+}
+
+fn nullifier_prefix(nullifier: &Digest) -> u16 {
+    (nullifier[3].as_int() >> 48) as u16
+}
+
+/// Handle to a mock node shared by every client created from it.
+#[derive(Clone)]
+pub struct MockNode {
+    chain: Rc<RefCell<MockChain>>,
+}
+
+impl MockNode {
+    pub fn new() -> Self {
+        Self {
+            chain: Rc::new(RefCell::new(MockChain::new())),
+        }
+    }
+
+    pub fn rpc_client(&self) -> MockRpcClient {
+        MockRpcClient {
+            chain: self.chain.clone(),
+        }
+    }
+
+    pub fn chain_tip(&self) -> u32 {
+        self.chain.borrow().chain_tip()
+    }
+}
+
+impl Default for MockNode {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub struct MockRpcClient {
+    chain: Rc<RefCell<MockChain>>,
+}
+
+impl NodeRpcClient for MockRpcClient {
+    async fn submit_proven_transaction(
+        &mut self,
+        proven_transaction: ProvenTransaction,
+    ) -> Result<(), NodeRpcClientError> {
+        self.chain.borrow_mut().submit(proven_transaction);
+        Ok(())
+    }
+
+    async fn get_block_header_by_number(
+        &mut self,
+        block_num: Option<u32>,
+        include_mmr_proof: bool,
+    ) -> Result<(BlockHeader, Option<MmrProof>), NodeRpcClientError> {
+        let chain = self.chain.borrow();
+        let block_num = block_num.unwrap_or(chain.chain_tip());
+        let block = chain.blocks.get(block_num as usize).ok_or_else(|| {
+            NodeRpcClientError::RequestError(
+                "get_block_header_by_number".to_string(),
+                format!("block {} not found", block_num),
+            )
+        })?;
+
+        let mmr_proof = if include_mmr_proof {
+            Some(
+                chain
+                    .chain_mmr
+                    .open(block_num as usize, chain.chain_mmr.forest())
+                    .unwrap(),
+            )
+        } else {
+            None
+        };
+
+        Ok((block.header, mmr_proof))
+    }
+
+    async fn get_notes_by_id(
+        &mut self,
+        note_ids: &[NoteId],
+    ) -> Result<Vec<NoteDetails>, NodeRpcClientError> {
+        let chain = self.chain.borrow();
+        let mut notes = vec![];
+        for (block_num, block) in chain.blocks.iter().enumerate() {
+            for note in block.notes.iter().filter(|note| note_ids.contains(&note.id)) {
+                let inclusion = chain.note_inclusion(block_num as u32, note);
+                notes.push(match &note.note {
+                    Some(details) => NoteDetails::Public(details.clone(), inclusion),
+                    None => NoteDetails::OffChain(note.id, note.metadata, inclusion),
+                });
+            }
+        }
+        Ok(notes)
+    }
+
+    async fn sync_state(
+        &mut self,
+        block_num: u32,
+        account_ids: &[AccountId],
+        note_tags: &[NoteTag],
+        nullifiers_tags: &[u16],
+    ) -> Result<StateSyncInfo, NodeRpcClientError> {
+        Ok(self
+            .chain
+            .borrow()
+            .sync_state(block_num, account_ids, note_tags, nullifiers_tags))
+    }
+
+    async fn get_account_update(
+        &mut self,
+        account_id: AccountId,
+    ) -> Result<AccountDetails, NodeRpcClientError> {
+        // aze accounts are all local for now, the mock node doesn't keep account states
+        Err(NodeRpcClientError::RequestError(
+            "get_account_update".to_string(),
+            format!("account {} is not public", account_id),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_genesis_block() {
+        let node = MockNode::new();
+        assert_eq!(node.chain_tip(), 0);
+    }
+
+    #[test]
+    fn test_sync_to_chain_tip_without_relevant_blocks() {
+        let node = MockNode::new();
+        node.chain
+            .borrow_mut()
+            .add_block(vec![], vec![], vec![], vec![]);
+        node.chain
+            .borrow_mut()
+            .add_block(vec![], vec![], vec![], vec![]);
+
+        let sync_info = node.chain.borrow().sync_state(0, &[], &[], &[]);
+        assert_eq!(sync_info.chain_tip, 2);
+        assert_eq!(sync_info.block_header.block_num(), 2);
+        assert!(sync_info.note_inclusions.is_empty());
+    }
+}
@@ -1,5 +1,5 @@
 use crate::client::AzeClient;
-use crate::constants::{ BUY_IN_AMOUNT, TRANSFER_AMOUNT, FLOP_NO_OF_CARDS };
+use crate::constants::{ TRANSFER_AMOUNT, FLOP_NO_OF_CARDS, MAX_HOLE_CARDS, NO_OF_PLAYERS };
 use crate::executor::execute_tx_and_sync;
 use miden_client::client::Client;
 use miden_client::{
@@ -36,17 +36,14 @@ pub fn create_send_card_note<
     target_account_id: AccountId,
     note_type: NoteType,
     mut rng: RpoRandomCoin,
-    cards: [[Felt; 4]; 2],
+    cards: [[Felt; 4]; MAX_HOLE_CARDS],
 ) -> Result<Note, NoteError> {
     let note_script = include_str!("../../contracts/notes/game/deal.masm");
     // TODO: hide it under feature flag debug (.with_debug_mode(true))
     let script_ast = ProgramAst::parse(note_script).unwrap();
     let note_script = client.compile_note_script(script_ast, vec![]).unwrap();
 
-    let card_1 = cards[0];
-    let card_2 = cards[1];
-
-    let mut inputs = [card_1.as_slice(), card_2.as_slice()].concat();
+    let inputs = cards.concat();
     
 
     let note_inputs = NoteInputs::new(inputs).unwrap();
@@ -269,6 +266,267 @@ pub fn create_play_fold_note<
     Ok(Note::new(vault, metadata, recipient))
 }
 
+pub fn create_start_hand_note<
+    R: FeltRng,
+    N: NodeRpcClient,
+    S: Store,
+    A: TransactionAuthenticator,
+>(
+    client: &mut Client<N, R, S, A>,
+    sender_account_id: AccountId,
+    target_account_id: AccountId,
+    note_type: NoteType,
+    mut rng: RpoRandomCoin,
+    small_blind: u64,
+    big_blind: u64,
+) -> Result<Note, NoteError> {
+    let note_script = include_str!("../../contracts/notes/game/start_hand.masm");
+    let script_ast = ProgramAst::parse(note_script).unwrap();
+    let note_script = client.compile_note_script(script_ast, vec![]).unwrap();
+
+    // the blinds of the hand being dealt, they go up between tournament levels
+    let note_inputs = NoteInputs::new(vec![Felt::new(big_blind), Felt::new(small_blind)]).unwrap();
+    let tag = NoteTag::from_account_id(target_account_id, NoteExecutionHint::Local)?;
+    let serial_num = rng.draw_word();
+    let aux = ZERO;
+
+    let metadata = NoteMetadata::new(sender_account_id, NoteType::Public, tag, aux)?;
+    let vault = NoteAssets::new(vec![])?;
+    let recipient = NoteRecipient::new(serial_num, note_script, note_inputs);
+
+    Ok(Note::new(vault, metadata, recipient))
+}
+
+pub fn create_join_seat_note<
+    R: FeltRng,
+    N: NodeRpcClient,
+    S: Store,
+    A: TransactionAuthenticator,
+>(
+    client: &mut Client<N, R, S, A>,
+    sender_account_id: AccountId,
+    target_account_id: AccountId,
+    note_type: NoteType,
+    mut rng: RpoRandomCoin,
+    seat: u8,
+    buy_in: FungibleAsset,
+) -> Result<Note, NoteError> {
+    let note_script = include_str!("../../contracts/notes/game/join_seat.masm");
+    let script_ast = ProgramAst::parse(note_script).unwrap();
+    let note_script = client.compile_note_script(script_ast, vec![]).unwrap();
+
+    // the sender of the note takes the seat, buying in with the asset it carries
+    let inputs = vec![Felt::from(seat)];
+    let note_inputs = NoteInputs::new(inputs).unwrap();
+    let tag = NoteTag::from_account_id(target_account_id, NoteExecutionHint::Local)?;
+    let serial_num = rng.draw_word();
+    let aux = ZERO;
+
+    let metadata = NoteMetadata::new(sender_account_id, NoteType::Public, tag, aux)?;
+    let vault = NoteAssets::new(vec![buy_in.into()])?;
+    let recipient = NoteRecipient::new(serial_num, note_script, note_inputs);
+
+    Ok(Note::new(vault, metadata, recipient))
+}
+
+pub fn create_leave_seat_note<
+    R: FeltRng,
+    N: NodeRpcClient,
+    S: Store,
+    A: TransactionAuthenticator,
+>(
+    client: &mut Client<N, R, S, A>,
+    sender_account_id: AccountId,
+    target_account_id: AccountId,
+    note_type: NoteType,
+    mut rng: RpoRandomCoin,
+    seat: u8,
+) -> Result<Note, NoteError> {
+    let note_script = include_str!("../../contracts/notes/game/leave_seat.masm");
+    let script_ast = ProgramAst::parse(note_script).unwrap();
+    let note_script = client.compile_note_script(script_ast, vec![]).unwrap();
+
+    let inputs = vec![Felt::from(seat)];
+    let note_inputs = NoteInputs::new(inputs).unwrap();
+    let tag = NoteTag::from_account_id(target_account_id, NoteExecutionHint::Local)?;
+    let serial_num = rng.draw_word();
+    let aux = ZERO;
+
+    let metadata = NoteMetadata::new(sender_account_id, NoteType::Public, tag, aux)?;
+    let vault = NoteAssets::new(vec![])?;
+    let recipient = NoteRecipient::new(serial_num, note_script, note_inputs);
+
+    Ok(Note::new(vault, metadata, recipient))
+}
+
+pub fn create_sit_out_note<
+    R: FeltRng,
+    N: NodeRpcClient,
+    S: Store,
+    A: TransactionAuthenticator,
+>(
+    client: &mut Client<N, R, S, A>,
+    sender_account_id: AccountId,
+    target_account_id: AccountId,
+    note_type: NoteType,
+    mut rng: RpoRandomCoin,
+    seat: u8,
+    sitting_out: bool,
+) -> Result<Note, NoteError> {
+    let note_script = include_str!("../../contracts/notes/game/sit_out.masm");
+    let script_ast = ProgramAst::parse(note_script).unwrap();
+    let note_script = client.compile_note_script(script_ast, vec![]).unwrap();
+
+    let inputs = vec![Felt::from(sitting_out as u8), Felt::from(seat)];
+    let note_inputs = NoteInputs::new(inputs).unwrap();
+    let tag = NoteTag::from_account_id(target_account_id, NoteExecutionHint::Local)?;
+    let serial_num = rng.draw_word();
+    let aux = ZERO;
+
+    let metadata = NoteMetadata::new(sender_account_id, NoteType::Public, tag, aux)?;
+    let vault = NoteAssets::new(vec![])?;
+    let recipient = NoteRecipient::new(serial_num, note_script, note_inputs);
+
+    Ok(Note::new(vault, metadata, recipient))
+}
+
+pub fn create_end_hand_note<
+    R: FeltRng,
+    N: NodeRpcClient,
+    S: Store,
+    A: TransactionAuthenticator,
+>(
+    client: &mut Client<N, R, S, A>,
+    sender_account_id: AccountId,
+    target_account_id: AccountId,
+    note_type: NoteType,
+    mut rng: RpoRandomCoin,
+    pot: u64,
+    winnings: [u64; NO_OF_PLAYERS as usize],
+) -> Result<Note, NoteError> {
+    let note_script = include_str!("../../contracts/notes/game/end_hand.masm");
+    let script_ast = ProgramAst::parse(note_script).unwrap();
+    let note_script = client.compile_note_script(script_ast, vec![]).unwrap();
+
+    // loaded as a word, so the last seat goes first to end up at the bottom of the stack. The
+    // game account takes its rake from the pot and checks the winnings add up to the rest
+    let mut inputs: Vec<Felt> = winnings.iter().rev().map(|amount| Felt::new(*amount)).collect();
+    inputs.push(Felt::new(pot));
+    let note_inputs = NoteInputs::new(inputs).unwrap();
+    let tag = NoteTag::from_account_id(target_account_id, NoteExecutionHint::Local)?;
+    let serial_num = rng.draw_word();
+    let aux = ZERO;
+
+    let metadata = NoteMetadata::new(sender_account_id, NoteType::Public, tag, aux)?;
+    let vault = NoteAssets::new(vec![])?;
+    let recipient = NoteRecipient::new(serial_num, note_script, note_inputs);
+
+    Ok(Note::new(vault, metadata, recipient))
+}
+
+/// Pays the assets to the target account, which consumes the note into its vault.
+pub fn create_p2id_note<
+    R: FeltRng,
+    N: NodeRpcClient,
+    S: Store,
+    A: TransactionAuthenticator,
+>(
+    client: &mut Client<N, R, S, A>,
+    sender_account_id: AccountId,
+    target_account_id: AccountId,
+    assets: Vec<Asset>,
+    note_type: NoteType,
+    mut rng: RpoRandomCoin,
+) -> Result<Note, NoteError> {
+    let note_script = include_str!("../../asm/note_scripts/P2ID.masm");
+    let script_ast = ProgramAst::parse(note_script).unwrap();
+    let note_script = client.compile_note_script(script_ast, vec![]).unwrap();
+
+    let note_inputs = NoteInputs::new(vec![target_account_id.into()]).unwrap();
+    let tag = NoteTag::from_account_id(target_account_id, NoteExecutionHint::Local)?;
+    let serial_num = rng.draw_word();
+    let aux = ZERO;
+
+    let metadata = NoteMetadata::new(sender_account_id, note_type, tag, aux)?;
+    let vault = NoteAssets::new(assets)?;
+    let recipient = NoteRecipient::new(serial_num, note_script, note_inputs);
+
+    Ok(Note::new(vault, metadata, recipient))
+}
+
+pub fn create_cash_out_note<
+    R: FeltRng,
+    N: NodeRpcClient,
+    S: Store,
+    A: TransactionAuthenticator,
+>(
+    client: &mut Client<N, R, S, A>,
+    sender_account_id: AccountId,
+    target_account_id: AccountId,
+    note_type: NoteType,
+    mut rng: RpoRandomCoin,
+    seat: u8,
+    payout: &Note,
+) -> Result<Note, NoteError> {
+    let note_script = include_str!("../../contracts/notes/game/cash_out.masm");
+    let script_ast = ProgramAst::parse(note_script).unwrap();
+    let note_script = client.compile_note_script(script_ast, vec![]).unwrap();
+
+    // the game account emits the payout note with this recipient, the amount is up to the
+    // balance left on the seat
+    let mut inputs: Vec<Felt> = payout.recipient().digest().iter().copied().collect();
+    inputs.extend([
+        Felt::from(payout.metadata().note_type() as u8),
+        Felt::from(payout.metadata().tag().inner()),
+        Felt::from(seat),
+    ]);
+    let note_inputs = NoteInputs::new(inputs).unwrap();
+    let tag = NoteTag::from_account_id(target_account_id, NoteExecutionHint::Local)?;
+    let serial_num = rng.draw_word();
+    let aux = ZERO;
+
+    let metadata = NoteMetadata::new(sender_account_id, NoteType::Public, tag, aux)?;
+    let vault = NoteAssets::new(vec![])?;
+    let recipient = NoteRecipient::new(serial_num, note_script, note_inputs);
+
+    Ok(Note::new(vault, metadata, recipient))
+}
+
+pub fn create_withdraw_rake_note<
+    R: FeltRng,
+    N: NodeRpcClient,
+    S: Store,
+    A: TransactionAuthenticator,
+>(
+    client: &mut Client<N, R, S, A>,
+    sender_account_id: AccountId,
+    target_account_id: AccountId,
+    note_type: NoteType,
+    mut rng: RpoRandomCoin,
+    payout: &Note,
+) -> Result<Note, NoteError> {
+    let note_script = include_str!("../../contracts/notes/game/withdraw_rake.masm");
+    let script_ast = ProgramAst::parse(note_script).unwrap();
+    let note_script = client.compile_note_script(script_ast, vec![]).unwrap();
+
+    // the game account emits the payout note with this recipient, carrying the collected rake
+    let mut inputs: Vec<Felt> = payout.recipient().digest().iter().copied().collect();
+    inputs.extend([
+        Felt::from(payout.metadata().note_type() as u8),
+        Felt::from(payout.metadata().tag().inner()),
+    ]);
+    let note_inputs = NoteInputs::new(inputs).unwrap();
+    let tag = NoteTag::from_account_id(target_account_id, NoteExecutionHint::Local)?;
+    let serial_num = rng.draw_word();
+    let aux = ZERO;
+
+    let metadata = NoteMetadata::new(sender_account_id, NoteType::Public, tag, aux)?;
+    let vault = NoteAssets::new(vec![])?;
+    let recipient = NoteRecipient::new(serial_num, note_script, note_inputs);
+
+    Ok(Note::new(vault, metadata, recipient))
+}
+
 pub fn create_play_check_note<
     R: FeltRng,
     N: NodeRpcClient,
@@ -344,7 +602,7 @@ pub fn create_set_community_cards_note<
     target_account_id: AccountId,
     note_type: NoteType,
     mut rng: RpoRandomCoin,
-    cards: [[Felt; 4]; 3],
+    cards: [[Felt; 4]; MAX_HOLE_CARDS],
     card_slot: u8,
 ) -> Result<Note, NoteError> {
     let note_script = include_str!("../../contracts/notes/game/set_community_cards.masm");
@@ -375,7 +633,7 @@ pub fn create_send_unmasked_cards_note<R: FeltRng, N: NodeRpcClient, S: Store, A
     target_account_id: AccountId,
     note_type: NoteType,
     mut rng: RpoRandomCoin,
-    cards: [[Felt; 4]; 3],
+    cards: [[Felt; 4]; MAX_HOLE_CARDS],
 ) -> Result<Note, NoteError> {
     let note_script = include_str!("../../contracts/notes/game/send_unmasked_cards.masm");
     let script_ast = ProgramAst::parse(note_script).unwrap();
@@ -404,7 +662,7 @@ pub fn create_unmask_note<R: FeltRng, N: NodeRpcClient, S: Store, A: Transaction
     target_account_id: AccountId,
     note_type: NoteType,
     mut rng: RpoRandomCoin,
-    cards: [[Felt; 4]; 3],
+    cards: [[Felt; 4]; MAX_HOLE_CARDS],
     card_slot: u8,
 ) -> Result<Note, NoteError> {
     let note_script = include_str!("../../contracts/notes/game/unmask.masm");
@@ -441,7 +699,7 @@ pub fn create_inter_unmask_note<
     target_account_id: AccountId,
     note_type: NoteType,
     mut rng: RpoRandomCoin,
-    cards: [[Felt; 4]; 3],
+    cards: [[Felt; 4]; MAX_HOLE_CARDS],
     requester_id: AccountId,
 ) -> Result<Note, NoteError> {
     let note_script = include_str!("../../contracts/notes/game/inter_unmask.masm");
@@ -478,7 +736,7 @@ pub fn create_set_hand_note<
     target_account_id: AccountId,
     note_type: NoteType,
     mut rng: RpoRandomCoin,
-    cards: [[Felt; 4]; 2],
+    cards: [[Felt; 4]; MAX_HOLE_CARDS],
     player_hand: u8,
     player_index: u8,
 ) -> Result<Note, NoteError> {
@@ -486,7 +744,10 @@ pub fn create_set_hand_note<
     let script_ast = ProgramAst::parse(note_script).unwrap();
     let note_script = client.compile_note_script(script_ast, vec![]).unwrap();
 
-    let inputs = vec![cards[0][0], cards[1][0], Felt::from(player_hand), Felt::from(player_index)];
+    let inputs = vec![
+        cards[0][0], cards[1][0], Felt::from(player_hand), Felt::from(player_index),
+        cards[2][0], cards[3][0], Felt::ZERO, Felt::ZERO,
+    ];
 
     let note_inputs = NoteInputs::new(inputs).unwrap();
     let tag = NoteTag::from_account_id(target_account_id, NoteExecutionHint::Local)?;
@@ -536,17 +797,17 @@ pub fn create_send_community_cards_note<
     Ok(Note::new(vault, metadata, recipient))
 }
 
-// TODO: remove this function after testing
-pub async fn mint_note(
-    client: &mut AzeClient,
+pub async fn mint_note<N: NodeRpcClient>(
+    client: &mut AzeClient<N>,
     basic_account_id: AccountId,
     faucet_account_id: AccountId,
+    amount: u64,
     note_type: NoteType,
 ) -> InputNote {
     let (regular_account, _seed) = client.get_account(basic_account_id).unwrap();
 
-    // Create a Mint Tx for 1000 units of our fungible asset
-    let fungible_asset = FungibleAsset::new(faucet_account_id, BUY_IN_AMOUNT).unwrap();
+    // Create a Mint Tx for `amount` units of our fungible asset
+    let fungible_asset = FungibleAsset::new(faucet_account_id, amount).unwrap();
     let tx_template =
         TransactionTemplate::MintFungibleAsset(fungible_asset, basic_account_id, note_type);
 
@@ -560,9 +821,8 @@ pub async fn mint_note(
     let note = client.get_input_note(note_id).unwrap();
     note.try_into().unwrap()
 }
-// TODO: remove it after testing the flow
-pub async fn consume_notes(
-    client: &mut AzeClient,
+pub async fn consume_notes<N: NodeRpcClient>(
+    client: &mut AzeClient<N>,
     account_id: AccountId,
     input_notes: &[InputNote],
 ) {
@@ -0,0 +1,319 @@
+use crate::constants::{
+    FLOP_SLOT, MAX_HOLE_CARDS, NO_OF_PLAYERS, PLAYER_DATA_SLOT, REQUESTER_SLOT, TEMP_CARD_SLOT,
+};
+use miden_objects::{accounts::Account, Felt, FieldElement, Word};
+
+// Player side of the mental poker protocol.
+//
+// The player account keeps a single `action_type` counter in `PLAYER_DATA_SLOT`. During the
+// masking round it holds the player's seat (1..=NO_OF_PLAYERS), afterwards every completed
+// inter-unmask round trip adds NO_OF_PLAYERS to it (see `increment_action_type` in
+// send_unmasked_cards.masm). So one unmasking stage (hole cards, flop, turn, river) spans
+// NO_OF_PLAYERS * (NO_OF_PLAYERS - 1) values of the counter.
+
+/// Number of `action_type` values covered by one unmasking stage.
+pub const UNMASK_STAGE_LEN: u64 = NO_OF_PLAYERS as u64 * (NO_OF_PLAYERS as u64 - 1);
+
+/// Number of inter-unmask round trips needed before a player can unmask cards on its own.
+pub const UNMASK_ROUNDS: u8 = NO_OF_PLAYERS - 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CardStage {
+    HoleCards,
+    Flop,
+    Turn,
+    River,
+}
+
+impl CardStage {
+    fn from_index(index: u64) -> Option<Self> {
+        match index {
+            0 => Some(CardStage::HoleCards),
+            1 => Some(CardStage::Flop),
+            2 => Some(CardStage::Turn),
+            3 => Some(CardStage::River),
+            _ => None,
+        }
+    }
+
+    /// Game account slot the unmasked cards of this stage are written to.
+    pub fn community_card_slot(&self) -> Option<u8> {
+        match self {
+            CardStage::HoleCards => None,
+            CardStage::Flop => Some(FLOP_SLOT),
+            CardStage::Turn => Some(FLOP_SLOT + 3),
+            CardStage::River => Some(FLOP_SLOT + 4),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayerState {
+    /// No shuffle note consumed yet.
+    Idle,
+    /// The deck was masked by `seat` and has to be forwarded to the next player.
+    Masking { seat: u8 },
+    /// The last player masked the deck, it goes back to the game account.
+    MaskingComplete,
+    /// Waiting on the other players to strip their masking layer off the cards.
+    Unmasking { stage: CardStage, round: u8 },
+    /// All other layers are gone, the player can unmask the cards with its own key.
+    Revealing { stage: CardStage },
+    /// The river has been revealed, nothing left to unmask for this hand.
+    Finished,
+}
+
+impl PlayerState {
+    pub fn from_action_type(action_type: u64) -> Self {
+        let no_of_players = NO_OF_PLAYERS as u64;
+        if action_type == 0 {
+            return PlayerState::Idle;
+        }
+        if action_type < no_of_players {
+            return PlayerState::Masking {
+                seat: action_type as u8,
+            };
+        }
+        if action_type == no_of_players {
+            return PlayerState::MaskingComplete;
+        }
+
+        let offset = action_type - 1;
+        let stage_index = offset / UNMASK_STAGE_LEN;
+        let round = (offset % UNMASK_STAGE_LEN) / no_of_players;
+
+        if round == 0 {
+            // the first slot of a stage reveals the cards of the previous one
+            match CardStage::from_index(stage_index - 1) {
+                Some(stage) => PlayerState::Revealing { stage },
+                None => PlayerState::Finished,
+            }
+        } else {
+            match CardStage::from_index(stage_index) {
+                Some(stage) => PlayerState::Unmasking {
+                    stage,
+                    round: round as u8,
+                },
+                None => PlayerState::Finished,
+            }
+        }
+    }
+}
+
+/// What the player client has to do next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtocolStep {
+    Wait,
+    /// Remask the deck and send it to the player stored at `player_index` of the player data.
+    Remask { player_index: usize },
+    /// Send the fully masked deck to the game account.
+    SendDeckToGame,
+    /// Ask the next player to remove its masking layer from the cards.
+    InterUnmask,
+    /// Unmask the hole cards with the player's own key.
+    RevealHoleCards,
+    /// Unmask the community cards and set them on the game account at `card_slot`.
+    RevealCommunityCards { card_slot: u8 },
+    /// Community cards were dealt to this player, start unmasking them.
+    StartCommunityUnmask,
+    /// Send the partially unmasked cards back to the requester.
+    SendUnmaskedCards { requester_id: u64 },
+}
+
+/// The parts of the player account storage the protocol reacts to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlayerSnapshot {
+    pub action_type: u64,
+    pub requester_id: u64,
+    pub temp_cards: [Word; MAX_HOLE_CARDS],
+}
+
+impl PlayerSnapshot {
+    pub fn from_account(player_account: &Account) -> Self {
+        let storage = player_account.storage();
+        let mut temp_cards = [[Felt::ZERO; 4]; MAX_HOLE_CARDS];
+        for (i, slot) in (TEMP_CARD_SLOT..TEMP_CARD_SLOT + MAX_HOLE_CARDS as u8).enumerate() {
+            temp_cards[i] = storage.get_item(slot).into();
+        }
+
+        Self {
+            action_type: storage.get_item(PLAYER_DATA_SLOT).as_elements()[0].as_int(),
+            requester_id: storage.get_item(REQUESTER_SLOT).as_elements()[0].as_int(),
+            temp_cards,
+        }
+    }
+
+    pub fn state(&self) -> PlayerState {
+        PlayerState::from_action_type(self.action_type)
+    }
+}
+
+/// Compares the player account before and after consuming notes and returns the steps to take,
+/// in order.
+pub fn next_steps(pre: &PlayerSnapshot, post: &PlayerSnapshot) -> Vec<ProtocolStep> {
+    let mut steps = vec![];
+
+    if pre.requester_id != post.requester_id {
+        if pre.temp_cards != post.temp_cards {
+            steps.push(ProtocolStep::StartCommunityUnmask);
+            return steps;
+        }
+        steps.push(ProtocolStep::SendUnmaskedCards {
+            requester_id: post.requester_id,
+        });
+    }
+
+    if pre.action_type != post.action_type {
+        steps.push(step_for_state(post.state()));
+    }
+
+    steps
+}
+
+fn step_for_state(state: PlayerState) -> ProtocolStep {
+    match state {
+        PlayerState::Idle | PlayerState::Finished => ProtocolStep::Wait,
+        PlayerState::Masking { seat } => ProtocolStep::Remask {
+            player_index: seat as usize,
+        },
+        PlayerState::MaskingComplete => ProtocolStep::SendDeckToGame,
+        PlayerState::Unmasking { .. } => ProtocolStep::InterUnmask,
+        PlayerState::Revealing { stage } => match stage.community_card_slot() {
+            Some(card_slot) => ProtocolStep::RevealCommunityCards { card_slot },
+            None => ProtocolStep::RevealHoleCards,
+        },
+    }
+}
+
+/// Index into the other players stored in `PLAYER_DATA_SLOT` of the player that has to remove
+/// its masking layer next.
+pub fn inter_unmask_target_index(action_type: u64) -> usize {
+    (((action_type - 1) % UNMASK_STAGE_LEN) / NO_OF_PLAYERS as u64) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(action_type: u64, requester_id: u64, card: u64) -> PlayerSnapshot {
+        PlayerSnapshot {
+            action_type,
+            requester_id,
+            temp_cards: [[Felt::new(card), Felt::ZERO, Felt::ZERO, Felt::ZERO]; MAX_HOLE_CARDS],
+        }
+    }
+
+    #[test]
+    fn test_masking_round() {
+        for seat in 1..NO_OF_PLAYERS {
+            assert_eq!(
+                PlayerState::from_action_type(seat as u64),
+                PlayerState::Masking { seat }
+            );
+        }
+        assert_eq!(
+            PlayerState::from_action_type(NO_OF_PLAYERS as u64),
+            PlayerState::MaskingComplete
+        );
+        assert_eq!(PlayerState::from_action_type(0), PlayerState::Idle);
+    }
+
+    #[test]
+    fn test_unmasking_stages() {
+        let stages = [
+            CardStage::HoleCards,
+            CardStage::Flop,
+            CardStage::Turn,
+            CardStage::River,
+        ];
+        for (i, stage) in stages.iter().enumerate() {
+            let stage_start = 1 + i as u64 * UNMASK_STAGE_LEN;
+            for round in 1..UNMASK_ROUNDS {
+                for seat in 0..NO_OF_PLAYERS as u64 {
+                    let action_type = stage_start + round as u64 * NO_OF_PLAYERS as u64 + seat;
+                    assert_eq!(
+                        PlayerState::from_action_type(action_type),
+                        PlayerState::Unmasking {
+                            stage: *stage,
+                            round
+                        }
+                    );
+                }
+            }
+            for seat in 0..NO_OF_PLAYERS as u64 {
+                let action_type = stage_start + UNMASK_STAGE_LEN + seat;
+                assert_eq!(
+                    PlayerState::from_action_type(action_type),
+                    PlayerState::Revealing { stage: *stage }
+                );
+            }
+        }
+        assert_eq!(
+            PlayerState::from_action_type(1 + 4 * UNMASK_STAGE_LEN + NO_OF_PLAYERS as u64),
+            PlayerState::Finished
+        );
+    }
+
+    #[test]
+    fn test_inter_unmask_targets_every_other_player() {
+        for seat in 1..=NO_OF_PLAYERS as u64 {
+            let targets: Vec<usize> = (0..UNMASK_ROUNDS as u64)
+                .map(|round| inter_unmask_target_index(seat + round * NO_OF_PLAYERS as u64))
+                .collect();
+            let expected: Vec<usize> = (0..UNMASK_ROUNDS as usize).collect();
+            assert_eq!(targets, expected);
+        }
+    }
+
+    #[test]
+    fn test_steps_on_action_change() {
+        assert_eq!(
+            next_steps(&snapshot(0, 0, 0), &snapshot(1, 0, 0)),
+            vec![ProtocolStep::Remask { player_index: 1 }]
+        );
+        assert_eq!(
+            next_steps(&snapshot(0, 0, 0), &snapshot(NO_OF_PLAYERS as u64, 0, 0)),
+            vec![ProtocolStep::SendDeckToGame]
+        );
+        assert_eq!(
+            next_steps(&snapshot(1, 0, 0), &snapshot(5, 0, 0)),
+            vec![ProtocolStep::InterUnmask]
+        );
+        assert_eq!(
+            next_steps(&snapshot(9, 0, 0), &snapshot(13, 0, 0)),
+            vec![ProtocolStep::RevealHoleCards]
+        );
+        assert_eq!(
+            next_steps(&snapshot(21, 0, 0), &snapshot(25, 0, 0)),
+            vec![ProtocolStep::RevealCommunityCards {
+                card_slot: FLOP_SLOT
+            }]
+        );
+        assert_eq!(
+            next_steps(&snapshot(45, 0, 0), &snapshot(49, 0, 0)),
+            vec![ProtocolStep::RevealCommunityCards {
+                card_slot: FLOP_SLOT + 4
+            }]
+        );
+        assert!(next_steps(&snapshot(5, 0, 0), &snapshot(5, 0, 0)).is_empty());
+    }
+
+    #[test]
+    fn test_steps_on_requester_change() {
+        assert_eq!(
+            next_steps(&snapshot(13, 0, 0), &snapshot(13, 42, 0)),
+            vec![ProtocolStep::SendUnmaskedCards { requester_id: 42 }]
+        );
+        assert_eq!(
+            next_steps(&snapshot(13, 0, 0), &snapshot(13, 42, 7)),
+            vec![ProtocolStep::StartCommunityUnmask]
+        );
+        assert_eq!(
+            next_steps(&snapshot(5, 0, 0), &snapshot(9, 42, 0)),
+            vec![
+                ProtocolStep::SendUnmaskedCards { requester_id: 42 },
+                ProtocolStep::InterUnmask
+            ]
+        );
+    }
+}
@@ -0,0 +1,467 @@
+use crate::client::{
+    AzeAccountTemplate, AzeClient, AzeGameMethods, AzeTransactionTemplate, PlayBetTransactionData,
+    PlayCallTransactionData, PlayCheckTransactionData, PlayFoldTransactionData,
+    PlayRaiseTransactionData,
+};
+use crate::constants::{
+    FIRST_PLAYER_INDEX, HIGHEST_BET_SLOT, NO_OF_PLAYERS, PLAYER_BALANCE_SLOT, PLAYER_STATS_SLOTS,
+};
+use crate::executor::execute_tx_and_sync;
+use crate::gamestate::{Check_Action, PokerGame};
+use crate::hands::{best_hand, HandCategory};
+use crate::notes::consume_notes;
+use crate::storage::GameStorageSlotData;
+use crate::strategy::Strategy;
+use aze_types::actions::ActionType;
+use miden_client::client::{
+    accounts::AccountStorageMode, rpc::NodeRpcClient,
+    transactions::transaction_request::TransactionRequest,
+};
+use miden_objects::accounts::AccountId;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use std::collections::HashMap;
+
+// Plays many hands between bots to tune blinds and check the contracts. The Rust `PokerGame`
+// referees every hand, `simulate_onchain` also replays the moves as notes against the game
+// account and both states are compared after each move.
+
+// a street never takes more moves than this, guards against bots raising back and forth
+const MAX_MOVES_PER_STREET: usize = 100;
+
+#[derive(Debug, Clone)]
+pub struct SimulationConfig {
+    pub hands: u64,
+    pub players: usize,
+    pub small_blind: u64,
+    pub buy_in: u64,
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SimulationStats {
+    pub hands: u64,
+    // per seat, split pots count as a share of a win
+    pub wins: Vec<f64>,
+    pub total_pot: u64,
+    pub showdowns: u64,
+    pub hand_categories: HashMap<HandCategory, u64>,
+    pub divergences: Vec<String>,
+}
+
+impl SimulationStats {
+    fn new(players: usize) -> Self {
+        Self {
+            wins: vec![0.0; players],
+            ..Default::default()
+        }
+    }
+
+    pub fn win_rate(&self, seat: usize) -> f64 {
+        if self.hands == 0 {
+            return 0.0;
+        }
+        self.wins[seat] / self.hands as f64
+    }
+
+    pub fn average_pot(&self) -> f64 {
+        if self.hands == 0 {
+            return 0.0;
+        }
+        self.total_pot as f64 / self.hands as f64
+    }
+
+    // share of showdown hands that made the category
+    pub fn category_frequency(&self, category: HandCategory) -> f64 {
+        let total: u64 = self.hand_categories.values().sum();
+        if total == 0 {
+            return 0.0;
+        }
+        *self.hand_categories.get(&category).unwrap_or(&0) as f64 / total as f64
+    }
+}
+
+/// A move of a hand, with the bet it was made against so it can be replayed on-chain.
+#[derive(Debug, Clone, Copy)]
+pub struct HandMove {
+    pub seat: usize,
+    pub action: Check_Action,
+    pub current_bet: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct HandResult {
+    pub moves: Vec<HandMove>,
+    pub pot: u64,
+    pub winners: Vec<usize>,
+    // best hand of every seat that went to showdown
+    pub showdown: Vec<(usize, HandCategory)>,
+}
+
+pub fn shuffled_deck(rng: &mut StdRng) -> Vec<u64> {
+    let mut deck: Vec<u64> = (1..=52).collect();
+    deck.shuffle(rng);
+    deck
+}
+
+/// Plays one hand with the seats numbered 1..=players in the `PokerGame`, hole cards are dealt
+/// from the top of the deck followed by the five community cards.
+pub fn play_offchain_hand(
+    config: &SimulationConfig,
+    strategies: &mut [Box<dyn Strategy>],
+    deck: &[u64],
+) -> HandResult {
+    let players = strategies.len();
+    let seat_ids: Vec<u64> = (1..=players as u64).collect();
+    let mut game = PokerGame::new(
+        seat_ids.clone(),
+        vec![config.buy_in; players],
+        config.small_blind,
+        config.small_blind * 2,
+    );
+    let hole_cards: Vec<[u64; 2]> = (0..players).map(|seat| [deck[2 * seat], deck[2 * seat + 1]]).collect();
+    let community_cards = &deck[2 * players..2 * players + 5];
+
+    let mut moves = vec![];
+    for street in 0..4 {
+        if street > 0 {
+            game.next_street();
+        }
+        let mut moves_this_street = 0;
+        // every player still in has to act once per street, and again after a raise
+        let mut to_act = game.active_player_ids().len();
+        while game.active_player_ids().len() > 1
+            && (to_act > 0 || !game.is_betting_settled())
+            && moves_this_street < MAX_MOVES_PER_STREET
+        {
+            let player_id = game.current_player_id();
+            let seat = (player_id - 1) as usize;
+            let current_bet = game.current_bet();
+            let mut action = strategies[seat].act(&game, player_id, &hole_cards[seat]);
+            if !game.check_move(action, player_id) {
+                // a bot asking for an illegal move gives up the hand
+                action = Check_Action {
+                    action_type: ActionType::Fold,
+                    amount: None,
+                };
+                game.check_move(action, player_id);
+            }
+            match action.action_type {
+                // the big blind still gets its option once everyone called
+                ActionType::BigBlind | ActionType::Straddle => to_act = game.active_player_ids().len(),
+                ActionType::Raise => to_act = game.active_player_ids().len().saturating_sub(1),
+                ActionType::SmallBlind => (),
+                _ => to_act = to_act.saturating_sub(1),
+            }
+            moves.push(HandMove {
+                seat,
+                action,
+                current_bet,
+            });
+            moves_this_street += 1;
+        }
+    }
+
+    let pot = game.pot();
+    let active = game.active_player_ids();
+    let mut showdown = vec![];
+    let winners: Vec<usize> = if active.len() == 1 {
+        vec![(active[0] - 1) as usize]
+    } else {
+        let values: Vec<(usize, _)> = active
+            .iter()
+            .map(|player_id| {
+                let seat = (*player_id - 1) as usize;
+                let mut cards = hole_cards[seat].to_vec();
+                cards.extend_from_slice(community_cards);
+                (seat, best_hand(&cards))
+            })
+            .collect();
+        showdown = values.iter().map(|(seat, value)| (*seat, value.category)).collect();
+        let best = values.iter().map(|(_, value)| value).max().unwrap().clone();
+        values
+            .into_iter()
+            .filter(|(_, value)| *value == best)
+            .map(|(seat, _)| seat)
+            .collect()
+    };
+    let winner_ids: Vec<u64> = winners.iter().map(|seat| seat_ids[*seat]).collect();
+    game.award_pot(&winner_ids);
+
+    HandResult {
+        moves,
+        pot,
+        winners,
+        showdown,
+    }
+}
+
+fn record_hand(stats: &mut SimulationStats, result: &HandResult) {
+    stats.hands += 1;
+    stats.total_pot += result.pot;
+    for winner in result.winners.iter() {
+        stats.wins[*winner] += 1.0 / result.winners.len() as f64;
+    }
+    if !result.showdown.is_empty() {
+        stats.showdowns += 1;
+    }
+    for (_, category) in result.showdown.iter() {
+        *stats.hand_categories.entry(*category).or_insert(0) += 1;
+    }
+}
+
+pub fn simulate_offchain(
+    config: &SimulationConfig,
+    strategies: &mut [Box<dyn Strategy>],
+) -> SimulationStats {
+    let mut rng = seeded_rng(config.seed);
+    let mut stats = SimulationStats::new(strategies.len());
+    for _ in 0..config.hands {
+        let deck = shuffled_deck(&mut rng);
+        let result = play_offchain_hand(config, strategies, &deck);
+        record_hand(&mut stats, &result);
+    }
+    stats
+}
+
+/// Same as `simulate_offchain` but every hand is also played on a fresh game account, the
+/// moves are sent as notes and the game account storage is checked against the `PokerGame`
+/// after each of them.
+pub async fn simulate_onchain<N: NodeRpcClient>(
+    client: &mut AzeClient<N>,
+    config: &SimulationConfig,
+    strategies: &mut [Box<dyn Strategy>],
+) -> Result<SimulationStats, String> {
+    if strategies.len() != NO_OF_PLAYERS as usize {
+        return Err(format!(
+            "On-chain simulation needs {} players, the game contract is fixed to it",
+            NO_OF_PLAYERS
+        ));
+    }
+    if config.buy_in > u8::MAX as u64 || config.small_blind * 2 > u8::MAX as u64 {
+        return Err("On-chain balances and blinds have to fit in a u8".to_string());
+    }
+
+    let mut player_account_ids = vec![];
+    for _ in 0..strategies.len() {
+        let (player_account, _) = client
+            .new_game_account(
+                AzeAccountTemplate::PlayerAccount {
+                    mutable_code: false,
+                    storage_mode: AccountStorageMode::Local,
+                },
+                None,
+            )
+            .map_err(|e| format!("Error creating player account: {}", e))?;
+        player_account_ids.push(player_account.id());
+    }
+
+    let mut rng = seeded_rng(config.seed);
+    let mut stats = SimulationStats::new(strategies.len());
+    for hand in 0..config.hands {
+        let deck = shuffled_deck(&mut rng);
+        let result = play_offchain_hand(config, strategies, &deck);
+
+        let slot_data = GameStorageSlotData::new(
+            config.small_blind as u8,
+            config.buy_in,
+            NO_OF_PLAYERS,
+            FIRST_PLAYER_INDEX,
+            config.small_blind as u8,
+            config.buy_in as u8,
+        );
+        let (game_account, _) = client
+            .new_game_account(
+                AzeAccountTemplate::GameAccount {
+                    mutable_code: false,
+                    storage_mode: AccountStorageMode::Local,
+                },
+                Some(slot_data),
+            )
+            .map_err(|e| format!("Error creating game account: {}", e))?;
+        let game_account_id = game_account.id();
+
+        // replay the hand move by move against a fresh referee
+        let seat_ids: Vec<u64> = (1..=strategies.len() as u64).collect();
+        let mut game = PokerGame::new(
+            seat_ids,
+            vec![config.buy_in; strategies.len()],
+            config.small_blind,
+            config.small_blind * 2,
+        );
+        for (i, hand_move) in result.moves.iter().enumerate() {
+            game.check_move(hand_move.action, hand_move.seat as u64 + 1);
+            let player_account_id = player_account_ids[hand_move.seat];
+            if !play_onchain_move(client, game_account_id, player_account_id, config, hand_move).await
+            {
+                stats.divergences.push(format!(
+                    "hand {} move {}: {:?} was not accepted by the game account",
+                    hand, i, hand_move.action.action_type
+                ));
+                break;
+            }
+            stats.divergences.extend(
+                compare_game_state(client, game_account_id, &game)
+                    .into_iter()
+                    .map(|divergence| format!("hand {} move {}: {}", hand, i, divergence)),
+            );
+        }
+
+        record_hand(&mut stats, &result);
+    }
+    Ok(stats)
+}
+
+async fn play_onchain_move<N: NodeRpcClient>(
+    client: &mut AzeClient<N>,
+    game_account_id: AccountId,
+    player_account_id: AccountId,
+    config: &SimulationConfig,
+    hand_move: &HandMove,
+) -> bool {
+    // same amounts the cli sends for each action
+    let txn_request = match hand_move.action.action_type {
+        ActionType::SmallBlind | ActionType::BigBlind | ActionType::Straddle => {
+            let blind = match hand_move.action.action_type {
+                ActionType::SmallBlind => config.small_blind,
+                ActionType::BigBlind => config.small_blind * 2,
+                _ => config.small_blind * 4,
+            };
+            client.build_aze_play_bet_tx_request(AzeTransactionTemplate::PlayBet(
+                PlayBetTransactionData::new(player_account_id, game_account_id, blind as u8),
+            ))
+        }
+        ActionType::Raise => client.build_aze_play_raise_tx_request(
+            AzeTransactionTemplate::PlayRaise(PlayRaiseTransactionData::new(
+                player_account_id,
+                game_account_id,
+                (hand_move.current_bet + hand_move.action.amount.unwrap_or(0)) as u8,
+            )),
+        ),
+        ActionType::Call => client.build_aze_play_call_tx_request(
+            AzeTransactionTemplate::PlayCall(PlayCallTransactionData::new(
+                player_account_id,
+                game_account_id,
+            )),
+        ),
+        ActionType::Check => client.build_aze_play_check_tx_request(
+            AzeTransactionTemplate::PlayCheck(PlayCheckTransactionData::new(
+                player_account_id,
+                game_account_id,
+            )),
+        ),
+        ActionType::Fold => client.build_aze_play_fold_tx_request(
+            AzeTransactionTemplate::PlayFold(PlayFoldTransactionData::new(
+                player_account_id,
+                game_account_id,
+            )),
+        ),
+    };
+    let txn_request: TransactionRequest = match txn_request {
+        Ok(txn_request) => txn_request,
+        Err(_) => return false,
+    };
+
+    execute_tx_and_sync(client, txn_request.clone()).await;
+    let note_id = txn_request.expected_output_notes()[0].id();
+    let note = match client.get_input_note(note_id) {
+        Ok(note) => note,
+        Err(_) => return false,
+    };
+    let input_note = match note.try_into() {
+        Ok(input_note) => input_note,
+        Err(_) => return false,
+    };
+
+    let nonce_before = client.get_account(game_account_id).unwrap().0.nonce();
+    consume_notes(client, game_account_id, &[input_note]).await;
+    let nonce_after = client.get_account(game_account_id).unwrap().0.nonce();
+    nonce_after != nonce_before
+}
+
+fn compare_game_state<N: NodeRpcClient>(
+    client: &AzeClient<N>,
+    game_account_id: AccountId,
+    game: &PokerGame,
+) -> Vec<String> {
+    let (game_account, _) = client.get_account(game_account_id).unwrap();
+    let storage = game_account.storage();
+    let mut divergences = vec![];
+
+    let highest_bet = storage.get_item(HIGHEST_BET_SLOT).as_elements()[0].as_int();
+    if highest_bet != game.current_bet() {
+        divergences.push(format!(
+            "highest bet is {} on-chain and {} off-chain",
+            highest_bet,
+            game.current_bet()
+        ));
+    }
+
+    for (seat, player_id) in game.player_ids().into_iter().enumerate() {
+        let balance_slot = PLAYER_BALANCE_SLOT + seat as u8 * PLAYER_STATS_SLOTS;
+        let balance = storage.get_item(balance_slot).as_elements()[0].as_int();
+        let expected = game.balance(player_id).unwrap_or(0);
+        if balance != expected {
+            divergences.push(format!(
+                "seat {} balance is {} on-chain and {} off-chain",
+                seat, balance, expected
+            ));
+        }
+    }
+
+    divergences
+}
+
+fn seeded_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::strategy::{create_strategy, AlwaysCall};
+    use aze_types::actions::StrategyType;
+
+    fn config(hands: u64) -> SimulationConfig {
+        SimulationConfig {
+            hands,
+            players: 4,
+            small_blind: 5,
+            buy_in: 1000,
+            seed: Some(42),
+        }
+    }
+
+    #[test]
+    fn test_always_call_goes_to_showdown() {
+        let mut strategies: Vec<Box<dyn Strategy>> = (0..4).map(|_| Box::new(AlwaysCall) as Box<dyn Strategy>).collect();
+        let deck: Vec<u64> = (1..=52).collect();
+        let result = play_offchain_hand(&config(1), &mut strategies, &deck);
+
+        // blinds, three calls and the big blind checking pre-flop, then four checks per street
+        assert_eq!(result.moves.len(), 6 + 3 * 4);
+        assert_eq!(result.pot, 40);
+        assert_eq!(result.showdown.len(), 4);
+        assert!(!result.winners.is_empty());
+    }
+
+    #[test]
+    fn test_stats_add_up() {
+        let mut strategies: Vec<Box<dyn Strategy>> = (0..4)
+            .map(|seat| create_strategy(StrategyType::Random, Some(seat)))
+            .collect();
+        let stats = simulate_offchain(&config(200), &mut strategies);
+
+        assert_eq!(stats.hands, 200);
+        let total_wins: f64 = stats.wins.iter().sum();
+        assert!((total_wins - 200.0).abs() < 1e-6);
+        let total_frequency: f64 = HandCategory::ALL
+            .iter()
+            .map(|category| stats.category_frequency(*category))
+            .sum();
+        assert!(stats.showdowns == 0 || (total_frequency - 1.0).abs() < 1e-6);
+        assert!(stats.average_pot() >= 15.0);
+    }
+}
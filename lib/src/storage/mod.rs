@@ -1,17 +1,36 @@
+use crate::constants::{
+    BIG_BLIND_ANTE, FIXED_LIMIT, HAND_OVER_PHASE, NO_ANTE, NO_LIMIT, PER_PLAYER_ANTE, POT_LIMIT,
+};
+use crate::gamestate::{Ante, BettingStructure};
+use crate::hands::GameVariant;
+
 #[derive(Clone)]
 pub struct GameStorageSlotData {
     small_blind_amt: u8,
-    buy_in_amt: u8,
+    buy_in_amt: u64,
     player_count: u8,
     current_turn_index: u8,
     highest_bet: u8,
     player_balance: u8,
+    // players seated in order when the account is created, None seats everyone
+    seated_players: Option<Vec<u64>>,
+    // faucet of the asset players buy in with, 0 when balances aren't backed by assets
+    buy_in_faucet: u64,
+    rake_percent: u64,
+    rake_cap: u64,
+    rake_operator: u64,
+    // stack every seat buys in for at tournament tables, 0 at cash tables
+    starting_stack: u64,
+    betting_structure: BettingStructure,
+    variant: GameVariant,
+    ante: Ante,
+    straddle: bool,
 }
 
 impl GameStorageSlotData {
     pub fn new(
         small_blind_amt: u8,
-        buy_in_amt: u8,
+        buy_in_amt: u64,
         player_count: u8,
         current_turn_index: u8,
         highest_bet: u8,
@@ -24,14 +43,74 @@ impl GameStorageSlotData {
             current_turn_index,
             highest_bet,
             player_balance,
+            seated_players: None,
+            buy_in_faucet: 0,
+            rake_percent: 0,
+            rake_cap: 0,
+            rake_operator: 0,
+            starting_stack: 0,
+            betting_structure: BettingStructure::NoLimit,
+            variant: GameVariant::Holdem,
+            ante: Ante::None,
+            straddle: false,
         }
     }
 
+    /// Seats the players in order, the remaining seats stay empty until someone joins. An
+    /// empty list creates a table players join one by one.
+    pub fn with_seated_players(mut self, player_ids: &[u64]) -> Self {
+        self.seated_players = Some(player_ids.to_vec());
+        self
+    }
+
+    /// Only the asset of this faucet is accepted as buy-in, the game account keeps it in its
+    /// vault while the player is seated.
+    pub fn with_buy_in_faucet(mut self, faucet_id: u64) -> Self {
+        self.buy_in_faucet = faucet_id;
+        self
+    }
+
+    /// Takes `percent` of every pot that reaches the flop, at most `cap`, for the operator to
+    /// withdraw.
+    pub fn with_rake(mut self, percent: u64, cap: u64, operator_id: u64) -> Self {
+        self.rake_percent = percent;
+        self.rake_cap = cap;
+        self.rake_operator = operator_id;
+        self
+    }
+
+    /// Runs the table as a tournament: every seat buys in for `starting_stack` and players left
+    /// without chips are eliminated instead of staying seated.
+    pub fn with_starting_stack(mut self, starting_stack: u64) -> Self {
+        self.starting_stack = starting_stack;
+        self
+    }
+
+    /// Limits how much players raise, the game account rejects raises that don't fit.
+    pub fn with_betting_structure(mut self, betting_structure: BettingStructure) -> Self {
+        self.betting_structure = betting_structure;
+        self
+    }
+
+    /// Deals the hole cards of `variant`, the community cards follow them in the deck.
+    pub fn with_variant(mut self, variant: GameVariant) -> Self {
+        self.variant = variant;
+        self
+    }
+
+    /// Posts the antes and the straddle at the start of every hand. Tables created with players
+    /// start their first hand with the antes already in the pot.
+    pub fn with_forced_bets(mut self, ante: Ante, straddle: bool) -> Self {
+        self.ante = ante;
+        self.straddle = straddle;
+        self
+    }
+
     pub fn small_blind_amt(&self) -> u8 {
         self.small_blind_amt
     }
 
-    pub fn buy_in_amt(&self) -> u8 {
+    pub fn buy_in_amt(&self) -> u64 {
         self.buy_in_amt
     }
 
@@ -40,7 +119,7 @@ impl GameStorageSlotData {
     }
 
     pub fn flop_index(&self) -> u8 {
-        self.player_count * 2 + 1
+        self.player_count * self.variant.hole_cards() as u8 + 1
     }
 
     pub fn current_turn_index(&self) -> u8 {
@@ -54,4 +133,116 @@ impl GameStorageSlotData {
     pub fn player_balance(&self) -> u8 {
         self.player_balance
     }
+
+    pub fn buy_in_faucet(&self) -> u64 {
+        self.buy_in_faucet
+    }
+
+    pub fn rake_percent(&self) -> u64 {
+        self.rake_percent
+    }
+
+    pub fn rake_cap(&self) -> u64 {
+        self.rake_cap
+    }
+
+    pub fn rake_operator(&self) -> u64 {
+        self.rake_operator
+    }
+
+    pub fn starting_stack(&self) -> u64 {
+        self.starting_stack
+    }
+
+    pub fn betting_structure(&self) -> BettingStructure {
+        self.betting_structure
+    }
+
+    pub fn variant(&self) -> GameVariant {
+        self.variant
+    }
+
+    pub fn ante(&self) -> Ante {
+        self.ante
+    }
+
+    pub fn straddle(&self) -> bool {
+        self.straddle
+    }
+
+    // ante the seat posts before the first hand of a table created with players, the second
+    // seat being the big blind
+    pub fn ante_posted(&self, seat: u8) -> u64 {
+        let posts = match self.ante {
+            Ante::None => false,
+            Ante::PerPlayer(_) => true,
+            Ante::BigBlind(_) => seat == 1,
+        };
+        match self.seated_player(seat) {
+            Some(_) if posts => self.ante.amount().min(self.player_balance as u64),
+            _ => 0,
+        }
+    }
+
+    // a table created without players waits between hands for them to join
+    pub fn current_phase(&self) -> u64 {
+        match self.seated_player(0) {
+            Some(_) => 0,
+            None => HAND_OVER_PHASE,
+        }
+    }
+
+    // player taking the seat at creation, Some(0) when the seat is taken by an unknown player
+    pub fn seated_player(&self, seat: u8) -> Option<u64> {
+        match &self.seated_players {
+            Some(player_ids) => player_ids.get(seat as usize).copied(),
+            None => Some(0),
+        }
+    }
+}
+
+/// Word the game account keeps the betting structure in: [kind, small bet, big bet, raise cap].
+pub fn betting_structure_word(betting_structure: BettingStructure) -> [u64; 4] {
+    match betting_structure {
+        BettingStructure::NoLimit => [NO_LIMIT, 0, 0, 0],
+        BettingStructure::PotLimit => [POT_LIMIT, 0, 0, 0],
+        BettingStructure::FixedLimit {
+            small_bet,
+            big_bet,
+            raise_cap,
+        } => [FIXED_LIMIT, small_bet, big_bet, raise_cap],
+    }
+}
+
+// an unknown kind is played as no-limit, like accounts created before betting structures
+pub fn betting_structure_from_word(word: [u64; 4]) -> BettingStructure {
+    match word[0] {
+        POT_LIMIT => BettingStructure::PotLimit,
+        FIXED_LIMIT => BettingStructure::FixedLimit {
+            small_bet: word[1],
+            big_bet: word[2],
+            raise_cap: word[3],
+        },
+        _ => BettingStructure::NoLimit,
+    }
+}
+
+/// Word the game account keeps the forced bets in: [ante kind, ante, straddle, 0].
+pub fn forced_bets_word(ante: Ante, straddle: bool) -> [u64; 4] {
+    let straddle = straddle as u64;
+    match ante {
+        Ante::None => [NO_ANTE, 0, straddle, 0],
+        Ante::PerPlayer(amount) => [PER_PLAYER_ANTE, amount, straddle, 0],
+        Ante::BigBlind(amount) => [BIG_BLIND_ANTE, amount, straddle, 0],
+    }
+}
+
+// accounts created before forced bets have none
+pub fn forced_bets_from_word(word: [u64; 4]) -> (Ante, bool) {
+    let ante = match word[0] {
+        PER_PLAYER_ANTE => Ante::PerPlayer(word[1]),
+        BIG_BLIND_ANTE => Ante::BigBlind(word[1]),
+        _ => Ante::None,
+    };
+    (ante, word[2] != 0)
 }
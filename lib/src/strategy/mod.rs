@@ -0,0 +1,202 @@
+use crate::gamestate::{Check_Action, PokerGame};
+use crate::hands::{card_rank, card_suit, combinations};
+use aze_types::actions::{ActionType, StrategyType};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+// Bots deciding on a move from the off-chain game state and their own hole cards, two of them in
+// Hold'em and four in Omaha. Cards are the 1..=52 numbers used on-chain, see `card_from_number`.
+
+pub trait Strategy {
+    fn decide(&mut self, game: &PokerGame, player_id: u64, hole_cards: &[u64]) -> Check_Action;
+
+    // posts the blinds on the bot's behalf, only asks the strategy for real decisions
+    fn act(&mut self, game: &PokerGame, player_id: u64, hole_cards: &[u64]) -> Check_Action {
+        match game.pending_blind() {
+            Some(blind) => action(blind, None),
+            None => self.decide(game, player_id, hole_cards),
+        }
+    }
+}
+
+pub fn create_strategy(strategy_type: StrategyType, seed: Option<u64>) -> Box<dyn Strategy> {
+    match strategy_type {
+        StrategyType::AlwaysCall => Box::new(AlwaysCall),
+        StrategyType::TightAggressive => Box::new(TightAggressive),
+        StrategyType::Random => Box::new(RandomStrategy::new(seed)),
+    }
+}
+
+/// Never folds, calls any bet it can afford and checks otherwise.
+pub struct AlwaysCall;
+
+impl Strategy for AlwaysCall {
+    fn decide(&mut self, game: &PokerGame, player_id: u64, _hole_cards: &[u64]) -> Check_Action {
+        check_or_call(game, player_id)
+    }
+}
+
+/// Plays only strong starting hands, and raises with them.
+pub struct TightAggressive;
+
+impl Strategy for TightAggressive {
+    fn decide(&mut self, game: &PokerGame, player_id: u64, hole_cards: &[u64]) -> Check_Action {
+        let to_call = game.amount_to_call(player_id).unwrap_or(0);
+        match starting_hand_strength(hole_cards) {
+            HandStrength::Strong => {
+                let raise = game.standard_raise();
+                let balance = game.balance(player_id).unwrap_or(0);
+                if balance >= game.current_bet() + raise && game.can_raise(raise) {
+                    action(ActionType::Raise, Some(raise))
+                } else {
+                    check_or_call(game, player_id)
+                }
+            }
+            HandStrength::Playable if to_call <= game.big_blind() => check_or_call(game, player_id),
+            _ if to_call == 0 => action(ActionType::Check, None),
+            _ => action(ActionType::Fold, None),
+        }
+    }
+}
+
+/// Picks a uniformly random legal move, raising by one big blind or the fixed-limit bet.
+pub struct RandomStrategy {
+    rng: StdRng,
+}
+
+impl RandomStrategy {
+    pub fn new(seed: Option<u64>) -> Self {
+        let rng = match seed {
+            Some(seed) => StdRng::seed_from_u64(seed),
+            None => StdRng::from_entropy(),
+        };
+        Self { rng }
+    }
+}
+
+impl Strategy for RandomStrategy {
+    fn decide(&mut self, game: &PokerGame, player_id: u64, _hole_cards: &[u64]) -> Check_Action {
+        let balance = game.balance(player_id).unwrap_or(0);
+        let mut moves = vec![check_or_call(game, player_id)];
+        if game.amount_to_call(player_id).unwrap_or(0) > 0 {
+            moves.push(action(ActionType::Fold, None));
+        }
+        let raise = game.standard_raise();
+        if balance >= game.current_bet() + raise && game.can_raise(raise) {
+            moves.push(action(ActionType::Raise, Some(raise)));
+        }
+        moves[self.rng.gen_range(0..moves.len())]
+    }
+}
+
+// ordered strongest first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HandStrength {
+    Strong,
+    Playable,
+    Weak,
+}
+
+// rough pre-flop classification: big pairs and big broadway cards are strong, any pair,
+// suited cards or a high card are playable. Omaha hands are as strong as their best two cards
+pub fn starting_hand_strength(hole_cards: &[u64]) -> HandStrength {
+    combinations(hole_cards, 2)
+        .iter()
+        .map(|pair| two_card_strength(pair[0], pair[1]))
+        .min()
+        .unwrap_or(HandStrength::Weak)
+}
+
+fn two_card_strength(card1: u64, card2: u64) -> HandStrength {
+    let (rank1, rank2) = (card_rank(card1), card_rank(card2));
+    let suited = card_suit(card1) == card_suit(card2);
+    let high = rank1.max(rank2);
+    let low = rank1.min(rank2);
+
+    if (rank1 == rank2 && low >= 9) || low >= 12 {
+        HandStrength::Strong
+    } else if rank1 == rank2 || suited || high >= 13 || low >= 10 {
+        HandStrength::Playable
+    } else {
+        HandStrength::Weak
+    }
+}
+
+fn check_or_call(game: &PokerGame, player_id: u64) -> Check_Action {
+    let to_call = game.amount_to_call(player_id).unwrap_or(0);
+    if to_call == 0 {
+        action(ActionType::Check, None)
+    } else if game.balance(player_id).unwrap_or(0) >= to_call {
+        action(ActionType::Call, None)
+    } else {
+        action(ActionType::Fold, None)
+    }
+}
+
+fn action(action_type: ActionType, amount: Option<u64>) -> Check_Action {
+    Check_Action {
+        action_type,
+        amount,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game_after_blinds() -> PokerGame {
+        let mut game = PokerGame::new(vec![1, 2, 3, 4], vec![1000, 1000, 1000, 1000], 10, 20);
+        game.check_move(action(ActionType::SmallBlind, None), 1);
+        game.check_move(action(ActionType::BigBlind, None), 2);
+        game
+    }
+
+    #[test]
+    fn test_bots_post_blinds() {
+        let mut game = PokerGame::new(vec![1, 2, 3, 4], vec![1000, 1000, 1000, 1000], 10, 20);
+        let mut bot = AlwaysCall;
+        let small_blind = bot.act(&game, 1, &[1, 2]);
+        assert_eq!(small_blind.action_type, ActionType::SmallBlind);
+        assert!(game.check_move(small_blind, 1));
+        assert_eq!(bot.act(&game, 2, &[1, 2]).action_type, ActionType::BigBlind);
+    }
+
+    #[test]
+    fn test_always_call() {
+        let game = game_after_blinds();
+        assert_eq!(AlwaysCall.act(&game, 3, &[2, 20]).action_type, ActionType::Call);
+    }
+
+    #[test]
+    fn test_tight_aggressive() {
+        let game = game_after_blinds();
+        let mut bot = TightAggressive;
+        // pocket aces
+        let aces = bot.act(&game, 3, &[1, 14]);
+        assert_eq!(aces, action(ActionType::Raise, Some(20)));
+        // seven-two offsuit
+        assert_eq!(bot.act(&game, 3, &[7, 15]).action_type, ActionType::Fold);
+        // suited connectors
+        assert_eq!(bot.act(&game, 3, &[8, 9]).action_type, ActionType::Call);
+    }
+
+    #[test]
+    fn test_random_moves_are_legal() {
+        let mut bot = RandomStrategy::new(Some(7));
+        for _ in 0..50 {
+            let mut game = game_after_blinds();
+            let check_action = bot.act(&game, 3, &[1, 2]);
+            assert!(game.check_move(check_action, 3));
+        }
+    }
+
+    #[test]
+    fn test_starting_hand_strength() {
+        assert_eq!(starting_hand_strength(&[10, 23]), HandStrength::Strong);
+        assert_eq!(starting_hand_strength(&[13, 12]), HandStrength::Strong);
+        assert_eq!(starting_hand_strength(&[2, 15]), HandStrength::Playable);
+        assert_eq!(starting_hand_strength(&[1, 16]), HandStrength::Playable);
+        assert_eq!(starting_hand_strength(&[3, 20]), HandStrength::Weak);
+        // an Omaha hand holding aces
+        assert_eq!(starting_hand_strength(&[3, 20, 1, 14]), HandStrength::Strong);
+    }
+}
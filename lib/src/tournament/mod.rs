@@ -0,0 +1,105 @@
+use aze_types::lobby::Stakes;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+// Sit-and-go tournaments: every seat buys in for the same stack, the blinds go up level by level
+// and players left without chips are eliminated until one of them holds every chip.
+
+/// When the blinds move on to the next level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LevelUp {
+    Hands(u64),
+    Minutes(u64),
+}
+
+/// Blinds of every level, the last level is played until the tournament ends.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct BlindSchedule {
+    levels: Vec<Stakes>,
+    level_up: LevelUp,
+}
+
+impl BlindSchedule {
+    pub fn new(levels: Vec<Stakes>, level_up: LevelUp) -> Result<Self, String> {
+        if levels.is_empty() {
+            return Err(String::from("A blind schedule needs at least one level"));
+        }
+        if levels
+            .windows(2)
+            .any(|pair| pair[1].small_blind < pair[0].small_blind)
+        {
+            return Err(String::from("Blinds can't go down from one level to the next"));
+        }
+        if matches!(level_up, LevelUp::Hands(0) | LevelUp::Minutes(0)) {
+            return Err(String::from("Levels have to last at least one hand or minute"));
+        }
+        Ok(Self { levels, level_up })
+    }
+
+    /// Parses levels written like stakes and separated by commas, e.g. `5/10,10/20,25/50`.
+    pub fn parse_levels(levels: &str) -> Result<Vec<Stakes>, String> {
+        levels.split(',').map(|level| level.trim().parse()).collect()
+    }
+
+    pub fn levels(&self) -> &[Stakes] {
+        &self.levels
+    }
+
+    // blinds of the first hand
+    pub fn first_level(&self) -> Stakes {
+        self.levels[0]
+    }
+
+    /// Level the blinds are at, counted from 0, once `hands_played` hands were dealt or
+    /// `elapsed` went by since the first one.
+    pub fn level_at(&self, hands_played: u64, elapsed: Duration) -> usize {
+        let levels_done = match self.level_up {
+            LevelUp::Hands(hands) => hands_played / hands,
+            LevelUp::Minutes(minutes) => elapsed.as_secs() / (minutes * 60),
+        };
+        (levels_done as usize).min(self.levels.len() - 1)
+    }
+
+    pub fn blinds_at(&self, level: usize) -> Stakes {
+        self.levels[level.min(self.levels.len() - 1)]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levels_by_hands() {
+        let levels = BlindSchedule::parse_levels("5/10, 10/20,25/50").unwrap();
+        let schedule = BlindSchedule::new(levels, LevelUp::Hands(10)).unwrap();
+        assert_eq!(schedule.first_level().big_blind, 10);
+        assert_eq!(schedule.level_at(0, Duration::ZERO), 0);
+        assert_eq!(schedule.level_at(9, Duration::from_secs(3600)), 0);
+        assert_eq!(schedule.level_at(10, Duration::ZERO), 1);
+        // the last level holds
+        assert_eq!(schedule.level_at(45, Duration::ZERO), 2);
+        assert_eq!(schedule.blinds_at(2).small_blind, 25);
+        assert_eq!(schedule.blinds_at(7).small_blind, 25);
+    }
+
+    #[test]
+    fn test_levels_by_minutes() {
+        let levels = BlindSchedule::parse_levels("5/10,10/20").unwrap();
+        let schedule = BlindSchedule::new(levels, LevelUp::Minutes(5)).unwrap();
+        assert_eq!(schedule.level_at(100, Duration::from_secs(299)), 0);
+        assert_eq!(schedule.level_at(0, Duration::from_secs(300)), 1);
+    }
+
+    #[test]
+    fn test_invalid_schedules() {
+        assert!(BlindSchedule::parse_levels("5/10,10/15").is_err());
+        assert!(BlindSchedule::parse_levels("").is_err());
+        assert!(BlindSchedule::new(vec![], LevelUp::Hands(10)).is_err());
+        let falling = BlindSchedule::parse_levels("10/20,5/10").unwrap();
+        assert!(BlindSchedule::new(falling, LevelUp::Hands(10)).is_err());
+        let levels = BlindSchedule::parse_levels("5/10").unwrap();
+        assert!(BlindSchedule::new(levels, LevelUp::Minutes(0)).is_err());
+    }
+}
@@ -1,4 +1,5 @@
 use aze_types::actions::ActionType;
+use aze_types::events::GameEvent;
 use miden_objects::{
     accounts::{Account, AccountCode, AccountId, AccountStorage, SlotItem},
     assembly::{ModuleAst, ProgramAst},
@@ -16,13 +17,16 @@ use miden_objects::{
 };
 
 use crate::{
+    auth::{RequestSigner, SIGNED_REQUEST_HEADER},
     broadcast::CheckmoveRequest,
     client::{AzeAccountTemplate, AzeClient, AzeGameMethods},
     constants::{
-        BUY_IN_AMOUNT, CURRENT_TURN_INDEX_SLOT, HIGHEST_BET, NO_OF_PLAYERS, PLAYER_INITIAL_BALANCE,
-        SMALL_BLIND_AMOUNT, SMALL_BUY_IN_AMOUNT,
+        BUY_IN_AMOUNT, CURRENT_TURN_INDEX_SLOT, HIGHEST_BET, NO_OF_PLAYERS, PLAYER_CARD1_SLOT,
+        PLAYER_CARD2_SLOT, PLAYER_INITIAL_BALANCE, SMALL_BLIND_AMOUNT, SMALL_BUY_IN_AMOUNT,
     },
-    gamestate::Check_Action,
+    gamestate::{Check_Action, PokerGame},
+    hands::GameVariant,
+    host::HostedGame,
     notes::{consume_notes, mint_note},
     storage::GameStorageSlotData,
 };
@@ -47,6 +51,9 @@ use std::{fs::File, io::Read, path::Path};
 use reqwest::Client as httpClient;
 use serde::{Deserialize, Serialize};
 use std::error::Error;
+use tokio_tungstenite::tungstenite::{
+    client::IntoClientRequest, handshake::client::Request, http::HeaderValue,
+};
 
 // use uuid::Uuid;
 
@@ -65,7 +72,9 @@ pub fn get_new_key_pair_with_advice_map() -> (Word, Vec<Felt>) {
 
 pub fn create_aze_store_path() -> std::path::PathBuf {
     let mut temp_file = temp_dir();
-    temp_file.push(format!("{}.sqlite3", "random")); // for now don't know why uuid is not importing
+    // for now don't know why uuid is not importing, a random suffix keeps stores apart
+    let suffix: u64 = rand::thread_rng().gen();
+    temp_file.push(format!("aze-{}.sqlite3", suffix));
     temp_file
 }
 
@@ -100,12 +109,14 @@ pub async fn log_account_status(client: &AzeClient, account_id: AccountId) {
         regular_account.storage().root()
     );
     println!(
-        "Account slot 100 --> {:?}",
-        regular_account.storage().get_item(100)
+        "Account slot {} --> {:?}",
+        PLAYER_CARD1_SLOT,
+        regular_account.storage().get_item(PLAYER_CARD1_SLOT)
     );
     println!(
-        "Account slot 101 --> {:?}",
-        regular_account.storage().get_item(101)
+        "Account slot {} --> {:?}",
+        PLAYER_CARD2_SLOT,
+        regular_account.storage().get_item(PLAYER_CARD2_SLOT)
     );
 }
 
@@ -125,7 +136,7 @@ pub async fn setup_accounts(
 ) -> (FungibleAsset, AccountId, AccountId, GameStorageSlotData) {
     let slot_data = GameStorageSlotData::new(
         SMALL_BLIND_AMOUNT,
-        SMALL_BUY_IN_AMOUNT,
+        SMALL_BUY_IN_AMOUNT as u64,
         NO_OF_PLAYERS,
         CURRENT_TURN_INDEX_SLOT,
         HIGHEST_BET,
@@ -169,6 +180,7 @@ pub async fn setup_accounts(
         &mut client,
         player_account_id,
         faucet_account_id,
+        BUY_IN_AMOUNT,
         NoteType::Public,
     )
     .await;
@@ -186,10 +198,15 @@ pub async fn setup_accounts(
     );
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize)]
 pub struct PublishRequest {
-    game_id: String,
-    event: String,
+    pub game_id: String,
+    pub event: GameEvent,
+}
+// opens the websocket of a game
+#[derive(Serialize, Deserialize)]
+pub struct SubscribeRequest {
+    pub game_id: String,
 }
 #[derive(Serialize)]
 pub struct StatRequest {
@@ -203,18 +220,28 @@ pub struct StatResponse {
     pub pot_value: u64,
     pub player_hands: Vec<u64>,
     pub current_state: u64,
-    pub player_hand_cards: Vec<Vec<u64>>
+    pub player_hand_cards: Vec<Vec<u64>>,
+    #[serde(default)]
+    pub variant: GameVariant,
+    #[serde(default)]
+    pub player_low_hands: Vec<Vec<u64>>,
 }
 
 // Config for saving broadcast url
 #[derive(Default, Serialize, Deserialize)]
 pub struct Ws_config {
     pub url: Option<String>,
+    // address the game server is listening on, only set on the host
+    #[serde(default)]
+    pub bind_address: Option<String>,
 }
 
 impl Ws_config {
     pub fn new() -> Self {
-        Ws_config { url: None }
+        Ws_config {
+            url: None,
+            bind_address: None,
+        }
     }
 
     pub fn load(config_path: &std::path::PathBuf) -> Self {
@@ -232,10 +259,11 @@ impl Ws_config {
     }
 }
 
-pub async fn broadcast_message(
+pub async fn broadcast_event(
     game_id: String,
     url: String,
-    message: String,
+    event: GameEvent,
+    signer: &RequestSigner,
 ) -> Result<(), Box<dyn Error>> {
     let client = httpClient::new();
     let url = url::Url::parse(&url).unwrap();
@@ -243,32 +271,56 @@ pub async fn broadcast_message(
     let port = url.port().map(|p| format!(":{}", p)).unwrap_or_default();
     let publish_url = format!("{}{}{}", base_url, port, "/publish");
 
-    let request_body = PublishRequest {
-        game_id,
-        event: message,
-    };
+    let request_body = signer.sign(&PublishRequest { game_id, event });
 
     let response = client.post(&publish_url).json(&request_body).send().await?;
 
     if response.status().is_success() {
-        println!("Message successfully published");
+        println!("Event successfully published");
         Ok(())
     } else {
         let status = response.status();
         let error_text = response.text().await?;
-        eprintln!("Failed to publish message: {} - {}", status, error_text);
-        Err(format!("Failed to publish message: {} - {}", status, error_text).into())
+        eprintln!("Failed to publish event: {} - {}", status, error_text);
+        Err(format!("Failed to publish event: {} - {}", status, error_text).into())
     }
 }
 
-pub async fn get_stats(game_id: String, url: String) -> Result<StatResponse, Box<dyn Error>> {
+// asks a running aze-server to host the game, returns the url players connect to
+pub async fn register_game(
+    server_url: String,
+    hosted_game: &HostedGame,
+    signer: &RequestSigner,
+) -> Result<String, Box<dyn Error>> {
+    let client = httpClient::new();
+    let games_url = format!("{}/games", server_url.trim_end_matches('/'));
+
+    let request_body = signer.sign(hosted_game);
+
+    let response = client.post(&games_url).json(&request_body).send().await?;
+
+    if response.status().is_success() {
+        Ok(response.json::<String>().await?)
+    } else {
+        let status = response.status();
+        let error_text = response.text().await?;
+        Err(format!("Failed to register game: {} - {}", status, error_text).into())
+    }
+}
+
+// hole cards of the other players stay hidden until the showdown, so the request is signed
+pub async fn get_stats(
+    game_id: String,
+    url: String,
+    signer: &RequestSigner,
+) -> Result<StatResponse, Box<dyn Error>> {
     let client = httpClient::new();
     let url = url::Url::parse(&url).unwrap();
     let base_url = format!("http://{}", url.host_str().unwrap());
     let port = url.port().map(|p| format!(":{}", p)).unwrap_or_default();
     let stat_url = format!("{}{}{}", base_url, port, "/stats");
 
-    let request_body = StatRequest { game_id };
+    let request_body = signer.sign(&StatRequest { game_id });
 
     let response = client.post(&stat_url).json(&request_body).send().await?;
 
@@ -286,6 +338,7 @@ pub async fn validate_action(
     action: Check_Action,
     url: String,
     player_id: u64,
+    signer: &RequestSigner,
 ) -> Result<bool, Box<dyn Error>> {
     let client = httpClient::new();
     let url = url::Url::parse(&url).unwrap();
@@ -293,7 +346,11 @@ pub async fn validate_action(
     let port = url.port().map(|p| format!(":{}", p)).unwrap_or_default();
     let stat_url = format!("{}{}{}", base_url, port, "/checkmove");
 
-    let request_body = CheckmoveRequest { player_id, action };
+    let request_body = signer.sign(&CheckmoveRequest {
+        game_id: game_id_from_url(&url),
+        player_id,
+        action,
+    });
 
     let response = client.post(&stat_url).json(&request_body).send().await?;
 
@@ -307,19 +364,74 @@ pub async fn validate_action(
     }
 }
 
+pub async fn get_game_state(url: String) -> Result<PokerGame, Box<dyn Error>> {
+    let client = httpClient::new();
+    let url = url::Url::parse(&url).unwrap();
+    let base_url = format!("http://{}", url.host_str().unwrap());
+    let port = url.port().map(|p| format!(":{}", p)).unwrap_or_default();
+    let gamestate_url = format!(
+        "{}{}/gamestate/{}",
+        base_url,
+        port,
+        game_id_from_url(&url)
+    );
+
+    let response = client.get(&gamestate_url).send().await?;
+
+    if response.status().is_success() {
+        Ok(response.json().await?)
+    } else {
+        let status = response.status();
+        let error_text = response.text().await?;
+        eprintln!("Failed to get game state: {} - {}", status, error_text);
+        Err(format!("Failed to get game state: {} - {}", status, error_text).into())
+    }
+}
+
+/// The websocket handshake for following a game, signed since the server only lets the game
+/// account and the game's players connect.
+pub fn subscribe_request(ws_url: &str, signer: &RequestSigner) -> Result<Request, Box<dyn Error>> {
+    let url = url::Url::parse(ws_url)?;
+    let signed_request = signer.sign(&SubscribeRequest {
+        game_id: game_id_from_url(&url),
+    });
+    let mut request = ws_url.into_client_request()?;
+    request.headers_mut().insert(
+        SIGNED_REQUEST_HEADER,
+        HeaderValue::from_str(&serde_json::to_string(&signed_request)?)?,
+    );
+    Ok(request)
+}
+
+// ws urls end with the id of the game, e.g. ws://192.168.1.2:12044/ws/0x8c2a
+fn game_id_from_url(url: &url::Url) -> String {
+    url.path_segments()
+        .and_then(|segments| segments.last())
+        .unwrap_or_default()
+        .to_string()
+}
+
 #[derive(Deserialize)]
 struct Player {
     player_id: u64,
     identifier: String,
 }
 
-// get player identifier
-pub fn read_player_data() -> Option<String> {
+fn read_player() -> Option<Player> {
     let mut file = File::open(Path::new("Player.toml")).ok()?;
     let mut content = String::new();
     file.read_to_string(&mut content).ok()?;
-    let player_info: Player = Toml::from_str(&content).ok()?;
-    Some(player_info.identifier)
+    Toml::from_str(&content).ok()
+}
+
+// get player identifier
+pub fn read_player_data() -> Option<String> {
+    read_player().map(|player_info| player_info.identifier)
+}
+
+// get player id
+pub fn read_player_id() -> Option<u64> {
+    read_player().map(|player_info| player_info.player_id)
 }
 
 
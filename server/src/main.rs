@@ -0,0 +1,181 @@
+use aze_lib::broadcast::{registry, start_server, ServerConfig};
+use aze_lib::client::node_rpc_config;
+use aze_lib::constants::{
+    BUY_IN_AMOUNT, DEFAULT_ACTION_TIMEOUT, DEFAULT_SERVER_PORT, DEFAULT_SPECTATOR_DELAY,
+};
+use aze_lib::gamestate::{Ante, BettingStructure};
+use aze_lib::hands::GameVariant;
+use aze_lib::host::{create_aze_game_account, run_host, HostedGame};
+use aze_lib::lobby::FormedTable;
+use aze_types::lobby::LobbyUpdate;
+use clap::Parser;
+use std::fs;
+use std::net::IpAddr;
+use std::path::{Path, PathBuf};
+use tokio::sync::watch;
+use tokio::task::{JoinHandle, LocalSet};
+
+// games being hosted, so a restarted server picks them up again
+const GAMES_FILE: &str = "games.json";
+// snapshots of each game's off-chain state and event log
+const STATE_DIR: &str = "games";
+
+/// Hosts aze game tables and runs the dealer loop of every game account in the local store.
+/// Run it from the directory holding the store the games are created with.
+#[derive(Parser)]
+#[command(author, version, about, long_about = None)]
+struct Args {
+    // address the server listens on, the first non-loopback IPv4 by default
+    #[arg(long)]
+    bind_address: Option<IpAddr>,
+
+    // 0 picks a free port
+    #[arg(long, default_value_t = DEFAULT_SERVER_PORT)]
+    port: u16,
+
+    // url players connect to when it differs from the bound address, e.g. behind NAT
+    #[arg(long)]
+    public_url: Option<String>,
+
+    // seconds spectators lag behind the table
+    #[arg(long, default_value_t = DEFAULT_SPECTATOR_DELAY)]
+    spectator_delay: u64,
+
+    // where the hosted games and their state are kept between restarts
+    #[arg(long, default_value = ".")]
+    data_dir: PathBuf,
+
+    // buy-in of the tables the lobby fills
+    #[arg(long, default_value_t = BUY_IN_AMOUNT)]
+    buy_in: u64,
+
+    // seconds a player has to act at lobby tables, 0 to wait forever
+    #[arg(long, default_value_t = DEFAULT_ACTION_TIMEOUT)]
+    action_timeout: u64,
+}
+
+#[tokio::main]
+async fn main() {
+    let args = Args::parse();
+    if let Err(error) = serve(args).await {
+        println!("{}", error);
+    }
+}
+
+async fn serve(args: Args) -> Result<(), String> {
+    let games_path = args.data_dir.join(GAMES_FILE);
+    let mut games = load_games(&games_path)?;
+    registry().persist_to(args.data_dir.join(STATE_DIR))?;
+    let server_config = ServerConfig {
+        bind_address: args.bind_address,
+        port: args.port,
+        public_url: args.public_url,
+        spectator_delay: args.spectator_delay,
+    };
+
+    let (shutdown, shutdown_rx) = watch::channel(false);
+    let mut server_shutdown = shutdown_rx.clone();
+    let (bound_address, base_url) = start_server(&server_config, async move {
+        let _ = server_shutdown.changed().await;
+    })?;
+    println!("Game server listening on {}, advertised as {}", bound_address, base_url);
+    let mut new_games = registry().accept_games();
+    let mut formed_tables = registry().accept_lobby(node_rpc_config());
+    let (buy_in, action_timeout) = (args.buy_in, args.action_timeout);
+
+    // the client behind every host loop is not Send
+    let local_set = LocalSet::new();
+    local_set
+        .run_until(async {
+            let mut hosts: Vec<JoinHandle<()>> = vec![];
+            for hosted_game in games.iter() {
+                println!("Recovering game {}", hosted_game.game_id);
+                registry().host_game(
+                    hosted_game.game_id.clone(),
+                    hosted_game.player_ids.clone(),
+                    hosted_game.buy_in,
+                    hosted_game.small_blind as u64,
+                );
+                hosts.push(tokio::task::spawn_local(run_host(
+                    hosted_game.clone(),
+                    shutdown_rx.clone(),
+                )));
+            }
+
+            loop {
+                tokio::select! {
+                    Some(hosted_game) = new_games.recv() => {
+                        println!("Hosting game {}", hosted_game.game_id);
+                        games.push(hosted_game.clone());
+                        if let Err(e) = save_games(&games_path, &games) {
+                            eprintln!("{}", e);
+                        }
+                        hosts.push(tokio::task::spawn_local(run_host(
+                            hosted_game,
+                            shutdown_rx.clone(),
+                        )));
+                    }
+                    Some(formed_table) = formed_tables.recv() => {
+                        tokio::task::spawn_local(seat_table(formed_table, buy_in, action_timeout));
+                    }
+                    _ = tokio::signal::ctrl_c() => break,
+                }
+            }
+
+            // let every host loop finish the transaction it is on before exiting
+            println!("Shutting down");
+            let _ = shutdown.send(true);
+            for host in hosts {
+                let _ = host.await;
+            }
+        })
+        .await;
+
+    save_games(&games_path, &games)
+}
+
+// creates the game account of a table the lobby filled and sends its players there
+async fn seat_table(formed_table: FormedTable, buy_in: u64, action_timeout: u64) {
+    let player_ids = formed_table.player_ids();
+    let small_blind = formed_table.stakes.small_blind;
+    let game_account_id =
+        match create_aze_game_account(player_ids.clone(), small_blind, buy_in, None, None, None, BettingStructure::NoLimit, GameVariant::Holdem, Ante::None, false).await {
+            Ok(game_account_id) => game_account_id,
+            Err(e) => {
+                formed_table.notify(LobbyUpdate::Rejected {
+                    reason: format!("Error creating game account: {}", e),
+                });
+                return;
+            }
+        };
+
+    let hosted_game = HostedGame {
+        game_id: game_account_id.to_string(),
+        player_ids,
+        small_blind,
+        buy_in,
+        action_timeout,
+        blind_schedule: None,
+    };
+    match registry().host_new_game(hosted_game.clone()) {
+        Ok(ws_url) => formed_table.notify(LobbyUpdate::Seated {
+            game_id: hosted_game.game_id,
+            ws_url,
+        }),
+        Err(reason) => formed_table.notify(LobbyUpdate::Rejected { reason }),
+    }
+}
+
+fn load_games(games_path: &Path) -> Result<Vec<HostedGame>, String> {
+    match fs::read_to_string(games_path) {
+        Ok(games_data) => serde_json::from_str(&games_data)
+            .map_err(|e| format!("Failed to parse {}: {}", games_path.display(), e)),
+        Err(_) => Ok(vec![]),
+    }
+}
+
+fn save_games(games_path: &Path, games: &[HostedGame]) -> Result<(), String> {
+    let games_data = serde_json::to_string_pretty(games).unwrap();
+    fs::write(games_path, games_data)
+        .map_err(|e| format!("Failed to write {}: {}", games_path.display(), e))
+}
@@ -28,11 +28,20 @@ pub enum ActionType {
     Raise,
     SmallBlind,
     BigBlind,
+    // twice the big blind, posted for the player after the big blind by the table
+    Straddle,
     Call,
     Check,
     Fold,
 }
 
+#[derive(ValueEnum, Debug, PartialEq, Clone, Copy, Eq, Deserialize, Serialize)]
+pub enum StrategyType {
+    AlwaysCall,
+    TightAggressive,
+    Random,
+}
+
 impl ResponseError for GameActionError {
     fn error_response(&self) -> HttpResponse {
         HttpResponse::build(self.status_code())
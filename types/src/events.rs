@@ -0,0 +1,236 @@
+use crate::actions::ActionType;
+use serde::{Deserialize, Serialize};
+
+// bumped whenever a variant or field changes meaning, clients should ignore newer versions
+pub const EVENT_PROTOCOL_VERSION: u32 = 1;
+
+/// Events published by the game server over the websocket. Serialized as JSON tagged by
+/// `type`, e.g. `{"type":"turn_changed","player_id":1}`. Cards are the 1..=52 numbers used
+/// on-chain.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum GameEvent {
+    PlayerJoined {
+        player_id: u64,
+        identifier: String,
+    },
+    // seats are numbered from 0, the buy-in becomes the player's balance
+    SeatTaken {
+        player_id: u64,
+        seat: u64,
+        buy_in: u64,
+    },
+    SeatLeft {
+        player_id: u64,
+        seat: u64,
+        balance: u64,
+    },
+    SittingOut {
+        player_id: u64,
+        sitting_out: bool,
+    },
+    // the seated players were dealt a new hand
+    HandStarted {
+        player_ids: Vec<u64>,
+    },
+    PlayerActed {
+        player_id: u64,
+        action: ActionType,
+        amount: Option<u64>,
+    },
+    TurnChanged {
+        player_id: u64,
+    },
+    // value of the game account's phase slot, 0 is pre-flop
+    StreetAdvanced {
+        phase: u64,
+    },
+    CommunityCardsRevealed {
+        cards: Vec<u64>,
+    },
+    // hand type as committed on-chain, 1 is a royal flush and 10 a high card
+    HandCommitted {
+        player_id: u64,
+        hand_type: u64,
+    },
+    // in hi-lo games the low winners split half of every pot, empty when no hand made a low
+    ShowdownResult {
+        winners: Vec<u64>,
+        hand_type: u64,
+        pot: u64,
+        #[serde(default)]
+        low_winners: Vec<u64>,
+    },
+    GameEnded,
+    // tournament blinds went up, levels are counted from 1
+    BlindsRaised {
+        level: u64,
+        small_blind: u64,
+        big_blind: u64,
+    },
+    // the player ran out of chips and finished the tournament at `place`, 1 being the winner
+    PlayerEliminated {
+        player_id: u64,
+        place: u64,
+    },
+    // every player of the tournament, from the winner down
+    TournamentEnded {
+        placements: Vec<u64>,
+    },
+    // table talk, signed by the player and checked by the server before going out
+    Chat {
+        player_id: u64,
+        identifier: String,
+        message: String,
+    },
+}
+
+impl GameEvent {
+    /// The event as spectators see it: committed hand types stay hidden, as 0, until the
+    /// showdown announces the winning hand.
+    pub fn redacted(&self) -> GameEvent {
+        match self {
+            GameEvent::HandCommitted { player_id, .. } => GameEvent::HandCommitted {
+                player_id: *player_id,
+                hand_type: 0,
+            },
+            event => event.clone(),
+        }
+    }
+}
+
+/// What actually goes over the wire, the event flattened next to its envelope. Sequence numbers
+/// start at 1 and grow by one with every event of the game.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct EventMessage {
+    pub version: u32,
+    pub game_id: String,
+    pub sequence: u64,
+    #[serde(flatten)]
+    pub event: GameEvent,
+}
+
+impl EventMessage {
+    pub fn new(game_id: String, sequence: u64, event: GameEvent) -> Self {
+        Self {
+            version: EVENT_PROTOCOL_VERSION,
+            game_id,
+            sequence,
+            event,
+        }
+    }
+
+    pub fn redacted(&self) -> Self {
+        Self {
+            event: self.event.redacted(),
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_events_are_tagged_in_snake_case() {
+        let event = GameEvent::PlayerActed {
+            player_id: 1,
+            action: ActionType::Raise,
+            amount: Some(20),
+        };
+        assert_eq!(
+            serde_json::to_value(&event).unwrap(),
+            json!({"type": "player_acted", "player_id": 1, "action": "Raise", "amount": 20})
+        );
+        assert_eq!(
+            serde_json::to_value(&GameEvent::GameEnded).unwrap(),
+            json!({"type": "game_ended"})
+        );
+
+        let event: GameEvent =
+            serde_json::from_str(r#"{"type":"turn_changed","player_id":3}"#).unwrap();
+        assert_eq!(event, GameEvent::TurnChanged { player_id: 3 });
+        // results sent before hi-lo games had no low winners
+        let event: GameEvent = serde_json::from_str(
+            r#"{"type":"showdown_result","winners":[2],"hand_type":9,"pot":40}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            event,
+            GameEvent::ShowdownResult {
+                winners: vec![2],
+                hand_type: 9,
+                pot: 40,
+                low_winners: vec![],
+            }
+        );
+        assert!(serde_json::from_str::<GameEvent>(r#"{"type":"TurnChanged","player_id":3}"#).is_err());
+    }
+
+    #[test]
+    fn test_message_flattens_the_event_into_its_envelope() {
+        let message = EventMessage::new(
+            String::from("0x1234"),
+            7,
+            GameEvent::StreetAdvanced { phase: 1 },
+        );
+        let value = serde_json::to_value(&message).unwrap();
+        assert_eq!(
+            value,
+            json!({
+                "version": EVENT_PROTOCOL_VERSION,
+                "game_id": "0x1234",
+                "sequence": 7,
+                "type": "street_advanced",
+                "phase": 1,
+            })
+        );
+        let parsed: EventMessage = serde_json::from_value(value).unwrap();
+        assert_eq!(parsed, message);
+
+        let text = serde_json::to_string(&message).unwrap();
+        assert_eq!(serde_json::from_str::<EventMessage>(&text).unwrap(), message);
+        // an envelope without an event is not a message
+        assert!(serde_json::from_value::<EventMessage>(
+            json!({"version": 1, "game_id": "0x1234", "sequence": 1})
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_redacted_hides_committed_hands() {
+        let committed = EventMessage::new(
+            String::from("0x1234"),
+            3,
+            GameEvent::HandCommitted {
+                player_id: 2,
+                hand_type: 4,
+            },
+        );
+        let redacted = committed.redacted();
+        assert_eq!(
+            redacted.event,
+            GameEvent::HandCommitted {
+                player_id: 2,
+                hand_type: 0,
+            }
+        );
+        assert_eq!(redacted.sequence, 3);
+        assert_eq!(redacted.game_id, committed.game_id);
+
+        // the board and the showdown are public
+        let board = GameEvent::CommunityCardsRevealed {
+            cards: vec![1, 14, 27],
+        };
+        assert_eq!(board.redacted(), board);
+        let showdown = GameEvent::ShowdownResult {
+            winners: vec![2],
+            hand_type: 4,
+            pot: 40,
+            low_winners: vec![],
+        };
+        assert_eq!(showdown.redacted(), showdown);
+    }
+}
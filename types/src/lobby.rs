@@ -0,0 +1,62 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Blinds a player wants to play, written `small/big` like `5/10`. The big blind is always
+/// twice the small one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct Stakes {
+    pub small_blind: u8,
+    pub big_blind: u8,
+}
+
+impl FromStr for Stakes {
+    type Err = String;
+
+    fn from_str(stakes: &str) -> Result<Self, Self::Err> {
+        let (small_blind, big_blind) = stakes
+            .split_once('/')
+            .ok_or_else(|| format!("Expected stakes like 5/10, got {}", stakes))?;
+        let small_blind: u8 = small_blind
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid small blind {}", small_blind))?;
+        let big_blind: u8 = big_blind
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid big blind {}", big_blind))?;
+        if small_blind == 0 || small_blind.checked_mul(2) != Some(big_blind) {
+            return Err(format!(
+                "The big blind must be twice the small blind, got {}/{}",
+                small_blind, big_blind
+            ));
+        }
+        Ok(Self {
+            small_blind,
+            big_blind,
+        })
+    }
+}
+
+impl fmt::Display for Stakes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.small_blind, self.big_blind)
+    }
+}
+
+/// First message a player sends on the lobby websocket, signed with their key.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LobbyJoinRequest {
+    pub player_id: u64,
+    pub identifier: String,
+    pub stakes: Stakes,
+}
+
+/// What the lobby tells a waiting player, tagged by `type` like game events.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum LobbyUpdate {
+    Waiting { waiting: usize, needed: usize },
+    Seated { game_id: String, ws_url: String },
+    Rejected { reason: String },
+}